@@ -0,0 +1,11 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use bq2cst::{cst::Node, error::BQ2CSTError, lexer::Lexer, parser::Parser};
+
+pub fn parse(code: String) -> Result<Vec<Node>, BQ2CSTError> {
+    let tokens = Lexer::new(code).tokenize_code()?;
+    Parser::new(tokens).parse_code()
+}
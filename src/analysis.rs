@@ -0,0 +1,963 @@
+#[cfg(test)]
+mod tests;
+
+use crate::constants;
+use crate::cst::{ContentType, Node, NodeType};
+use crate::diagnostic::{Diagnostic, Span};
+use crate::error::ErrorCode;
+use crate::functions;
+use crate::literal::{self, LiteralValue};
+use crate::parser;
+use crate::precedence::{self, Associativity};
+use crate::scope::{self, BindingKind, Resolution};
+use crate::token::{LetterCase, Token};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+/// Statements which may modify all the rows of a table by mistake.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum DangerKind {
+    DeleteWithoutWhere,
+    UpdateWithoutWhere,
+    Truncate,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct DangerousStatement {
+    pub kind: DangerKind,
+    /// from the first keyword to the end of the statement (including `;`)
+    pub span: Span,
+}
+
+/// How a node was laid out in the original code (see `original_layout`).
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OriginalLayout {
+    /// `true` if all the tokens of the node except comments are on the same line.
+    pub single_line: bool,
+    /// The keys of the children starting with a keyword (e.g. `from`, `where`)
+    /// which were placed at the beginning of a line.
+    pub own_line_clauses: Vec<String>,
+}
+
+/// The object created or modified by a statement and the tables it reads (see `lineage`).
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Lineage {
+    /// e.g. the table of `CREATE TABLE` and `INSERT`, the view of `CREATE VIEW`
+    pub target: Option<String>,
+    pub sources: Vec<String>,
+}
+
+/// The number of tokens written in each letter case.
+#[derive(PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CaseCounts {
+    pub upper: usize,
+    pub lower: usize,
+    pub mixed: usize,
+}
+
+impl CaseCounts {
+    fn add(&mut self, token: &Token) {
+        match token.letter_case() {
+            Some(LetterCase::Upper) => self.upper += 1,
+            Some(LetterCase::Lower) => self.lower += 1,
+            Some(LetterCase::Mixed) => self.mixed += 1,
+            None => (),
+        }
+    }
+    /// Returns the most used case (`Upper` > `Lower` > `Mixed` if they are tied)
+    /// or `None` if nothing is counted.
+    pub fn dominant(&self) -> Option<LetterCase> {
+        let mut res = None;
+        let mut max = 0;
+        for (case, count) in [
+            (LetterCase::Upper, self.upper),
+            (LetterCase::Lower, self.lower),
+            (LetterCase::Mixed, self.mixed),
+        ] {
+            if max < count {
+                res = Some(case);
+                max = count;
+            }
+        }
+        res
+    }
+}
+
+/// See `casing_stats`.
+#[derive(PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct CasingStats {
+    /// the same tokens as `keyword_tokens`
+    pub keywords: CaseCounts,
+    /// the names of called functions (e.g. `COUNT`, `SAFE.DIVIDE`)
+    pub functions: CaseCounts,
+    /// the other identifiers except quoted ones
+    pub identifiers: CaseCounts,
+}
+
+/// What an item of `GROUP BY` refers to (see `grouping_items`).
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum GroupingKind {
+    /// e.g. `GROUP BY 1`
+    Ordinal,
+    /// an alias defined in the select list
+    Alias,
+    Expr,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct GroupingItem {
+    pub kind: GroupingKind,
+    pub span: Span,
+}
+
+/// Returns the table paths referenced by `node` in order of appearance.
+/// FROM items, DML targets and `TABLE t` arguments are collected,
+/// backticks are removed and CTE names are excluded.
+pub fn referenced_tables(node: &Node) -> Vec<String> {
+    dedup_by_position(table_references(node))
+}
+
+/// Returns the named query parameters (e.g. `@param`) used in `node`
+/// in order of appearance. System variables such as `@@time_zone`
+/// and positional parameters (`?`) are not included.
+pub fn referenced_parameters(node: &Node) -> Vec<String> {
+    let mut params = Vec::new();
+    collect_parameters(node, &mut params);
+    dedup_by_position(params)
+}
+
+/// Splits a table path (e.g. `` `project.dataset.table` ``, `` `project`.dataset.table ``,
+/// `project-id.dataset.table`) into its parts without backticks.
+/// Returns an empty vector if `node` is not an identifier path.
+pub fn split_table_path(node: &Node) -> Vec<String> {
+    match identifier_path(node) {
+        Some((_, path)) => path.split('.').map(|s| s.to_string()).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Counts the letter cases of keywords, function names and identifiers in `stmts`,
+/// which is useful to detect the style of the code (see `CaseCounts::dominant`).
+pub fn casing_stats(stmts: &[Node]) -> CasingStats {
+    let mut stats = CasingStats::default();
+    for stmt in stmts {
+        for token in keyword_tokens(stmt) {
+            stats.keywords.add(token);
+        }
+        collect_casing_stats(stmt, false, &mut stats);
+    }
+    stats
+}
+
+/// Returns the object which `stmt` creates or modifies (CREATE, ALTER, DROP, LOAD and DML)
+/// and the tables it reads, which is enough to build a lineage graph of ELT scripts.
+/// The target is not regarded as a source unless it is referenced again
+/// (e.g. `INSERT INTO t SELECT * FROM t`).
+pub fn lineage(stmt: &Node) -> Lineage {
+    let key = match stmt.node_type {
+        NodeType::CreateTableStatement
+        | NodeType::CreateViewStatement
+        | NodeType::CreateFunctionStatement
+        | NodeType::CreateProcedureStatement
+        | NodeType::CreateSchemaStatement
+        | NodeType::CreateModelStatement
+        | NodeType::AlterTableStatement
+        | NodeType::AlterColumnStatement
+        | NodeType::AlterViewStatement
+        | NodeType::AlterSchemaStatement
+        | NodeType::AlterModelStatement
+        | NodeType::DropStatement
+        | NodeType::LoadStatement => "ident",
+        NodeType::InsertStatement => "target_name",
+        NodeType::UpdateStatement
+        | NodeType::DeleteStatement
+        | NodeType::MergeStatement
+        | NodeType::TruncateStatement => "table_name",
+        _ => "",
+    };
+    let target = match stmt.children.get(key) {
+        Some(ContentType::Node(n)) => identifier_path(n),
+        _ => None,
+    };
+    let mut refs = table_references(stmt);
+    if let Some((pos, _)) = &target {
+        refs.retain(|(p, _)| p != pos);
+    }
+    Lineage {
+        target: target.map(|(_, path)| path),
+        sources: dedup_by_position(refs),
+    }
+}
+
+/// Returns DELETE and UPDATE statements without WHERE clause and TRUNCATE statements in `node`
+/// (including the ones in scripting blocks) in order of appearance.
+/// `WHEN MATCHED THEN UPDATE` in MERGE statements is not included.
+pub fn dangerous_statements(node: &Node) -> Vec<DangerousStatement> {
+    let mut res = Vec::new();
+    collect_dangerous_statements(node, &mut res);
+    res.sort_by_key(|d| (d.span.start_line, d.span.start_column));
+    res
+}
+
+/// Returns the keyword tokens in `node` in order of appearance.
+/// The tokens of keyword nodes (e.g. `Keyword`, `KeywordWithExpr`), reserved keywords
+/// and the first keywords of statements are included, but types (e.g. `INT64`) are not.
+pub fn keyword_tokens(node: &Node) -> Vec<&Token> {
+    let mut tokens = Vec::new();
+    collect_keywords(node, &mut tokens);
+    tokens.sort_by_key(|t| (t.line, t.column));
+    tokens
+}
+
+/// Returns the letter case shared by all the keywords in `node`.
+/// `LetterCase::Mixed` is returned if they differ (e.g. `SELECT 1 from t`)
+/// and `None` if there is no keyword.
+pub fn keyword_case(node: &Node) -> Option<LetterCase> {
+    let mut res = None;
+    for token in keyword_tokens(node) {
+        match (res, token.letter_case()) {
+            (None, case) => res = case,
+            (Some(prev), Some(case)) if prev != case => return Some(LetterCase::Mixed),
+            _ => (),
+        }
+    }
+    res
+}
+
+/// Returns the layout of `node` in the original code, which formatters can use to
+/// preserve line breaks made by the user (e.g. `SELECT 1 FROM t` kept on one line,
+/// or `WHERE` placed on its own line).
+/// Only the tokens in `node` are taken into account,
+/// so a clause following another statement on the same line still starts a line.
+pub fn original_layout(node: &Node) -> OriginalLayout {
+    let mut tokens = Vec::new();
+    collect_non_comment_tokens(node, &mut tokens);
+    let single_line = tokens.iter().all(|t| {
+        let span = Span::from_token(t);
+        span.start_line == tokens[0].line && span.end_line == tokens[0].line
+    });
+    let mut own_line_clauses = Vec::new();
+    for (key, content) in &node.children {
+        let child = match content {
+            ContentType::Node(n) => n,
+            ContentType::NodeVec(_) => continue,
+        };
+        let token = match &child.token {
+            Some(t) if !t.is_comment() && is_keyword(child, t) => t,
+            _ => continue,
+        };
+        let starts_line = tokens
+            .iter()
+            .all(|t| t.line != token.line || token.column <= t.column);
+        if starts_line {
+            own_line_clauses.push(key.clone());
+        }
+    }
+    own_line_clauses.sort_by_key(|key| match node.children.get(key) {
+        Some(ContentType::Node(n)) => position(n),
+        _ => (usize::MAX, usize::MAX),
+    });
+    OriginalLayout {
+        single_line,
+        own_line_clauses,
+    }
+}
+
+/// Returns the spans (from `(` to `)`) of the parenthesized expressions in `node`
+/// which can be removed without changing the structure of the CST,
+/// e.g. `(a * b) + c` and `((x))`.
+/// Only operands of operators and doubled parentheses are checked
+/// because parentheses are required elsewhere (e.g. `CREATE FUNCTION f() AS (1)`).
+pub fn redundant_parentheses(node: &Node) -> Vec<Span> {
+    let mut res = Vec::new();
+    collect_redundant_parentheses(node, &mut res);
+    res.sort_by_key(|s| (s.start_line, s.start_column));
+    res
+}
+
+/// Returns the operands of a chain of the same binary operator,
+/// e.g. `a`, `b` and `c` for `a || b || c`, which is parsed as `(a || b) || c`.
+/// Parenthesized operands are not flattened.
+/// `vec![node]` is returned if `node` is not a binary operator.
+pub fn flatten_binary_operator(node: &Node) -> Vec<&Node> {
+    let mut res = Vec::new();
+    match &node.token {
+        Some(t) if is_plain_binary_operator(node) => {
+            collect_binary_operands(node, &t.literal.to_uppercase(), &mut res)
+        }
+        _ => res.push(node),
+    }
+    res
+}
+
+/// Checks the calls of builtin functions (see `crate::functions`).
+/// * W005: unknown function
+/// * W006: wrong number of arguments
+///
+/// Functions created by `CREATE TEMP FUNCTION` in `stmts`, functions in other namespaces
+/// (e.g. `dataset.udf()`) and quoted names are regarded as user-defined functions.
+pub fn check_function_calls(stmts: &[Node]) -> Vec<Diagnostic> {
+    let mut udfs = Vec::new();
+    let mut res = Vec::new();
+    for stmt in stmts {
+        collect_udf_names(stmt, &mut udfs);
+    }
+    for stmt in stmts {
+        collect_function_diagnostics(stmt, &udfs, &mut res);
+    }
+    res.sort_by_key(|d| (d.span.start_line, d.span.start_column));
+    res
+}
+
+/// Checks the values of `DATE`, `DATETIME`, `TIME` and `TIMESTAMP` literals
+/// (e.g. `DATE '2024-02-30'`, see `literal::validate_datetime_literal`).
+/// * W007: invalid typed literal
+pub fn check_typed_literals(stmts: &[Node]) -> Vec<Diagnostic> {
+    let mut res = Vec::new();
+    for stmt in stmts {
+        collect_typed_literal_diagnostics(stmt, &mut res);
+    }
+    res.sort_by_key(|d| (d.span.start_line, d.span.start_column));
+    res
+}
+
+/// Checks that DECLARE comes before any other statement in a script or `BEGIN ... END` block
+/// (`ParseOptions.strict_declare` makes the parser reject it instead).
+/// * P009: misplaced DECLARE
+pub fn check_declare(stmts: &[Node]) -> Vec<Diagnostic> {
+    let mut res = Vec::new();
+    collect_declare_diagnostics(stmts, true, &mut res);
+    res.sort_by_key(|d| (d.span.start_line, d.span.start_column));
+    res
+}
+
+/// Returns the items of every `GROUP BY` in `stmt` in order of appearance.
+/// The items in `ROLLUP`, `CUBE` and `GROUPING SETS` are returned one by one
+/// (e.g. `1` and `x` for `GROUPING SETS ((1, x))`), and `GROUP BY ALL` has no items.
+pub fn grouping_items(stmt: &Node) -> Vec<GroupingItem> {
+    let res = scope::resolve(stmt);
+    let mut items = Vec::new();
+    collect_grouping_items(stmt, &res, &mut items);
+    items.sort_by_key(|i| (i.span.start_line, i.span.start_column));
+    items
+}
+
+// ----- utilities -----
+pub(crate) fn child_nodes(node: &Node) -> Vec<(&String, &Node)> {
+    let mut res = Vec::new();
+    for (k, content) in &node.children {
+        match content {
+            ContentType::Node(n) => res.push((k, n)),
+            ContentType::NodeVec(ns) => {
+                for n in ns {
+                    res.push((k, n));
+                }
+            }
+        }
+    }
+    res
+}
+
+fn position(node: &Node) -> (usize, usize) {
+    match &node.token {
+        Some(t) => (t.line, t.column),
+        None => (usize::MAX, usize::MAX),
+    }
+}
+
+fn dedup_by_position(mut items: Vec<((usize, usize), String)>) -> Vec<String> {
+    items.sort_by_key(|(pos, _)| *pos);
+    let mut res: Vec<String> = Vec::new();
+    for (_, item) in items {
+        if !res.contains(&item) {
+            res.push(item);
+        }
+    }
+    res
+}
+
+fn unquote(literal: &str) -> String {
+    literal::decode_identifier_literal(literal)
+        .unwrap_or_else(|| literal.trim_matches('`').to_string())
+}
+
+/// Concatenates the parts of an identifier path (`a.b.c`, `project-id.dataset.table`).
+/// Returns the path and the position of its first token.
+pub(crate) fn identifier_path(node: &Node) -> Option<((usize, usize), String)> {
+    match node.node_type {
+        NodeType::Identifier => {
+            let token = node.token.as_ref()?;
+            Some((position(node), unquote(&token.literal)))
+        }
+        NodeType::MultiTokenIdentifier => {
+            let token = node.token.as_ref()?;
+            let mut path = unquote(&token.literal);
+            if let Some(ContentType::NodeVec(idents)) = node.children.get("trailing_idents") {
+                for ident in idents {
+                    path.push_str(&unquote(&ident.token.as_ref()?.literal));
+                }
+            }
+            Some((position(node), path))
+        }
+        NodeType::DotOperator => {
+            let left = match node.children.get("left") {
+                Some(ContentType::Node(n)) => identifier_path(n)?,
+                _ => return None,
+            };
+            let right = match node.children.get("right") {
+                Some(ContentType::Node(n)) => identifier_path(n)?,
+                _ => return None,
+            };
+            Some((left.0, format!("{}.{}", left.1, right.1)))
+        }
+        _ => None,
+    }
+}
+
+// ----- tables -----
+/// Same as `referenced_tables` but every occurrence is returned
+/// together with the position of its first token.
+pub(crate) fn table_references(node: &Node) -> Vec<((usize, usize), String)> {
+    let mut ctes = Vec::new();
+    collect_cte_names(node, &mut ctes);
+    let mut tables = Vec::new();
+    collect_tables(node, &ctes, &mut tables);
+    tables
+}
+
+fn collect_cte_names(node: &Node, ctes: &mut Vec<String>) {
+    if node.node_type == NodeType::WithQuery {
+        if let Some(t) = &node.token {
+            ctes.push(unquote(&t.literal));
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_cte_names(child, ctes);
+    }
+}
+
+fn is_table_position(parent: &Node, key: &str) -> bool {
+    match parent.node_type {
+        NodeType::SelectStatement => key == "from",
+        NodeType::UpdateStatement => key == "table_name" || key == "from",
+        NodeType::DeleteStatement | NodeType::TruncateStatement => key == "table_name",
+        NodeType::InsertStatement => key == "target_name",
+        NodeType::MergeStatement => key == "table_name" || key == "using",
+        NodeType::CreateTableStatement => key == "source_table" || key == "clone",
+        NodeType::UnaryOperator => {
+            key == "right"
+                && match &parent.token {
+                    Some(t) => t.is("TABLE"),
+                    None => false,
+                }
+        }
+        _ => false,
+    }
+}
+
+fn collect_tables(node: &Node, ctes: &[String], tables: &mut Vec<((usize, usize), String)>) {
+    for (key, child) in child_nodes(node) {
+        if is_table_position(node, key) {
+            collect_from_item(child, ctes, tables);
+        } else {
+            collect_tables(child, ctes, tables);
+        }
+    }
+}
+
+fn collect_from_item(node: &Node, ctes: &[String], tables: &mut Vec<((usize, usize), String)>) {
+    match node.node_type {
+        NodeType::Identifier | NodeType::MultiTokenIdentifier | NodeType::DotOperator => {
+            if let Some((pos, path)) = identifier_path(node) {
+                if !ctes.iter().any(|c| c.eq_ignore_ascii_case(&path)) {
+                    tables.push((pos, path));
+                }
+            }
+            // e.g. FOR SYSTEM_TIME AS OF (SELECT ...)
+            for (key, child) in child_nodes(node) {
+                if !["left", "right", "trailing_idents"].contains(&key.as_str()) {
+                    collect_tables(child, ctes, tables);
+                }
+            }
+        }
+        NodeType::JoinOperator => {
+            for (key, child) in child_nodes(node) {
+                if key == "left" || key == "right" {
+                    collect_from_item(child, ctes, tables);
+                } else {
+                    collect_tables(child, ctes, tables);
+                }
+            }
+        }
+        NodeType::GroupedExpr | NodeType::KeywordWithExpr => {
+            for (key, child) in child_nodes(node) {
+                if key == "expr" {
+                    collect_from_item(child, ctes, tables);
+                } else {
+                    collect_tables(child, ctes, tables);
+                }
+            }
+        }
+        _ => collect_tables(node, ctes, tables),
+    }
+}
+
+// ----- dangerous statements -----
+fn collect_dangerous_statements(node: &Node, res: &mut Vec<DangerousStatement>) {
+    let kind = match node.node_type {
+        NodeType::DeleteStatement if !node.children.contains_key("where") => {
+            Some(DangerKind::DeleteWithoutWhere)
+        }
+        NodeType::UpdateStatement if !node.children.contains_key("where") => {
+            Some(DangerKind::UpdateWithoutWhere)
+        }
+        NodeType::TruncateStatement => Some(DangerKind::Truncate),
+        NodeType::MergeStatement => return,
+        _ => None,
+    };
+    if let Some(kind) = kind {
+        if let Some(span) = node_span(node) {
+            res.push(DangerousStatement { kind, span });
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_dangerous_statements(child, res);
+    }
+}
+
+fn node_span(node: &Node) -> Option<Span> {
+    let mut tokens = Vec::new();
+    collect_non_comment_tokens(node, &mut tokens);
+    tokens_span(&tokens)
+}
+
+/// Same as `node_span` but the `comma` child (e.g. of an item of a list) is not included.
+fn node_span_without_comma(node: &Node) -> Option<Span> {
+    let mut tokens = Vec::new();
+    if let Some(t) = node.token.as_ref().filter(|t| !t.is_comment()) {
+        tokens.push(t);
+    }
+    for (key, child) in child_nodes(node) {
+        if key != "comma" {
+            collect_non_comment_tokens(child, &mut tokens);
+        }
+    }
+    tokens_span(&tokens)
+}
+
+fn tokens_span(tokens: &[&Token]) -> Option<Span> {
+    let first = tokens.iter().min_by_key(|t| (t.line, t.column))?;
+    let last = tokens.iter().max_by_key(|t| (t.line, t.column))?;
+    let end = Span::from_token(last);
+    Some(Span::new(
+        first.line,
+        first.column,
+        end.end_line,
+        end.end_column,
+    ))
+}
+
+fn collect_non_comment_tokens<'a>(node: &'a Node, tokens: &mut Vec<&'a Token>) {
+    if let Some(t) = &node.token {
+        if !t.is_comment() {
+            tokens.push(t);
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_non_comment_tokens(child, tokens);
+    }
+}
+
+// ----- keywords -----
+fn is_keyword(node: &Node, token: &Token) -> bool {
+    match node.node_type {
+        NodeType::Keyword
+        | NodeType::KeywordSequence
+        | NodeType::KeywordWithExpr
+        | NodeType::KeywordWithExprs
+        | NodeType::KeywordWithType
+        | NodeType::KeywordWithGroupedXXX
+        | NodeType::KeywordWithStatement
+        | NodeType::KeywordWithStatements
+        | NodeType::MatchRecognizeClause => true,
+        // these nodes hold names
+        NodeType::Identifier
+        | NodeType::MultiTokenIdentifier
+        | NodeType::WithQuery
+        | NodeType::TypeDeclaration
+        | NodeType::IdentWithOptions
+        | NodeType::WindowExpr
+        | NodeType::PatternDefinition
+        | NodeType::Type
+        | NodeType::StringLiteral
+        | NodeType::Comment => false,
+        _ => {
+            let literal = token.literal.to_uppercase();
+            token.is_reserved_keyword() || constants::STATEMENT_KEYWORDS.contains(&literal.as_str())
+        }
+    }
+}
+
+fn collect_keywords<'a>(node: &'a Node, tokens: &mut Vec<&'a Token>) {
+    if let Some(t) = &node.token {
+        if is_keyword(node, t) {
+            tokens.push(t);
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_keywords(child, tokens);
+    }
+}
+
+// ----- binary operator -----
+/// `IS NOT`, `NOT LIKE` and `= ANY` are excluded.
+fn is_plain_binary_operator(node: &Node) -> bool {
+    node.node_type == NodeType::BinaryOperator
+        && !node.children.contains_key("not")
+        && !node.children.contains_key("quantifier")
+}
+
+fn collect_binary_operands<'a>(node: &'a Node, operator: &str, res: &mut Vec<&'a Node>) {
+    let same = is_plain_binary_operator(node)
+        && node
+            .token
+            .as_ref()
+            .is_some_and(|t| t.literal.to_uppercase() == operator);
+    if !same {
+        res.push(node);
+        return;
+    }
+    for key in ["left", "right"] {
+        if let Some(ContentType::Node(n)) = node.children.get(key) {
+            collect_binary_operands(n, operator, res);
+        }
+    }
+}
+
+// ----- parentheses -----
+fn collect_redundant_parentheses(node: &Node, res: &mut Vec<Span>) {
+    for (key, child) in child_nodes(node) {
+        if child.node_type == NodeType::GroupedExpr && is_redundant_group(node, key, child) {
+            if let Some(span) = group_span(child) {
+                res.push(span);
+            }
+        }
+        collect_redundant_parentheses(child, res);
+    }
+}
+
+fn is_redundant_group(parent: &Node, key: &str, group: &Node) -> bool {
+    let inner = match group.children.get("expr") {
+        Some(ContentType::Node(n)) => n,
+        _ => return false,
+    };
+    if parent.node_type == NodeType::GroupedExpr {
+        return true; // ((x))
+    }
+    if parent.node_type == NodeType::CallingFunction {
+        return false; // args of functions are not operands
+    }
+    // `x LIKE ANY (a)`, `x IN (a)`
+    if key == "right"
+        && (parent.children.contains_key("quantifier") || parent.node_type == NodeType::InOperator)
+    {
+        return false;
+    }
+    let outer = match precedence::node_precedence(parent) {
+        Some(p) => p,
+        None => return false,
+    };
+    let inner_precedence = match precedence::node_precedence(inner) {
+        Some(p) => p,
+        None => return true, // e.g. `(1) + 2`
+    };
+    let prefix = parent.node_type == NodeType::UnaryOperator
+        || parent.node_type == NodeType::BinaryOperator && key == "right";
+    if prefix && inner.node_type == NodeType::UnaryOperator {
+        let symbol = |n: &Node| {
+            n.token
+                .as_ref()
+                .is_some_and(|t| ["+", "-", "~"].contains(&t.literal.as_str()))
+        };
+        if symbol(parent) && symbol(inner) {
+            return false; // `-(-x)` and `a-(-b)` cannot be `--x` and `a--b`, which are comments
+        }
+    }
+    match key {
+        "left" => {
+            inner_precedence < outer
+                || inner_precedence == outer
+                    && precedence::associativity(outer) == Associativity::Left
+        }
+        // prefix operators can be repeated (e.g. `NOT NOT x`)
+        "right" if parent.node_type == NodeType::UnaryOperator => inner_precedence <= outer,
+        "right" | "right_min" | "right_max" => inner_precedence < outer,
+        _ => false,
+    }
+}
+
+fn group_span(group: &Node) -> Option<Span> {
+    let lparen = group.token.as_ref()?;
+    let rparen = match group.children.get("rparen") {
+        Some(ContentType::Node(n)) => n.token.as_ref()?,
+        _ => return None,
+    };
+    let end = Span::from_token(rparen);
+    Some(Span::new(
+        lparen.line,
+        lparen.column,
+        end.end_line,
+        end.end_column,
+    ))
+}
+
+// ----- parameters -----
+fn collect_parameters(node: &Node, params: &mut Vec<((usize, usize), String)>) {
+    if node.node_type == NodeType::Parameter {
+        if let Some(t) = &node.token {
+            if t.literal.starts_with('@') && !t.literal.starts_with("@@") {
+                params.push((position(node), t.literal.clone()));
+            }
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_parameters(child, params);
+    }
+}
+
+// ----- casing -----
+fn collect_casing_stats(node: &Node, is_function: bool, stats: &mut CasingStats) {
+    if node.node_type == NodeType::Identifier {
+        if let Some(token) = &node.token {
+            if !token.literal.starts_with('`') && !is_keyword(node, token) {
+                if is_function {
+                    stats.functions.add(token);
+                } else {
+                    stats.identifiers.add(token);
+                }
+            }
+        }
+    }
+    for (key, child) in child_nodes(node) {
+        let is_function = match node.node_type {
+            NodeType::CallingFunction | NodeType::CallingTableFunction => key == "func",
+            // e.g. SAFE.DIVIDE
+            NodeType::DotOperator => is_function && (key == "left" || key == "right"),
+            _ => false,
+        };
+        collect_casing_stats(child, is_function, stats);
+    }
+}
+
+// ----- functions -----
+fn collect_udf_names(node: &Node, udfs: &mut Vec<String>) {
+    if node.node_type == NodeType::CreateFunctionStatement {
+        if let Some(ContentType::Node(ident)) = node.children.get("ident") {
+            if let Some(name) = split_table_path(ident).last() {
+                udfs.push(name.to_uppercase());
+            }
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_udf_names(child, udfs);
+    }
+}
+
+/// Returns the name (e.g. `NET.HOST`) and its last token
+/// if `func` may be a builtin function.
+fn builtin_function_name(func: &Node) -> Option<(String, &Token)> {
+    let (namespace, name) = match func.node_type {
+        NodeType::Identifier => (None, func.token.as_ref()?),
+        NodeType::DotOperator => {
+            let left = match func.children.get("left") {
+                Some(ContentType::Node(n)) if n.node_type == NodeType::Identifier => n,
+                _ => return None,
+            };
+            let right = match func.children.get("right") {
+                Some(ContentType::Node(n)) if n.node_type == NodeType::Identifier => n,
+                _ => return None,
+            };
+            let namespace = left.token.as_ref()?.literal.to_uppercase();
+            let name = right.token.as_ref()?;
+            match namespace.as_str() {
+                "SAFE" => (None, name),
+                _ if functions::NAMESPACES.contains(&namespace.as_str()) => (Some(namespace), name),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    if name.literal.starts_with('`') {
+        return None;
+    }
+    let name_literal = name.literal.to_uppercase();
+    match namespace {
+        Some(namespace) => Some((format!("{}.{}", namespace, name_literal), name)),
+        None => Some((name_literal, name)),
+    }
+}
+
+fn collect_function_diagnostics(node: &Node, udfs: &[String], res: &mut Vec<Diagnostic>) {
+    if node.node_type == NodeType::CallingFunction {
+        let func = match node.children.get("func") {
+            Some(ContentType::Node(n)) => builtin_function_name(n),
+            _ => None,
+        };
+        if let Some((name, token)) = func.filter(|(name, _)| !udfs.contains(name)) {
+            let args = match node.children.get("args") {
+                Some(ContentType::NodeVec(ns)) => ns.len(),
+                _ => 0,
+            };
+            match functions::lookup(&name) {
+                None => res.push(Diagnostic::warning(
+                    "W005",
+                    Span::from_token(token),
+                    format!("Unknown function `{}`.", name),
+                )),
+                Some(sig) if !sig.accepts(args) => {
+                    let expected = match sig.max_args {
+                        Some(max) if max == sig.min_args => format!("{}", max),
+                        Some(max) => format!("{} to {}", sig.min_args, max),
+                        None => format!("at least {}", sig.min_args),
+                    };
+                    res.push(Diagnostic::warning(
+                        "W006",
+                        Span::from_token(token),
+                        format!(
+                            "`{}` takes {} arguments but {} given.",
+                            name, expected, args
+                        ),
+                    ))
+                }
+                _ => (),
+            }
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_function_diagnostics(child, udfs, res);
+    }
+}
+
+fn collect_typed_literal_diagnostics(node: &Node, res: &mut Vec<Diagnostic>) {
+    let typed = match (&node.token, node.children.get("right")) {
+        (Some(t), Some(ContentType::Node(right)))
+            if node.node_type == NodeType::UnaryOperator
+                && t.in_(&vec!["DATE", "DATETIME", "TIME", "TIMESTAMP"]) =>
+        {
+            Some((t, right))
+        }
+        _ => None,
+    };
+    if let Some((type_name, right)) = typed {
+        if let Some(LiteralValue::String(value)) = literal::decode_string_literal(right) {
+            if let Err(reason) = literal::validate_datetime_literal(&type_name.literal, &value) {
+                // the string token (e.g. `'...'` of `r'...'`)
+                let string = match right.children.get("right") {
+                    Some(ContentType::Node(n)) => n,
+                    _ => right,
+                };
+                if let Some(token) = &string.token {
+                    res.push(Diagnostic::warning(
+                        "W007",
+                        Span::from_token(token),
+                        format!(
+                            "Invalid {} literal: {}.",
+                            type_name.literal.to_uppercase(),
+                            reason
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_typed_literal_diagnostics(child, res);
+    }
+}
+
+fn collect_declare_diagnostics(stmts: &[Node], block: bool, res: &mut Vec<Diagnostic>) {
+    let mut leading = block;
+    for stmt in stmts {
+        match (&stmt.token, &stmt.node_type) {
+            (Some(t), NodeType::DeclareStatement) if !leading => res.push(Diagnostic::warning(
+                ErrorCode::MisplacedDeclare.as_str(),
+                Span::from_token(t),
+                parser::misplaced_declare_message(block).to_string(),
+            )),
+            (_, NodeType::DeclareStatement) => (),
+            _ => leading = false,
+        }
+        collect_nested_declare_diagnostics(stmt, res);
+    }
+}
+
+fn collect_nested_declare_diagnostics(node: &Node, res: &mut Vec<Diagnostic>) {
+    for (key, child) in child_nodes(node) {
+        if key != "stmts" {
+            collect_nested_declare_diagnostics(child, res);
+        }
+    }
+    if let Some(ContentType::NodeVec(stmts)) = node.children.get("stmts") {
+        let block = node.node_type == NodeType::BeginStatement;
+        collect_declare_diagnostics(stmts, block, res);
+    }
+}
+
+// ----- grouping -----
+fn collect_grouping_items(node: &Node, res: &Resolution, items: &mut Vec<GroupingItem>) {
+    if node.node_type == NodeType::GroupByExprs {
+        // ROLLUP, CUBE and GROUPING SETS
+        let nested = node.children.contains_key("how");
+        if let Some(ContentType::NodeVec(exprs)) = node.children.get("exprs") {
+            for expr in exprs {
+                push_grouping_item(expr, nested, res, items);
+            }
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_grouping_items(child, res, items);
+    }
+}
+
+fn push_grouping_item(expr: &Node, nested: bool, res: &Resolution, items: &mut Vec<GroupingItem>) {
+    match expr.node_type {
+        NodeType::StructLiteral | NodeType::GroupedExpr if nested => {
+            for (key, child) in child_nodes(expr) {
+                if key == "exprs" || key == "expr" {
+                    push_grouping_item(child, nested, res, items);
+                }
+            }
+            return;
+        }
+        NodeType::EmptyStruct if nested => return, // `()` of GROUPING SETS
+        _ => (),
+    }
+    let span = match node_span_without_comma(expr) {
+        Some(span) => span,
+        None => return,
+    };
+    let kind = match (&expr.node_type, &expr.token) {
+        (NodeType::NumericLiteral, Some(t)) if t.literal.bytes().all(|b| b.is_ascii_digit()) => {
+            GroupingKind::Ordinal
+        }
+        (NodeType::Identifier, Some(t)) if is_alias_reference(res, (t.line, t.column)) => {
+            GroupingKind::Alias
+        }
+        _ => GroupingKind::Expr,
+    };
+    items.push(GroupingItem { kind, span });
+}
+
+fn is_alias_reference(res: &Resolution, id: (usize, usize)) -> bool {
+    res.references.iter().any(|r| {
+        r.id == id
+            && r.binding
+                .and_then(|b| res.binding(b))
+                .is_some_and(|b| b.kind == BindingKind::ColumnAlias)
+    })
+}
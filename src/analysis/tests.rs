@@ -0,0 +1,474 @@
+use super::*;
+use crate::diagnostic::Severity;
+use crate::utils::parse;
+
+#[test]
+fn test_referenced_tables() {
+    let test_cases = vec![
+        ("SELECT 1;", vec![]),
+        (
+            "SELECT * FROM `project.dataset.table` AS t JOIN dataset.t2 USING (a);",
+            vec!["project.dataset.table", "dataset.t2"],
+        ),
+        (
+            "SELECT * FROM `project`.dataset.t1, project-id.dataset.t2;",
+            vec!["project.dataset.t1", "project-id.dataset.t2"],
+        ),
+        // CTE names are excluded
+        (
+            "WITH cte AS (SELECT * FROM src) SELECT * FROM cte JOIN src ON TRUE;",
+            vec!["src"],
+        ),
+        // subquery and UNNEST
+        (
+            "SELECT * FROM (SELECT * FROM a) JOIN UNNEST([1]) JOIN ((b));",
+            vec!["a", "b"],
+        ),
+        (
+            "SELECT (SELECT MAX(x) FROM c) FROM d WHERE EXISTS(SELECT 1 FROM e);",
+            vec!["c", "d", "e"],
+        ),
+        // DML
+        ("INSERT INTO t (a) SELECT a FROM s;", vec!["t", "s"]),
+        ("DELETE t WHERE TRUE;", vec!["t"]),
+        (
+            "UPDATE t SET a = 1 FROM s WHERE t.id = s.id;",
+            vec!["t", "s"],
+        ),
+        (
+            "MERGE t USING s ON t.id = s.id WHEN MATCHED THEN DELETE;",
+            vec!["t", "s"],
+        ),
+        ("TRUNCATE TABLE t;", vec!["t"]),
+        // TABLE argument
+        (
+            "SELECT * FROM ML.PREDICT(MODEL m, TABLE dataset.t);",
+            vec!["dataset.t"],
+        ),
+        ("CREATE TABLE t CLONE s;", vec!["s"]),
+    ];
+    for (code, expected) in test_cases {
+        let stmts = parse(code);
+        assert_eq!(expected, referenced_tables(&stmts[0]), "{}", code);
+    }
+}
+
+#[test]
+fn test_referenced_parameters() {
+    let test_cases = vec![
+        ("SELECT 1;", vec![]),
+        (
+            "SELECT @a, @b FROM t WHERE x = @a AND y = ? AND @@time_zone = 'UTC';",
+            vec!["@a", "@b"],
+        ),
+        ("INSERT INTO t VALUES (@v);", vec!["@v"]),
+    ];
+    for (code, expected) in test_cases {
+        let stmts = parse(code);
+        assert_eq!(expected, referenced_parameters(&stmts[0]), "{}", code);
+    }
+}
+
+#[test]
+fn test_dangerous_statements() {
+    let code = "\
+DELETE t WHERE TRUE;
+DELETE FROM t;
+UPDATE t SET x = 1 WHERE id = 2;
+UPDATE t SET x = 1;
+TRUNCATE TABLE t;
+BEGIN
+  DELETE u
+  ;
+END;
+MERGE t USING s ON t.id = s.id WHEN MATCHED THEN UPDATE SET x = 1 WHEN MATCHED THEN DELETE;";
+    let res: Vec<(DangerKind, Span)> = parse(code)
+        .iter()
+        .flat_map(dangerous_statements)
+        .map(|d| (d.kind, d.span))
+        .collect();
+    assert_eq!(
+        res,
+        vec![
+            (DangerKind::DeleteWithoutWhere, Span::new(2, 1, 2, 15)),
+            (DangerKind::UpdateWithoutWhere, Span::new(4, 1, 4, 20)),
+            (DangerKind::Truncate, Span::new(5, 1, 5, 18)),
+            (DangerKind::DeleteWithoutWhere, Span::new(7, 3, 8, 4)),
+        ]
+    );
+}
+
+#[test]
+fn test_identifier_path() {
+    let stmts = parse("CREATE ASSIGNMENT admin-project.region-us.prod.123456 OPTIONS();");
+    let ident = match stmts[0].children.get("ident") {
+        Some(ContentType::Node(n)) => n,
+        _ => panic!(),
+    };
+    assert_eq!(
+        identifier_path(ident),
+        Some(((1, 19), "admin-project.region-us.prod.123456".to_string()))
+    );
+}
+
+#[test]
+fn test_keyword_case() {
+    let stmts = parse(
+        "\
+SELECT select_ FROM `from` WHERE x IS NOT NULL;
+select CAST(x as INT64) from t;
+Select 1;
+CREATE TEMP FUNCTION f(x INT64) AS (x);
+SELECT 1 + 2;
+",
+    );
+    let literals: Vec<&str> = keyword_tokens(&stmts[0])
+        .iter()
+        .map(|t| t.literal.as_str())
+        .collect();
+    assert_eq!(
+        literals,
+        vec!["SELECT", "FROM", "WHERE", "IS", "NOT", "NULL"]
+    );
+    assert_eq!(keyword_case(&stmts[0]), Some(LetterCase::Upper));
+    // function names (e.g. `CAST`) and types are not keywords
+    assert_eq!(keyword_case(&stmts[1]), Some(LetterCase::Lower));
+    assert_eq!(keyword_case(&stmts[2]), Some(LetterCase::Mixed));
+    assert_eq!(keyword_case(&stmts[3]), Some(LetterCase::Upper));
+    assert_eq!(keyword_case(&stmts[5]), None); // EOF
+}
+
+#[test]
+fn test_split_table_path() {
+    let stmts = parse(
+        "\
+SELECT * FROM `project.dataset.table`;
+SELECT * FROM `project`.dataset.`table`;
+SELECT * FROM `project-id.dataset`.table;
+SELECT * FROM project-id.dataset.table;
+SELECT * FROM table;
+SELECT 1 + 1;
+",
+    );
+    let paths: Vec<Vec<String>> = stmts[..5]
+        .iter()
+        .map(|stmt| match stmt.children.get("from") {
+            Some(ContentType::Node(from)) => match from.children.get("expr") {
+                Some(ContentType::Node(expr)) => split_table_path(expr),
+                _ => panic!(),
+            },
+            _ => panic!(),
+        })
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            vec!["project", "dataset", "table"],
+            vec!["project", "dataset", "table"],
+            vec!["project-id", "dataset", "table"],
+            vec!["project-id", "dataset", "table"],
+            vec!["table"],
+        ]
+    );
+    let expr = match stmts[5].children.get("exprs") {
+        Some(ContentType::NodeVec(exprs)) => &exprs[0],
+        _ => panic!(),
+    };
+    assert!(split_table_path(expr).is_empty());
+}
+
+#[test]
+fn test_redundant_parentheses() {
+    let stmts = parse(
+        "\
+SELECT (a * b) + c, (a + b) * c, a - (b - c), (a - b) - c, ((x));
+SELECT -(-1), NOT (NOT x), (a = b) = c, x BETWEEN (a + 1) AND b, f((x));
+CREATE TEMP FUNCTION f() AS (1);
+SELECT (x) LIKE ANY (a), a-(-b), a + (+b), x IN (a);
+",
+    );
+    assert_eq!(
+        redundant_parentheses(&stmts[0]),
+        vec![
+            Span::new(1, 8, 1, 15),
+            Span::new(1, 47, 1, 54),
+            Span::new(1, 61, 1, 64),
+        ]
+    );
+    assert_eq!(
+        redundant_parentheses(&stmts[1]),
+        vec![Span::new(2, 19, 2, 26), Span::new(2, 51, 2, 58)]
+    );
+    assert!(redundant_parentheses(&stmts[2]).is_empty());
+    assert_eq!(
+        redundant_parentheses(&stmts[3]),
+        vec![Span::new(4, 8, 4, 11)]
+    );
+}
+
+#[test]
+fn test_flatten_binary_operator() {
+    let stmts = parse(
+        "\
+SELECT
+  a || b || 'c' || d,
+  a || (b || c),
+  x AND y or z AND w,
+  a IS NOT NULL,
+  1;
+",
+    );
+    let exprs = match stmts[0].children.get("exprs") {
+        Some(ContentType::NodeVec(exprs)) => exprs,
+        _ => panic!(),
+    };
+    let literals = |n: &Node| {
+        flatten_binary_operator(n)
+            .into_iter()
+            .map(|n| n.token.as_ref().unwrap().literal.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(literals(&exprs[0]), vec!["a", "b", "'c'", "d"]);
+    assert_eq!(literals(&exprs[1]), vec!["a", "("]);
+    assert_eq!(literals(&exprs[2]), vec!["AND", "AND"]);
+    assert_eq!(literals(&exprs[3]), vec!["IS"]);
+    assert_eq!(literals(&exprs[4]), vec!["1"]);
+}
+
+#[test]
+fn test_original_layout() {
+    let stmts = parse(
+        "\
+SELECT a FROM t WHERE true; SELECT b
+FROM t -- comment
+  WHERE true GROUP BY b
+ORDER BY b;
+SELECT '''multi
+line''';
+",
+    );
+    assert_eq!(
+        original_layout(&stmts[0]),
+        OriginalLayout {
+            single_line: true,
+            own_line_clauses: vec![],
+        }
+    );
+    assert_eq!(
+        original_layout(&stmts[1]),
+        OriginalLayout {
+            single_line: false,
+            own_line_clauses: vec![
+                "from".to_string(),
+                "where".to_string(),
+                "orderby".to_string()
+            ],
+        }
+    );
+    assert!(!original_layout(&stmts[2]).single_line);
+}
+
+#[test]
+fn test_check_function_calls() {
+    let stmts = parse(
+        "\
+CREATE TEMP FUNCTION my_func(x INT64) AS (x);
+SELECT
+  SUBSTR('abc'), CONCAT('a', 'b', 'c'), CURRENT_DATE,
+  SAFE.PARSE_DATE('%Y', '2020'), NET.HOST(url), NET.UNKNOWN(url),
+  unknown_func(1), my_func(1), dataset.udf(1), `quoted`(1), COUNT(*);
+",
+    );
+    let res = check_function_calls(&stmts);
+    assert_eq!(
+        res.iter()
+            .map(|d| (d.code.as_str(), d.span, d.message.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "W006",
+                Span::new(3, 3, 3, 9),
+                "`SUBSTR` takes 2 to 3 arguments but 1 given."
+            ),
+            (
+                "W005",
+                Span::new(4, 53, 4, 60),
+                "Unknown function `NET.UNKNOWN`."
+            ),
+            (
+                "W005",
+                Span::new(5, 3, 5, 15),
+                "Unknown function `UNKNOWN_FUNC`."
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_check_declare() {
+    let stmts = parse(
+        "\
+DECLARE x INT64;
+SELECT 1; DECLARE y INT64;
+BEGIN
+  DECLARE z INT64; SET z = 1; DECLARE w INT64;
+  IF TRUE THEN DECLARE v INT64; END IF;
+  BEGIN DECLARE u INT64; END;
+END;
+",
+    );
+    let res = check_declare(&stmts);
+    assert_eq!(
+        res.iter()
+            .map(|d| (d.code.as_str(), d.span.start_line, d.span.start_column))
+            .collect::<Vec<_>>(),
+        vec![("P009", 2, 11), ("P009", 4, 31), ("P009", 5, 16)]
+    );
+    assert!(res.iter().all(|d| d.severity == Severity::Warning));
+    assert!(check_declare(&parse("CREATE TEMP FUNCTION f() AS (1); SELECT f();")).is_empty());
+}
+
+#[test]
+fn test_check_typed_literals() {
+    let stmts = parse(
+        "\
+SELECT
+  DATE '2024-02-29', DATE '2023-02-29', DATE r'2024-13-01',
+  TIME '12:30:00.123456', TIME '24:00:00', DATETIME '2024-01-01T10:00:00',
+  TIMESTAMP '2024-01-01 00:00:00+09', TIMESTAMP '2024-01-01 America/Los_Angeles',
+  TIMESTAMP 'yesterday', DATE(ts), t.date;
+",
+    );
+    let res = check_typed_literals(&stmts);
+    assert_eq!(
+        res.iter()
+            .map(|d| (d.code.as_str(), d.span, d.message.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                "W007",
+                Span::new(2, 27, 2, 39),
+                "Invalid DATE literal: day is out of range."
+            ),
+            (
+                "W007",
+                Span::new(2, 47, 2, 59),
+                "Invalid DATE literal: month is out of range."
+            ),
+            (
+                "W007",
+                Span::new(3, 32, 3, 42),
+                "Invalid TIME literal: hour is out of range."
+            ),
+            (
+                "W007",
+                Span::new(5, 13, 5, 24),
+                "Invalid TIMESTAMP literal: invalid date."
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_lineage() {
+    let stmts = parse(
+        "\
+CREATE OR REPLACE TABLE `dataset.t` AS
+WITH cte AS (SELECT * FROM src1) SELECT * FROM cte JOIN dataset.src2 USING (id);
+INSERT INTO t SELECT * FROM t;
+UPDATE t SET a = 1 FROM u WHERE t.id = u.id;
+CREATE VIEW v AS SELECT 1;
+DROP TABLE t;
+SELECT * FROM t;
+",
+    );
+    let test_cases = vec![
+        (Some("dataset.t"), vec!["src1", "dataset.src2"]),
+        (Some("t"), vec!["t"]),
+        (Some("t"), vec!["u"]),
+        (Some("v"), vec![]),
+        (Some("t"), vec![]),
+        (None, vec!["t"]),
+    ];
+    for (stmt, (target, sources)) in stmts.iter().zip(test_cases) {
+        assert_eq!(
+            lineage(stmt),
+            Lineage {
+                target: target.map(|t| t.to_string()),
+                sources: sources.into_iter().map(|s| s.to_string()).collect(),
+            }
+        );
+    }
+}
+
+#[test]
+fn test_casing_stats() {
+    let stmts = parse(
+        "\
+SELECT count(*), SAFE.DIVIDE(a, b), `Quoted`, Col FROM t WHERE x IS NULL;
+select 1 from T;
+",
+    );
+    let stats = casing_stats(&stmts);
+    assert_eq!(
+        stats.keywords,
+        CaseCounts {
+            upper: 5,
+            lower: 2,
+            mixed: 0
+        }
+    );
+    assert_eq!(
+        stats.functions,
+        CaseCounts {
+            upper: 2,
+            lower: 1,
+            mixed: 0
+        }
+    );
+    assert_eq!(
+        stats.identifiers,
+        CaseCounts {
+            upper: 1,
+            lower: 4,
+            mixed: 1
+        }
+    );
+    assert_eq!(stats.keywords.dominant(), Some(LetterCase::Upper));
+    assert_eq!(stats.identifiers.dominant(), Some(LetterCase::Lower));
+    assert_eq!(CaseCounts::default().dominant(), None);
+}
+
+#[test]
+fn test_grouping_items() {
+    let stmts = parse(
+        "\
+SELECT a AS x, b, SUM(c) FROM t GROUP BY 1, x, b + 1;
+SELECT a, b FROM t GROUP BY GROUPING SETS ((1, a), b, ());
+SELECT a FROM t GROUP BY ALL;
+",
+    );
+    let items = |stmt| {
+        grouping_items(stmt)
+            .into_iter()
+            .map(|i| (i.kind, i.span))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        items(&stmts[0]),
+        vec![
+            (GroupingKind::Ordinal, Span::new(1, 42, 1, 43)),
+            (GroupingKind::Alias, Span::new(1, 45, 1, 46)),
+            (GroupingKind::Expr, Span::new(1, 48, 1, 53)),
+        ]
+    );
+    assert_eq!(
+        items(&stmts[1]),
+        vec![
+            (GroupingKind::Ordinal, Span::new(2, 45, 2, 46)),
+            (GroupingKind::Expr, Span::new(2, 48, 2, 49)),
+            (GroupingKind::Expr, Span::new(2, 52, 2, 53)),
+        ]
+    );
+    assert!(items(&stmts[2]).is_empty());
+}
@@ -0,0 +1,158 @@
+//! Reads code from stdin and writes the result as JSON to stdout.
+//!
+//! ```shell
+//! bq2cst [parse | parse_script | tokenize] < query.sql
+//! bq2cst [grammar | grammar_ebnf]
+//! bq2cst [trace | debug_tree] < query.sql
+//! bq2cst check < query.sql
+//! ```
+//!
+//! The output is the same as the one of the JavaScript function of the same name.
+//! `grammar` and `grammar_ebnf` do not read stdin and write the implemented statements
+//! (see `bq2cst::grammar`).
+//! `trace` writes which parse function consumed which tokens (see `Parser::with_trace`)
+//! as indented text, followed by the error if any.
+//! `debug_tree` writes `Node::debug_tree` of each statement.
+//! `check` writes `bq2cst::check::CheckReport` instead of the CST.
+//! If the code cannot be parsed, the error is written instead and the exit status is 1.
+//! This binary does not depend on wasm-bindgen, so it can be built for WASI
+//! (`cargo build --release --target wasm32-wasip1 --no-default-features --features cli`).
+
+use bq2cst::check;
+use bq2cst::error::BQ2CSTResult;
+use bq2cst::grammar;
+use bq2cst::lexer::Lexer;
+use bq2cst::parser::{Parser, TraceEvent};
+use serde::Serialize;
+use std::cell::Cell;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+use std::rc::Rc;
+
+fn run<T: Serialize>(
+    code: String,
+    f: impl FnOnce(String) -> BQ2CSTResult<T>,
+) -> serde_json::Result<(String, bool)> {
+    match f(code) {
+        Ok(res) => Ok((serde_json::to_string(&res)?, true)),
+        Err(bq2cst_error) => Ok((serde_json::to_string(&bq2cst_error)?, false)),
+    }
+}
+
+/// Writes the events of `Parser::with_trace` to stdout as they are emitted.
+fn trace(code: String) -> BQ2CSTResult<()> {
+    let depth = Rc::new(Cell::new(0));
+    let tokens = Lexer::new(code).tokenize_code()?;
+    Parser::new(tokens)
+        .with_trace(move |event| match event {
+            TraceEvent::Enter { rule, token } => {
+                println!(
+                    "{}{} {}:{} `{}`",
+                    "  ".repeat(depth.get()),
+                    rule,
+                    token.line,
+                    token.column,
+                    token.literal
+                );
+                depth.set(depth.get() + 1);
+            }
+            TraceEvent::Exit { rule, token, ok } => {
+                depth.set(depth.get() - 1);
+                println!(
+                    "{}/{} {} {}:{} `{}`",
+                    "  ".repeat(depth.get()),
+                    rule,
+                    if *ok { "consumed to" } else { "failed at" },
+                    token.line,
+                    token.column,
+                    token.literal
+                );
+            }
+        })
+        .parse_code()?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let command = std::env::args().nth(1).unwrap_or("parse".to_string());
+    match command.as_str() {
+        "grammar" | "grammar_ebnf" => {
+            let rules = match grammar::grammar() {
+                Ok(rules) => rules,
+                Err(bq2cst_error) => {
+                    eprintln!("{}", bq2cst_error.message());
+                    return ExitCode::FAILURE;
+                }
+            };
+            if command == "grammar" {
+                let json =
+                    serde_json::to_string(&rules).expect("Problem converting the result to json.");
+                println!("{}", json);
+            } else {
+                print!("{}", grammar::to_ebnf(&rules));
+            }
+            return ExitCode::SUCCESS;
+        }
+        _ => (),
+    }
+    let mut code = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut code) {
+        eprintln!("Failed to read stdin: {}", e);
+        return ExitCode::from(2);
+    }
+    let res = match command.as_str() {
+        "parse" => run(code, |code| {
+            Parser::new(Lexer::new(code).tokenize_code()?).parse_code()
+        }),
+        "parse_script" => run(code, |code| {
+            Parser::new(Lexer::new(code).tokenize_code()?).parse_script()
+        }),
+        "tokenize" => run(code, |code| Lexer::new(code).tokenize_code()),
+        "check" => {
+            let report = check::check(&code);
+            serde_json::to_string(&report).map(|json| (json, report.ok))
+        }
+        "debug_tree" => match Lexer::new(code)
+            .tokenize_code()
+            .and_then(|tokens| Parser::new(tokens).parse_code())
+        {
+            Ok(stmts) => {
+                for stmt in stmts {
+                    print!("{}", stmt.debug_tree());
+                }
+                return ExitCode::SUCCESS;
+            }
+            Err(bq2cst_error) => Ok((
+                serde_json::to_string(&bq2cst_error)
+                    .expect("Problem converting the error to json."),
+                false,
+            )),
+        },
+        "trace" => match trace(code) {
+            Ok(()) => return ExitCode::SUCCESS,
+            Err(bq2cst_error) => Ok((
+                serde_json::to_string(&bq2cst_error)
+                    .expect("Problem converting the error to json."),
+                false,
+            )),
+        },
+        _ => {
+            eprintln!("Unknown command: {}", command);
+            eprintln!("Usage: bq2cst [parse | parse_script | tokenize] < query.sql");
+            eprintln!("       bq2cst [grammar | grammar_ebnf]");
+            eprintln!("       bq2cst [trace | debug_tree] < query.sql");
+            eprintln!("       bq2cst check < query.sql");
+            return ExitCode::from(2);
+        }
+    };
+    let (json, ok) = res.expect("Problem converting the result to json.");
+    let mut stdout = io::stdout().lock();
+    if writeln!(stdout, "{}", json).is_err() {
+        return ExitCode::from(2);
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
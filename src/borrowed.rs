@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::token::Token;
+use alloc::borrow::Cow;
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use core::borrow::Borrow;
+use core::ops::Deref;
+use serde::Deserialize;
+
+/// Same as `Token` but the strings are borrowed from the JSON where possible.
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct BorrowedToken<'a> {
+    pub line: usize,
+    pub column: usize,
+    #[serde(borrow)]
+    pub literal: Cow<'a, str>,
+    #[serde(default)]
+    pub synthetic: bool,
+    #[serde(default, borrow)]
+    pub leading_trivia: Cow<'a, str>,
+    #[serde(default, borrow)]
+    pub trailing_trivia: Cow<'a, str>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub enum BorrowedContentType<'a> {
+    #[serde(borrow)]
+    Node(BorrowedNode<'a>),
+    #[serde(borrow)]
+    NodeVec(Vec<BorrowedNode<'a>>),
+}
+
+/// Same as `Node` but the strings are borrowed from the JSON where possible.
+/// Only strings which contain escape sequences (e.g. `\n`, `\"`) are copied.
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct BorrowedNode<'a> {
+    #[serde(borrow)]
+    pub token: Option<BorrowedToken<'a>>,
+    pub node_type: NodeType,
+    #[serde(borrow)]
+    pub children: BTreeMap<&'a str, BorrowedContentType<'a>>,
+    #[serde(default)]
+    pub format_exempt: bool,
+    #[serde(default, borrow)]
+    pub annotations: BTreeMap<BorrowedStr<'a>, Vec<BorrowedStr<'a>>>,
+}
+
+/// A string borrowed from the JSON where possible.
+/// `Cow<str>` is always copied when it is placed in a collection,
+/// whose elements do not inherit `#[serde(borrow)]`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct BorrowedStr<'a>(#[serde(borrow)] pub Cow<'a, str>);
+
+impl Deref for BorrowedStr<'_> {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for BorrowedStr<'_> {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Node {
+    /// Deserializes a node serialized as JSON (e.g. an element of the result of `parse`)
+    /// without copying the strings.
+    /// Use `BorrowedNode::into_owned` to get a `Node`.
+    pub fn from_json_str(json: &str) -> serde_json::Result<BorrowedNode<'_>> {
+        serde_json::from_str(json)
+    }
+}
+
+impl BorrowedToken<'_> {
+    pub fn into_owned(self) -> Token {
+        let mut token = Token::new(self.line, self.column, self.literal.into_owned());
+        token.synthetic = self.synthetic;
+        token.leading_trivia = self.leading_trivia.into_owned();
+        token.trailing_trivia = self.trailing_trivia.into_owned();
+        token.offset = self.offset;
+        token
+    }
+}
+
+impl BorrowedNode<'_> {
+    pub fn into_owned(self) -> Node {
+        let mut node = match self.token {
+            Some(token) => Node::new(token.into_owned(), self.node_type),
+            None => Node::empty(self.node_type),
+        };
+        for (key, content) in self.children {
+            let content = match content {
+                BorrowedContentType::Node(n) => ContentType::Node(n.into_owned()),
+                BorrowedContentType::NodeVec(ns) => {
+                    ContentType::NodeVec(ns.into_iter().map(BorrowedNode::into_owned).collect())
+                }
+            };
+            node.children.insert(key.to_string(), content);
+        }
+        node.format_exempt = self.format_exempt;
+        node.annotations = self
+            .annotations
+            .into_iter()
+            .map(|(k, vs)| {
+                (
+                    k.0.into_owned(),
+                    vs.into_iter().map(|v| v.0.into_owned()).collect(),
+                )
+            })
+            .collect();
+        node
+    }
+}
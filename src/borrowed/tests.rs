@@ -0,0 +1,39 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::options::ParseOptions;
+use crate::parser::Parser;
+use alloc::string::ToString;
+
+#[test]
+fn test_from_json_str() {
+    let code = "-- owner: me\nSELECT 'a\\nb', [1, 2,] FROM t;";
+    let options = ParseOptions {
+        keep_trivia: true,
+        ..Default::default()
+    };
+    let tokens = Lexer::with_options(code.to_string(), options)
+        .tokenize_code()
+        .unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let json = serde_json::to_string(&stmts[0]).unwrap();
+
+    let borrowed = Node::from_json_str(&json).unwrap();
+    let token = borrowed.token.as_ref().unwrap();
+    assert!(matches!(token.literal, Cow::Borrowed("SELECT")));
+    assert!(matches!(token.trailing_trivia, Cow::Borrowed(" ")));
+    let exprs = match &borrowed.children["exprs"] {
+        BorrowedContentType::NodeVec(ns) => ns,
+        other => panic!("{:?}", other),
+    };
+    // `\` is escaped in JSON
+    let string = exprs[0].token.as_ref().unwrap();
+    assert!(matches!(string.literal, Cow::Owned(_)));
+    assert_eq!(string.literal, "'a\\nb'");
+    // so are annotations (the keys of children are `&str`)
+    let (key, values) = borrowed.annotations.iter().next().unwrap();
+    assert!(matches!(key.0, Cow::Borrowed("owner")));
+    assert!(matches!(values[..], [BorrowedStr(Cow::Borrowed("me"))]));
+
+    assert_eq!(borrowed.into_owned(), stmts[0]);
+    assert!(Node::from_json_str("{").is_err());
+}
@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::edit::{parse_select, place_tokens};
+use crate::error::{BQ2CSTError, BQ2CSTResult, ErrorCode};
+use crate::token::Token;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Builds a SELECT statement clause by clause.
+/// Each clause is given as code and parsed in its own context,
+/// then the tokens are laid out on a single line and marked as synthetic.
+/// The result can be written out by `crate::edit::unparse`.
+#[derive(Debug, Clone, Default)]
+pub struct NodeBuilder {
+    distinct: bool,
+    columns: Vec<String>,
+    from: Option<String>,
+    where_: Option<String>,
+    group_by: Vec<String>,
+    having: Option<String>,
+    order_by: Vec<String>,
+    limit: Option<String>,
+}
+
+impl NodeBuilder {
+    pub fn select() -> NodeBuilder {
+        NodeBuilder::default()
+    }
+    /// Adds `DISTINCT` (as `distinct_or_all`, not as a part of the select list).
+    pub fn distinct(mut self) -> NodeBuilder {
+        self.distinct = true;
+        self
+    }
+    /// Adds an expression to the select list (e.g. `col AS alias`).
+    pub fn column(mut self, expr: &str) -> NodeBuilder {
+        self.columns.push(expr.to_string());
+        self
+    }
+    /// Sets the FROM clause (e.g. `t1 JOIN t2 USING(id)`).
+    pub fn from(mut self, from_item: &str) -> NodeBuilder {
+        self.from = Some(from_item.to_string());
+        self
+    }
+    pub fn where_(mut self, condition: &str) -> NodeBuilder {
+        self.where_ = Some(condition.to_string());
+        self
+    }
+    pub fn group_by(mut self, expr: &str) -> NodeBuilder {
+        self.group_by.push(expr.to_string());
+        self
+    }
+    pub fn having(mut self, condition: &str) -> NodeBuilder {
+        self.having = Some(condition.to_string());
+        self
+    }
+    /// Adds an ordering expression (e.g. `col DESC`).
+    pub fn order_by(mut self, expr: &str) -> NodeBuilder {
+        self.order_by.push(expr.to_string());
+        self
+    }
+    pub fn limit(mut self, expr: &str) -> NodeBuilder {
+        self.limit = Some(expr.to_string());
+        self
+    }
+    pub fn build(&self) -> BQ2CSTResult<Node> {
+        if self.columns.is_empty() {
+            return Err(BQ2CSTError::new(
+                1,
+                1,
+                ErrorCode::UnexpectedToken,
+                "Expected at least one column.".to_string(),
+            )
+            .with_expected(&["column"]));
+        }
+        let mut layout = Layout { column: 1 };
+        let mut stmt = layout.node("SELECT", NodeType::SelectStatement);
+        if self.distinct {
+            stmt.push_node(
+                "distinct_or_all",
+                layout.node("DISTINCT", NodeType::Keyword),
+            );
+        }
+        let mut exprs = parse_clause("SELECT ", &self.columns.join(", "), "exprs", None)?;
+        layout.place(&mut exprs);
+        stmt.children.insert("exprs".to_string(), exprs);
+        if let Some(from) = &self.from {
+            let mut clause = layout.node("FROM", NodeType::KeywordWithExpr);
+            let mut expr = parse_clause("SELECT 1 FROM ", from, "from", Some("expr"))?;
+            layout.place(&mut expr);
+            clause.children.insert("expr".to_string(), expr);
+            stmt.push_node("from", clause);
+        }
+        if let Some(where_) = &self.where_ {
+            let mut clause = layout.node("WHERE", NodeType::KeywordWithExpr);
+            let mut expr = parse_clause("SELECT 1 WHERE ", where_, "where", Some("expr"))?;
+            layout.place(&mut expr);
+            clause.children.insert("expr".to_string(), expr);
+            stmt.push_node("where", clause);
+        }
+        if !self.group_by.is_empty() {
+            let mut clause = layout.node("GROUP", NodeType::GroupByExprs);
+            clause.push_node("by", layout.node("BY", NodeType::Keyword));
+            let code = self.group_by.join(", ");
+            let mut exprs = parse_clause("SELECT 1 GROUP BY ", &code, "groupby", Some("exprs"))?;
+            layout.place(&mut exprs);
+            clause.children.insert("exprs".to_string(), exprs);
+            stmt.push_node("groupby", clause);
+        }
+        if let Some(having) = &self.having {
+            let mut clause = layout.node("HAVING", NodeType::KeywordWithExpr);
+            let mut expr = parse_clause("SELECT 1 HAVING ", having, "having", Some("expr"))?;
+            layout.place(&mut expr);
+            clause.children.insert("expr".to_string(), expr);
+            stmt.push_node("having", clause);
+        }
+        if !self.order_by.is_empty() {
+            let mut clause = layout.node("ORDER", NodeType::XXXByExprs);
+            clause.push_node("by", layout.node("BY", NodeType::Keyword));
+            let code = self.order_by.join(", ");
+            let mut exprs = parse_clause("SELECT 1 ORDER BY ", &code, "orderby", Some("exprs"))?;
+            layout.place(&mut exprs);
+            clause.children.insert("exprs".to_string(), exprs);
+            stmt.push_node("orderby", clause);
+        }
+        if let Some(limit) = &self.limit {
+            let mut clause = layout.node("LIMIT", NodeType::LimitClause);
+            let mut expr = parse_clause("SELECT 1 LIMIT ", limit, "limit", Some("expr"))?;
+            layout.place(&mut expr);
+            clause.children.insert("expr".to_string(), expr);
+            stmt.push_node("limit", clause);
+        }
+        Ok(stmt)
+    }
+}
+
+// ----- utilities -----
+struct Layout {
+    column: usize,
+}
+
+impl Layout {
+    fn node(&mut self, literal: &str, node_type: NodeType) -> Node {
+        let node = Node::new(Token::synthetic(1, self.column, literal), node_type);
+        self.column += literal.chars().count() + 1;
+        node
+    }
+    fn place(&mut self, content: &mut ContentType) {
+        let end = match content {
+            ContentType::Node(n) => place_tokens(core::slice::from_mut(n), 1, self.column),
+            ContentType::NodeVec(ns) => place_tokens(ns, 1, self.column),
+        };
+        self.column = end + 1;
+    }
+}
+
+/// Parses `prefix` + `code` and takes out `stmt.key` (or `stmt.key.sub_key`).
+fn parse_clause(
+    prefix: &str,
+    code: &str,
+    key: &str,
+    sub_key: Option<&str>,
+) -> BQ2CSTResult<ContentType> {
+    let mut stmt = parse_select(&format!("{}{}", prefix, code))?;
+    let content = stmt.children.remove(key);
+    // e.g. `.from("t WHERE true")`
+    let rest = stmt.children.keys().any(|k| k != "exprs");
+    let content = match (content, sub_key) {
+        (Some(ContentType::Node(mut n)), Some(sub_key)) => n.children.remove(sub_key),
+        (content, _) => content,
+    };
+    content.filter(|_| !rest).ok_or_else(|| {
+        BQ2CSTError::new(
+            1,
+            1,
+            ErrorCode::UnexpectedToken,
+            format!("Cannot use `{}` here.", code),
+        )
+    })
+}
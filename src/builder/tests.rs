@@ -0,0 +1,47 @@
+use super::*;
+use crate::edit::unparse;
+use crate::utils::parse;
+
+#[test]
+fn test_build_select() {
+    let stmt = NodeBuilder::select()
+        .column("a")
+        .column("SUM(b) AS c")
+        .from("dataset.table AS t JOIN u USING(id)")
+        .where_("a > 0")
+        .group_by("a")
+        .having("c  > 1")
+        .order_by("a DESC")
+        .order_by("c")
+        .limit("10")
+        .build()
+        .unwrap();
+    let expected = "SELECT a, SUM(b) AS c FROM dataset.table AS t JOIN u USING(id) WHERE a > 0 GROUP BY a HAVING c  > 1 ORDER BY a DESC, c LIMIT 10";
+    assert_eq!(unparse(std::slice::from_ref(&stmt)), expected);
+    assert_eq!(stmt.to_string(), parse(expected)[0].to_string());
+    assert!(stmt.token.as_ref().unwrap().synthetic);
+
+    let stmt = NodeBuilder::select().column("1").build().unwrap();
+    assert_eq!(unparse(&[stmt]), "SELECT 1");
+
+    let stmt = NodeBuilder::select()
+        .distinct()
+        .column("a")
+        .from("t")
+        .build()
+        .unwrap();
+    let expected = "SELECT DISTINCT a FROM t";
+    assert_eq!(unparse(std::slice::from_ref(&stmt)), expected);
+    assert_eq!(stmt.to_string(), parse(expected)[0].to_string());
+}
+
+#[test]
+fn test_build_error() {
+    assert!(NodeBuilder::select().from("t").build().is_err());
+    assert!(NodeBuilder::select()
+        .column("a")
+        .from("t WHERE true")
+        .build()
+        .is_err());
+    assert!(NodeBuilder::select().column("a; SELECT 1").build().is_err());
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests;
+
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use alloc::string::ToString;
+use serde::{Deserialize, Serialize};
+
+/// The result of `check`.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct CheckReport {
+    pub ok: bool,
+    pub error: Option<BQ2CSTError>,
+    /// The number of tokens except comments and EOF.
+    pub token_count: usize,
+    /// The number of statements except EOF (0 if `ok` is `false`).
+    pub statement_count: usize,
+    /// The time spent on lexing and parsing.
+    /// Only available if the `std` or `cli` feature is enabled
+    /// (and not on `wasm32-unknown-unknown`).
+    pub elapsed_micros: Option<u64>,
+}
+
+/// Validates the syntax of `code` without serializing the result,
+/// which is intended for pre-commit hooks and CI.
+pub fn check(code: &str) -> CheckReport {
+    #[cfg(all(
+        any(feature = "std", feature = "cli"),
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    let start = std::time::Instant::now();
+    let mut token_count = 0;
+    let parsed: BQ2CSTResult<usize> = Lexer::new(code.to_string())
+        .tokenize_code()
+        .and_then(|tokens| {
+            token_count = tokens
+                .iter()
+                .filter(|t| !t.is_comment() && !t.literal.is_empty())
+                .count();
+            Parser::new(tokens).parse_code()
+        })
+        .map(|stmts| stmts.len() - 1);
+    #[cfg(all(
+        any(feature = "std", feature = "cli"),
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ))]
+    let elapsed_micros = Some(start.elapsed().as_micros() as u64);
+    #[cfg(not(all(
+        any(feature = "std", feature = "cli"),
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    )))]
+    let elapsed_micros = None;
+    match parsed {
+        Ok(statement_count) => CheckReport {
+            ok: true,
+            error: None,
+            token_count,
+            statement_count,
+            elapsed_micros,
+        },
+        Err(error) => CheckReport {
+            ok: false,
+            error: Some(error),
+            token_count,
+            statement_count: 0,
+            elapsed_micros,
+        },
+    }
+}
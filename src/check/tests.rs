@@ -0,0 +1,22 @@
+use super::*;
+
+#[test]
+fn test_check() {
+    let report = check("SELECT 1; -- comment\nSELECT a FROM t;");
+    assert!(report.ok);
+    assert_eq!(report.error, None);
+    assert_eq!(report.token_count, 8);
+    assert_eq!(report.statement_count, 2);
+    assert!(report.elapsed_micros.is_some());
+
+    let report = check("SELEC 1;");
+    assert!(!report.ok);
+    assert_eq!(report.error.map(|e| (e.line, e.column)), Some((1, 1)));
+    assert_eq!(report.token_count, 3);
+    assert_eq!(report.statement_count, 0);
+
+    // lexer errors
+    let report = check("SELECT 'abc");
+    assert!(!report.ok);
+    assert_eq!(report.token_count, 0);
+}
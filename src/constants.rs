@@ -1,3 +1,4 @@
+// sorted so that `is_reserved_keyword` can use binary search
 pub const KEYWORDS: [&str; 96] = [
     "ALL",
     "AND",
@@ -96,3 +97,38 @@ pub const KEYWORDS: [&str; 96] = [
     "WITH",
     "WITHIN",
 ];
+
+// keywords which can start a statement (see `Parser::parse_statement`)
+pub const STATEMENT_KEYWORDS: [&str; 35] = [
+    "WITH", "SELECT", "INSERT", "DELETE", "TRUNCATE", "UPDATE", "MERGE", "CREATE", "ALTER", "DROP",
+    "UNDROP", "GRANT", "REVOKE", "DECLARE", "SET", "EXECUTE", "IF", "BEGIN", "CASE", "LOAD",
+    "LOOP", "REPEAT", "WHILE", "BREAK", "LEAVE", "CONTINUE", "ITERATE", "FOR", "COMMIT",
+    "ROLLBACK", "RAISE", "RETURN", "CALL", "ASSERT", "EXPORT",
+];
+
+/// `KEYWORDS` and `STATEMENT_KEYWORDS`, which are the candidates of misspelled keywords.
+pub const ALL_KEYWORDS: [&str; KEYWORDS.len() + STATEMENT_KEYWORDS.len()] = {
+    let mut res = [""; KEYWORDS.len() + STATEMENT_KEYWORDS.len()];
+    let mut i = 0;
+    while i < KEYWORDS.len() {
+        res[i] = KEYWORDS[i];
+        i += 1;
+    }
+    while i < res.len() {
+        res[i] = STATEMENT_KEYWORDS[i - KEYWORDS.len()];
+        i += 1;
+    }
+    res
+};
+
+/// Case-insensitive lookup of `KEYWORDS` without allocation.
+pub fn is_reserved_keyword(word: &str) -> bool {
+    if !word.is_ascii() {
+        // e.g. `ſ` is `S` in upper case
+        let upper = word.to_uppercase();
+        return KEYWORDS.binary_search(&upper.as_str()).is_ok();
+    }
+    KEYWORDS
+        .binary_search_by(|kw| kw.bytes().cmp(word.bytes().map(|b| b.to_ascii_uppercase())))
+        .is_ok()
+}
@@ -1,10 +1,156 @@
 #[cfg(test)]
 mod tests;
 
-use crate::token::Token;
+use crate::analysis::{original_layout, OriginalLayout};
+use crate::diagnostic::Span;
+use crate::token::{CommentKind, Token};
+use alloc::collections::BTreeMap;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fmt;
+
+/// The version of the serialized shape of the CST.
+/// It is incremented when the shape changes in a way which breaks printers.
+///
+/// - 1: a bare array of statements
+/// - 2: `VersionedCst` (children in stable order, `Token.synthetic`)
+pub const CST_VERSION: u32 = 2;
+
+/// The serialized root of the CST.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedCst {
+    pub cst_version: u32,
+    pub stmts: Vec<Node>,
+}
+
+/// The CST in the shape of a specific `CST_VERSION`.
+/// Printers which have not been upgraded yet can keep receiving the previous shape.
+#[derive(PartialEq, Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum CstOutput {
+    V1(Vec<Node>),
+    V2(VersionedCst),
+}
+
+impl CstOutput {
+    /// Returns `None` if `cst_version` is unknown.
+    pub fn new(stmts: Vec<Node>, cst_version: u32) -> Option<CstOutput> {
+        match cst_version {
+            1 => Some(CstOutput::V1(stmts)),
+            2 => Some(CstOutput::V2(VersionedCst { cst_version, stmts })),
+            _ => None,
+        }
+    }
+}
+
+/// The whole input as a single root.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Script {
+    /// Comments at the top of the file which are separated from the first statement
+    /// by a blank line (e.g. a license header).
+    /// They are moved here from `leading_comments` of the first statement.
+    pub leading_comments: Vec<Node>,
+    /// The statements followed by `EOF`, same as `Parser::parse_code`.
+    pub stmts: Vec<Node>,
+    /// `blank_lines[i]` is the number of empty lines right before `stmts[i]`
+    /// (including its leading comments).
+    pub blank_lines: Vec<usize>,
+    /// `layouts[i]` is how `stmts[i]` was laid out in the original code
+    /// (see `crate::analysis::original_layout`).
+    pub layouts: Vec<OriginalLayout>,
+    /// The EOF token, which has the position of the end of the input
+    /// (`line`, `column` and `offset` in bytes) and the whitespace before it.
+    /// `None` if the script is not made from the tokens returned by the lexer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eof: Option<Token>,
+}
+
+impl Script {
+    pub fn new(mut stmts: Vec<Node>) -> Script {
+        let mut leading_comments = Vec::new();
+        if let Some(first) = stmts.first_mut() {
+            if let Some(ContentType::NodeVec(mut comments)) =
+                first.children.remove("leading_comments")
+            {
+                let stmt_line = token_lines(first).map(|(start, _)| start);
+                let next_lines: Vec<Option<usize>> = comments
+                    .iter()
+                    .skip(1)
+                    .map(|c| c.token.as_ref().map(|t| t.line))
+                    .chain(core::iter::once(stmt_line))
+                    .collect();
+                // up to the last comment followed by a blank line
+                let header_len = comments
+                    .iter()
+                    .zip(next_lines)
+                    .rposition(|(c, next_line)| match (&c.token, next_line) {
+                        (Some(t), Some(next_line)) => Span::from_token(t).end_line + 1 < next_line,
+                        _ => false,
+                    })
+                    .map_or(0, |i| i + 1);
+                leading_comments = comments.drain(..header_len).collect();
+                if !comments.is_empty() {
+                    first.push_node_vec("leading_comments", comments);
+                }
+            }
+        }
+        let mut blank_lines = Vec::new();
+        let mut prev_end_line = match leading_comments
+            .last()
+            .and_then(|c: &Node| c.token.as_ref())
+        {
+            Some(t) => Span::from_token(t).end_line,
+            None => 0,
+        };
+        for stmt in &stmts {
+            match token_lines(stmt) {
+                Some((start_line, end_line)) => {
+                    blank_lines.push(start_line.saturating_sub(prev_end_line + 1));
+                    prev_end_line = end_line;
+                }
+                None => blank_lines.push(0), // EOF without comments
+            }
+        }
+        let layouts = stmts.iter().map(original_layout).collect();
+        Script {
+            leading_comments,
+            stmts,
+            blank_lines,
+            layouts,
+            eof: None,
+        }
+    }
+}
+
+/// Returns the first and the last line of the tokens in `node` (comments included).
+fn token_lines(node: &Node) -> Option<(usize, usize)> {
+    let mut res: Option<(usize, usize)> = None;
+    if let Some(t) = &node.token {
+        if t.line != usize::MAX {
+            res = Some((t.line, Span::from_token(t).end_line));
+        }
+    }
+    for content in node.children.values() {
+        let nodes = match content {
+            ContentType::Node(n) => core::slice::from_ref(n),
+            ContentType::NodeVec(ns) => ns.as_slice(),
+        };
+        for n in nodes {
+            if let Some((start, end)) = token_lines(n) {
+                res = Some(match res {
+                    Some((s, e)) => (s.min(start), e.max(end)),
+                    None => (start, end),
+                });
+            }
+        }
+    }
+    res
+}
 
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub enum ContentType {
@@ -13,6 +159,7 @@ pub enum ContentType {
 }
 
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum NodeType {
     AccessOperator,      // arr[OFFSET(1)] | json['path']
     AddColumnClause,     // ADD COLUMN x INT64 OPTIONS()
@@ -73,6 +220,7 @@ pub enum NodeType {
     GrantStatement,
     GroupByExprs,
     GroupedIdentWithOptions,             // (col OPTIONS())
+    GroupedPattern,                      // (a b+ | c)
     GroupedExpr,                         // (1)
     GroupedExprs,                        // (1, 2, 3)
     GroupedStatement,                    // (SELECT 1)
@@ -86,6 +234,7 @@ pub enum NodeType {
     KeywordWithGroupedXXX, // AS (1 + 1)
     KeywordWithStatement,  // THEN INSERT ROW
     KeywordWithStatements, // THEN SELECT 1;
+    Hint,                  // @{FORCE_INDEX=idx}
     Identifier,
     IdentWithOptions,
     IfStatement,
@@ -97,6 +246,8 @@ pub enum NodeType {
     LimitClause,
     LoadStatement,
     LoopStatement,
+    MatchRecognizeClause,
+    MatchRecognizeConfig, // (PARTITION BY x ORDER BY y MEASURES ... PATTERN (...) DEFINE ...)
     MergeStatement,
     MultiTokenIdentifier,
     NumericLiteral, // 1 | 1.1 | .1E10
@@ -104,6 +255,7 @@ pub enum NodeType {
     OverClause,                // OVER (PARTITON BY x, y)
     OverwritePartitionsClause, // OVERWRITE PARTITIONS (_PARTITIONTIME = ts)
     Parameter,                 // ? | @param
+    PatternDefinition,         // a AS x > 0
     PivotConfig,               // (SUM(c1) FOR c2 IN (v1, v2))
     PivotOperator,
     RaiseStatement,
@@ -111,10 +263,11 @@ pub enum NodeType {
     RenameColumnClause,
     RepeatStatement,
     RevokeStatement,
-    SelectStatement,      // SELECT 1;
-    SetOperator,          // UNION | INTERSECT | EXCEPT
-    SetStatement,         // SET x = 5
-    SingleTokenStatement, // BREAK; | LEAVE; | ...
+    SelectStatement,            // SELECT 1;
+    SetOperator,                // UNION | INTERSECT | EXCEPT
+    SetStatement,               // SET x = 5
+    SetSystemVariableStatement, // SET @@time_zone = 'UTC'
+    SingleTokenStatement,       // BREAK; | LEAVE; | ...
     StringLiteral,
     StructLiteral,
     Symbol,                          // ) | ] | * | ...
@@ -129,6 +282,7 @@ pub enum NodeType {
     UnaryOperator,   // - | + | TIMESTAMP | ...
     Unknown,
     UndropStatement,
+    UnparsedStatement, // see Parser::parse_code_with_recovery
     UnpivotOperator,
     UnpivotConfig, // ((c1, c2) FOR v IN ((v1, v2) 1, (v3, v4) 3))
     UpdateStatement,
@@ -149,7 +303,19 @@ pub enum NodeType {
 pub struct Node {
     pub token: Option<Token>,
     pub node_type: NodeType,
-    pub children: HashMap<String, ContentType>,
+    /// Children are sorted (and serialized) in lexicographic order of their keys,
+    /// regardless of the order in which they were pushed.
+    pub children: BTreeMap<String, ContentType>,
+    /// `true` if the node is placed between `-- bq2cst: off` and `-- bq2cst: on`,
+    /// which means it should be printed as it is written.
+    /// Only the outermost nodes in the region are marked.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub format_exempt: bool,
+    /// `key: value` lines in the comments placed immediately before a statement
+    /// (e.g. `-- depends_on: dataset.table`).
+    /// Values of the same key are kept in order of appearance.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, Vec<String>>,
 }
 
 impl Node {
@@ -157,14 +323,18 @@ impl Node {
         Node {
             token: Some(token),
             node_type,
-            children: HashMap::new(),
+            children: BTreeMap::new(),
+            format_exempt: false,
+            annotations: BTreeMap::new(),
         }
     }
     pub fn empty(node_type: NodeType) -> Node {
         Node {
             token: None,
             node_type,
-            children: HashMap::new(),
+            children: BTreeMap::new(),
+            format_exempt: false,
+            annotations: BTreeMap::new(),
         }
     }
     fn format(&self, indent: usize, is_array: bool) -> String {
@@ -174,26 +344,23 @@ impl Node {
             Some(t) => t.literal,
             None => "None".to_string(),
         };
-        let self_;
-        if is_array {
-            self_ = format!("{}- self: {}", " ".repeat((indent - 1) * 2), literal);
+        let self_ = if is_array {
+            format!("{}- self: {}", " ".repeat((indent - 1) * 2), literal)
         } else {
-            self_ = format!("{}self: {}", " ".repeat(indent * 2), literal);
-        }
+            format!("{}self: {}", " ".repeat(indent * 2), literal)
+        };
         let type_ = format!("{:?}", self.node_type);
         res.push(format!("{} ({})", self_, type_));
         // children
-        let mut keys: Vec<&String> = self.children.keys().collect();
-        keys.sort();
-        for k in keys {
-            match self.children.get(k) {
-                Some(ContentType::Node(n)) => {
+        for (k, content) in &self.children {
+            match content {
+                ContentType::Node(n) => {
                     res.push(format!("{}{}:", " ".repeat(indent * 2), k));
                     res.push(n.format(indent + 1, false));
                 }
-                Some(ContentType::NodeVec(ns)) => {
+                ContentType::NodeVec(ns) => {
                     let mut empty_array = " []";
-                    if ns.len() != 0 {
+                    if !ns.is_empty() {
                         empty_array = ""
                     };
                     res.push(format!("{}{}:{}", " ".repeat(indent * 2), k, empty_array));
@@ -201,11 +368,86 @@ impl Node {
                         res.push(n.format(indent + 1, true));
                     }
                 }
-                None => panic!(),
             }
         }
         res.join("\n")
     }
+    /// Returns an indented tree which is easier to read than JSON (e.g. in bug reports).
+    /// Each line has the key, the node type, the token and the span of the whole node.
+    ///
+    /// ```text
+    /// SelectStatement `SELECT` 1:1..1:16
+    ///   exprs[0]: Identifier `a` 1:8..1:9
+    ///   from: KeywordWithExpr `FROM` 1:10..1:16
+    ///     expr: Identifier `t` 1:15..1:16
+    /// ```
+    pub fn debug_tree(&self) -> String {
+        let mut res = String::new();
+        self.push_debug_tree(&mut res, "", 0);
+        res
+    }
+    fn push_debug_tree(&self, res: &mut String, key: &str, indent: usize) {
+        res.push_str(&"  ".repeat(indent));
+        if !key.is_empty() {
+            res.push_str(&format!("{}: ", key));
+        }
+        res.push_str(&format!("{:?}", self.node_type));
+        if let Some(t) = &self.token {
+            if t.line != usize::MAX {
+                let mut literal: String = t.literal.chars().take(30).collect();
+                if literal.len() < t.literal.len() {
+                    literal.push_str("...");
+                }
+                res.push_str(&format!(" `{}`", literal.replace('\n', "\\n")));
+            }
+        }
+        if let Some(span) = self.span() {
+            res.push_str(&format!(
+                " {}:{}..{}:{}",
+                span.start_line, span.start_column, span.end_line, span.end_column
+            ));
+        }
+        res.push('\n');
+        for (k, content) in &self.children {
+            match content {
+                ContentType::Node(n) => n.push_debug_tree(res, k, indent + 1),
+                ContentType::NodeVec(ns) => {
+                    for (i, n) in ns.iter().enumerate() {
+                        n.push_debug_tree(res, &format!("{}[{}]", k, i), indent + 1);
+                    }
+                }
+            }
+        }
+    }
+    /// From the first token to the end of the last token (comments included).
+    fn span(&self) -> Option<Span> {
+        let mut first: Option<&Token> = None;
+        let mut last: Option<&Token> = None;
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            if let Some(t) = node.token.as_ref().filter(|t| t.line != usize::MAX) {
+                if first.is_none_or(|f| (t.line, t.column) < (f.line, f.column)) {
+                    first = Some(t);
+                }
+                if last.is_none_or(|l| (l.line, l.column) < (t.line, t.column)) {
+                    last = Some(t);
+                }
+            }
+            for content in node.children.values() {
+                match content {
+                    ContentType::Node(n) => stack.push(n),
+                    ContentType::NodeVec(ns) => stack.extend(ns),
+                }
+            }
+        }
+        let (first, last) = (first?, Span::from_token(last?));
+        Some(Span::new(
+            first.line,
+            first.column,
+            last.end_line,
+            last.end_column,
+        ))
+    }
     pub fn push_node(&mut self, key: &str, node: Node) {
         self.children
             .insert(key.to_string(), ContentType::Node(node));
@@ -214,10 +456,51 @@ impl Node {
         self.children
             .insert(key.to_string(), ContentType::NodeVec(nodes));
     }
+    /// Returns the documentation of the object created by a CREATE statement,
+    /// which are the `leading_comments` placed immediately before `CREATE`.
+    /// Either a single `/** ... */` block or consecutive `--` lines are recognized
+    /// and no blank line is allowed between them and `CREATE`.
+    pub fn doc_comments(&self) -> Vec<&Token> {
+        let mut res = Vec::new();
+        let (comments, mut next_line) = match (self.children.get("leading_comments"), &self.token) {
+            (Some(ContentType::NodeVec(comments)), Some(t)) if t.is("CREATE") => (comments, t.line),
+            _ => return res,
+        };
+        for token in comments.iter().rev().filter_map(|c| c.token.as_ref()) {
+            let last_line = token.line + token.literal.matches('\n').count();
+            if last_line + 1 != next_line {
+                break;
+            }
+            match token.comment_kind() {
+                Some(CommentKind::DocBlock) if res.is_empty() => {
+                    res.push(token);
+                    break;
+                }
+                Some(CommentKind::DoubleDash) => {
+                    res.push(token);
+                    next_line = token.line;
+                }
+                _ => break,
+            }
+        }
+        res.reverse();
+        res
+    }
+    /// Returns `true` if the last element of `exprs` is followed by a comma
+    /// (e.g. `SELECT a, b, FROM t`, `[1, 2,]`).
+    /// The comma itself is the `comma` child of the last element.
+    pub fn has_trailing_comma(&self) -> bool {
+        match self.children.get("exprs") {
+            Some(ContentType::NodeVec(exprs)) => exprs
+                .last()
+                .is_some_and(|expr| expr.children.contains_key("comma")),
+            _ => false,
+        }
+    }
 }
 
 impl fmt::Display for Node {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}\n", self.format(0, false))
+        writeln!(f, "{}", self.format(0, false))
     }
 }
@@ -21,7 +21,7 @@ fn test_to_string() {
     let mut parent = Node::new(Token::from_str0("parent"), NodeType::Unknown);
     parent.push_node("son", son);
     parent.push_node("daughter", daughter);
-    let res = format!("{}", parent.to_string());
+    let res = format!("{}", parent);
 
     println!("{}", res);
     assert_eq!(
@@ -40,3 +40,163 @@ son:
         res
     );
 }
+
+#[test]
+fn test_children_order() {
+    let mut node = Node::new(Token::from_str0("SELECT"), NodeType::SelectStatement);
+    // pushed in the order of appearance
+    node.push_node_vec("exprs", vec![]);
+    node.push_node(
+        "from",
+        Node::new(Token::from_str0("FROM"), NodeType::KeywordWithExpr),
+    );
+    node.push_node(
+        "where",
+        Node::new(Token::from_str0("WHERE"), NodeType::KeywordWithExpr),
+    );
+    node.push_node(
+        "semicolon",
+        Node::new(Token::from_str0(";"), NodeType::Symbol),
+    );
+    let keys: Vec<&str> = node.children.keys().map(|k| k.as_str()).collect();
+    assert_eq!(keys, vec!["exprs", "from", "semicolon", "where"]);
+}
+
+#[test]
+fn test_cst_output() {
+    let stmts = vec![Node::empty(NodeType::EOF)];
+    assert_eq!(
+        CstOutput::new(stmts.clone(), 1),
+        Some(CstOutput::V1(stmts.clone()))
+    );
+    match CstOutput::new(stmts.clone(), CST_VERSION) {
+        Some(CstOutput::V2(cst)) => {
+            assert_eq!(cst.cst_version, CST_VERSION);
+            assert_eq!(cst.stmts, stmts);
+        }
+        output => panic!("{:?}", output),
+    }
+    assert_eq!(CstOutput::new(stmts, 0), None);
+}
+
+#[test]
+fn test_script() {
+    let code = "\
+-- license header
+
+-- about the first statement
+SELECT 1;
+
+
+/* a multi-line
+comment */
+SELECT 2; SELECT 3;
+-- end of file
+";
+    let tokens = crate::lexer::Lexer::new(code.to_string())
+        .tokenize_code()
+        .unwrap();
+    let script = crate::parser::Parser::new(tokens).parse_script().unwrap();
+    let literals: Vec<&str> = script
+        .leading_comments
+        .iter()
+        .map(|c| c.token.as_ref().unwrap().literal.as_str())
+        .collect();
+    assert_eq!(literals, vec!["-- license header"]);
+    match script.stmts[0].children.get("leading_comments") {
+        Some(ContentType::NodeVec(comments)) => assert_eq!(comments.len(), 1),
+        other => panic!("{:?}", other),
+    }
+    assert_eq!(script.stmts.len(), 4);
+    assert_eq!(script.blank_lines, vec![1, 2, 0, 0]);
+    assert_eq!(script.layouts.len(), 4);
+
+    // without header comments
+    let tokens = crate::lexer::Lexer::new("-- comment\nSELECT 1;".to_string())
+        .tokenize_code()
+        .unwrap();
+    let script = crate::parser::Parser::new(tokens).parse_script().unwrap();
+    assert!(script.leading_comments.is_empty());
+    assert!(script.stmts[0].children.contains_key("leading_comments"));
+    assert_eq!(script.blank_lines, vec![0, 0]);
+    let eof = script.eof.unwrap();
+    assert_eq!((eof.line, eof.column, eof.offset), (2, 10, Some(20)));
+
+    // an error at the end of the input points to the position of EOF
+    let tokens = crate::lexer::Lexer::new("SELECT 1 FROM\n".to_string())
+        .tokenize_code()
+        .unwrap();
+    let error = crate::parser::Parser::new(tokens)
+        .parse_script()
+        .unwrap_err();
+    assert_eq!((error.line, error.column), (2, 1));
+}
+
+#[test]
+fn test_debug_tree() {
+    let tokens = crate::lexer::Lexer::new("SELECT a FROM t -- comment\n;".to_string())
+        .tokenize_code()
+        .unwrap();
+    let stmts = crate::parser::Parser::new(tokens).parse_code().unwrap();
+    assert_eq!(
+        stmts[0].debug_tree(),
+        "\
+SelectStatement `SELECT` 1:1..2:2
+  exprs[0]: Identifier `a` 1:8..1:9
+  from: KeywordWithExpr `FROM` 1:10..1:27
+    expr: Identifier `t` 1:15..1:27
+      trailing_comments[0]: Comment `-- comment` 1:17..1:27
+  semicolon: Symbol `;` 2:1..2:2
+"
+    );
+    assert_eq!(stmts[1].debug_tree(), "EOF\n");
+
+    let long = Node::new(
+        Token::new(1, 1, "'''\n".repeat(10)),
+        NodeType::StringLiteral,
+    );
+    assert!(long.debug_tree().starts_with("StringLiteral `'''\\n'''\\n"));
+    assert!(long.debug_tree().contains("...` 1:1..11:1"));
+}
+
+#[test]
+fn test_doc_comments() {
+    let code = "\
+-- not doc
+
+-- doc 1
+-- doc 2
+CREATE TABLE t (x INT64);
+/* not doc */
+CREATE VIEW v AS SELECT 1;
+/**
+ * doc
+ */
+CREATE SCHEMA s;
+-- doc
+SELECT 1;
+";
+    let tokens = crate::lexer::Lexer::new(code.to_string())
+        .tokenize_code()
+        .unwrap();
+    let stmts = crate::parser::Parser::new(tokens).parse_code().unwrap();
+    let docs: Vec<Vec<&str>> = stmts
+        .iter()
+        .map(|s| {
+            s.doc_comments()
+                .iter()
+                .map(|t| t.literal.as_str())
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        docs,
+        vec![
+            vec!["-- doc 1", "-- doc 2"],
+            vec![],
+            vec!["/**\n * doc\n */"],
+            vec![],
+            vec![],
+        ]
+    );
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests;
+
+use crate::error::BQ2CSTError;
+use crate::token::Token;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A range of the source code.
+/// `start_*` points to the first character and `end_*` points to the next of the last character.
+/// Like `Token`, both line and column are 1-based.
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Span {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    pub fn new(start_line: usize, start_column: usize, end_line: usize, end_column: usize) -> Span {
+        Span {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+    pub fn from_token(token: &Token) -> Span {
+        let mut end_line = token.line;
+        let mut end_column = token.column;
+        for ch in token.literal.chars() {
+            if ch == '\n' {
+                end_line += 1;
+                end_column = 1;
+            } else {
+                end_column += 1;
+            }
+        }
+        Span::new(token.line, token.column, end_line, end_column)
+    }
+}
+
+/// A problem found in the source code which does not stop parsing.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub span: Span,
+    pub code: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn warning(code: &str, span: Span, message: String) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            span,
+            code: code.to_string(),
+            message,
+        }
+    }
+    /// Converts a lexer or parser error, e.g. to report it together with warnings.
+    pub fn from_error(error: &BQ2CSTError) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Error,
+            span: error.span.unwrap_or_else(|| {
+                Span::new(
+                    error.line,
+                    error.column,
+                    error.line,
+                    error.column.saturating_add(1),
+                )
+            }),
+            code: error.code.as_str().to_string(),
+            message: error.message().to_string(),
+        }
+    }
+}
+
+/// Warnings which can be found only by looking at tokens.
+/// * W002: both `!=` and `<>` are used
+/// * W003: `#legacySQL` is specified
+pub(crate) fn lint_tokens(tokens: &[Token]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut inequality: Option<&str> = None;
+    for token in tokens {
+        match token.literal.as_str() {
+            "!=" | "<>" => match inequality {
+                None => inequality = Some(&token.literal),
+                Some(first) if first != token.literal => diagnostics.push(Diagnostic::warning(
+                    "W002",
+                    Span::from_token(token),
+                    format!(
+                        "`{}` is used but `{}` is used elsewhere.",
+                        token.literal, first
+                    ),
+                )),
+                _ => (),
+            },
+            _ => {
+                if token.is_comment() && token.literal.trim_end().eq_ignore_ascii_case("#legacySQL")
+                {
+                    diagnostics.push(Diagnostic::warning(
+                        "W003",
+                        Span::from_token(token),
+                        "Legacy SQL is deprecated and is not supported.".to_string(),
+                    ))
+                }
+            }
+        }
+    }
+    diagnostics
+}
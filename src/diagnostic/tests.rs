@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn test_span_from_token() {
+    assert_eq!(
+        Span::from_token(&Token::from_str(2, 3, "abc")),
+        Span::new(2, 3, 2, 6)
+    );
+    assert_eq!(
+        Span::from_token(&Token::from_str(2, 3, "/*\n  */")),
+        Span::new(2, 3, 3, 5)
+    );
+}
+
+#[test]
+fn test_lint_tokens() {
+    let test_cases = vec![
+        (vec![Token::from_str(1, 10, "!=")], vec![]),
+        (
+            vec![Token::from_str(1, 10, "<>"), Token::from_str(2, 10, "!=")],
+            vec![("W002", Span::new(2, 10, 2, 12))],
+        ),
+        (
+            vec![Token::from_str(1, 1, "#legacySQL")],
+            vec![("W003", Span::new(1, 1, 1, 11))],
+        ),
+        (vec![Token::from_str(1, 1, "#standardSQL")], vec![]),
+    ];
+    for (tokens, expected) in test_cases {
+        let diagnostics = lint_tokens(&tokens);
+        let actual: Vec<(&str, Span)> = diagnostics
+            .iter()
+            .map(|d| (d.code.as_str(), d.span))
+            .collect();
+        assert_eq!(expected, actual, "{:?}", tokens);
+    }
+}
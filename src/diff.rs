@@ -0,0 +1,205 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+/// A difference between two CSTs.
+/// `path` is the location of the subtree (e.g. `from.expr.left`, `exprs[1]`).
+/// The index of `Removed` refers to the old tree and the others refer to the new tree.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub enum CstEdit {
+    Inserted { path: String, node: Node },
+    Removed { path: String, node: Node },
+    Changed { path: String, old: Node, new: Node },
+}
+
+/// Returns the structural differences between `old` and `new`.
+/// Positions, whitespace and comments are ignored and keywords are case-insensitive.
+pub fn diff(old: &Node, new: &Node) -> Vec<CstEdit> {
+    let mut edits = Vec::new();
+    diff_node(old, new, "", &mut edits);
+    edits
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn is_trivia(key: &str) -> bool {
+    key == "leading_comments" || key == "trailing_comments"
+}
+
+fn same_token(old: &Node, new: &Node) -> bool {
+    if old.node_type != new.node_type {
+        return false;
+    }
+    match (&old.token, &new.token) {
+        (Some(o), Some(n)) => {
+            if o.is_identifier() || o.is_string() {
+                o.literal == n.literal
+            } else {
+                o.literal.eq_ignore_ascii_case(&n.literal)
+            }
+        }
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn sorted_keys(node: &Node) -> Vec<&String> {
+    let mut keys: Vec<&String> = node.children.keys().filter(|k| !is_trivia(k)).collect();
+    keys.sort();
+    keys
+}
+
+fn same_subtree(old: &Node, new: &Node) -> bool {
+    if !same_token(old, new) || sorted_keys(old) != sorted_keys(new) {
+        return false;
+    }
+    for key in sorted_keys(old) {
+        let same = match (old.children.get(key), new.children.get(key)) {
+            (Some(ContentType::Node(o)), Some(ContentType::Node(n))) => same_subtree(o, n),
+            (Some(ContentType::NodeVec(os)), Some(ContentType::NodeVec(ns))) => {
+                os.len() == ns.len() && os.iter().zip(ns).all(|(o, n)| same_subtree(o, n))
+            }
+            _ => false,
+        };
+        if !same {
+            return false;
+        }
+    }
+    true
+}
+
+fn diff_node(old: &Node, new: &Node, path: &str, edits: &mut Vec<CstEdit>) {
+    if !same_token(old, new) {
+        edits.push(CstEdit::Changed {
+            path: path.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        });
+        return;
+    }
+    let mut keys = sorted_keys(old);
+    keys.extend(sorted_keys(new));
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let child_path = join(path, key);
+        match (old.children.get(key), new.children.get(key)) {
+            (Some(ContentType::Node(o)), Some(ContentType::Node(n))) => {
+                diff_node(o, n, &child_path, edits)
+            }
+            (Some(ContentType::NodeVec(os)), Some(ContentType::NodeVec(ns))) => {
+                diff_node_vec(os, ns, &child_path, edits)
+            }
+            (Some(o), Some(n)) => {
+                // Node <-> NodeVec (not expected)
+                diff_node_vec(&to_vec(o), &to_vec(n), &child_path, edits)
+            }
+            (Some(o), None) => {
+                for (i, node) in to_vec(o).into_iter().enumerate() {
+                    edits.push(CstEdit::Removed {
+                        path: vec_path(o, &child_path, i),
+                        node,
+                    });
+                }
+            }
+            (None, Some(n)) => {
+                for (i, node) in to_vec(n).into_iter().enumerate() {
+                    edits.push(CstEdit::Inserted {
+                        path: vec_path(n, &child_path, i),
+                        node,
+                    });
+                }
+            }
+            (None, None) => (),
+        }
+    }
+}
+
+fn to_vec(content: &ContentType) -> Vec<Node> {
+    match content {
+        ContentType::Node(n) => vec![n.clone()],
+        ContentType::NodeVec(ns) => ns.clone(),
+    }
+}
+
+fn vec_path(content: &ContentType, path: &str, idx: usize) -> String {
+    match content {
+        ContentType::Node(_) => path.to_string(),
+        ContentType::NodeVec(_) => format!("{}[{}]", path, idx),
+    }
+}
+
+/// Aligns the elements by the longest common subsequence
+/// and compares the unmatched ones.
+fn diff_node_vec(old: &[Node], new: &[Node], path: &str, edits: &mut Vec<CstEdit>) {
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if same_subtree(&old[i], &new[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut removed: Vec<usize> = Vec::new();
+    let mut inserted: Vec<usize> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && same_subtree(&old[i], &new[j]) {
+            flush(old, new, &removed, &inserted, path, edits);
+            removed.clear();
+            inserted.clear();
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            inserted.push(j);
+            j += 1;
+        } else {
+            removed.push(i);
+            i += 1;
+        }
+    }
+    flush(old, new, &removed, &inserted, path, edits);
+}
+
+fn flush(
+    old: &[Node],
+    new: &[Node],
+    removed: &[usize],
+    inserted: &[usize],
+    path: &str,
+    edits: &mut Vec<CstEdit>,
+) {
+    let paired = removed.len().min(inserted.len());
+    for k in 0..paired {
+        let child_path = format!("{}[{}]", path, inserted[k]);
+        diff_node(&old[removed[k]], &new[inserted[k]], &child_path, edits);
+    }
+    for i in &removed[paired..] {
+        edits.push(CstEdit::Removed {
+            path: format!("{}[{}]", path, i),
+            node: old[*i].clone(),
+        });
+    }
+    for j in &inserted[paired..] {
+        edits.push(CstEdit::Inserted {
+            path: format!("{}[{}]", path, j),
+            node: new[*j].clone(),
+        });
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+use crate::utils::parse;
+
+fn summary(edits: &[CstEdit]) -> Vec<String> {
+    edits
+        .iter()
+        .map(|e| match e {
+            CstEdit::Inserted { path, node } => {
+                format!("+ {} {}", path, node.token.as_ref().unwrap().literal)
+            }
+            CstEdit::Removed { path, node } => {
+                format!("- {} {}", path, node.token.as_ref().unwrap().literal)
+            }
+            CstEdit::Changed { path, old, new } => format!(
+                "~ {} {} -> {}",
+                path,
+                old.token.as_ref().unwrap().literal,
+                new.token.as_ref().unwrap().literal
+            ),
+        })
+        .collect()
+}
+
+#[test]
+fn test_diff() {
+    let test_cases = vec![
+        ("SELECT a FROM t", "select a\nfrom t -- comment", vec![]),
+        (
+            "SELECT a FROM t",
+            "SELECT a FROM u",
+            vec!["~ from.expr t -> u"],
+        ),
+        (
+            "SELECT a, c FROM t",
+            "SELECT a, b, c FROM t",
+            vec!["+ exprs[1] b"],
+        ),
+        (
+            "SELECT a, b FROM t",
+            "SELECT b FROM t",
+            vec!["- exprs[0] a"],
+        ),
+        (
+            "SELECT a FROM t",
+            "SELECT a FROM t WHERE true",
+            vec!["+ where WHERE"],
+        ),
+    ];
+    for (old, new, expected) in test_cases {
+        let edits = diff(&parse(old)[0], &parse(new)[0]);
+        assert_eq!(expected, summary(&edits), "{} / {}", old, new);
+    }
+}
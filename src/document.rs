@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests;
+
+use crate::analysis;
+use crate::cst::Node;
+use crate::diagnostic::{self, Diagnostic};
+use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::lexer::Lexer;
+use crate::options::ParseOptions;
+use crate::parser::Parser;
+use crate::stats::{self, ParseStats};
+use crate::token::Token;
+use alloc::{string::String, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+/// The result of parsing a whole file.
+/// In addition to the statements, it holds what is placed before SQL.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Document {
+    /// `true` if the input started with a UTF-8 BOM (it is stripped before lexing).
+    pub bom: bool,
+    /// Each header line (see `ParseOptions.header_lines`) as a token.
+    pub header: Vec<Token>,
+    pub stmts: Vec<Node>,
+    /// Problems which do not stop parsing, sorted by their positions.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Parser errors. Always empty unless `ParseOptions.recover_from_errors` is `true`.
+    pub errors: Vec<BQ2CSTError>,
+    /// Sizes of the result, which are useful to set limits on inputs.
+    #[serde(default)]
+    pub stats: ParseStats,
+}
+
+/// Buffers of the lexer and the parser which are reused by `parse_document_with_buffers`
+/// so that parsing many files (see `ParserEngine::parse_project`)
+/// allocates them only for the largest file instead of for each file.
+#[derive(Default)]
+pub(crate) struct Buffers {
+    chars: Vec<char>,
+    tokens: Vec<Token>,
+}
+
+pub fn parse_document(code: String, options: ParseOptions) -> BQ2CSTResult<Document> {
+    parse_document_with_buffers(&code, options, &mut Buffers::default())
+}
+
+pub(crate) fn parse_document_with_buffers(
+    code: &str,
+    options: ParseOptions,
+    buffers: &mut Buffers,
+) -> BQ2CSTResult<Document> {
+    #[cfg(feature = "alloc-stats")]
+    let base = stats::reset_peak_allocation();
+    let mut lexer = Lexer::with_buffers(
+        code,
+        options.clone(),
+        core::mem::take(&mut buffers.chars),
+        core::mem::take(&mut buffers.tokens),
+    );
+    let tokenized = lexer.tokenize();
+    let bom = lexer.bom;
+    let header = core::mem::take(&mut lexer.header);
+    let tokens = core::mem::take(&mut lexer.tokens);
+    let mut diagnostics = core::mem::take(&mut lexer.warnings);
+    buffers.chars = lexer.into_chars();
+    if let Err(error) = tokenized {
+        buffers.tokens = tokens;
+        return Err(error);
+    }
+    diagnostics.append(&mut diagnostic::lint_tokens(&tokens));
+    let recover_from_errors = options.recover_from_errors;
+    let mut parser = Parser::with_options(tokens, options);
+    let (stmts, errors) = if recover_from_errors {
+        parser.parse_code_with_recovery()
+    } else {
+        match parser.parse_code() {
+            Ok(stmts) => (stmts, Vec::new()),
+            Err(error) => {
+                buffers.tokens = parser.into_tokens();
+                return Err(error);
+            }
+        }
+    };
+    buffers.tokens = parser.into_tokens();
+    diagnostics.append(&mut analysis::check_declare(&stmts));
+    diagnostics.sort_by_key(|d| (d.span.start_line, d.span.start_column));
+    let stats = ParseStats {
+        #[cfg(feature = "alloc-stats")]
+        peak_allocation: base.map(|base| stats::peak_allocation().saturating_sub(base)),
+        ..stats::collect_stats(&stmts)
+    };
+    Ok(Document {
+        bom,
+        header,
+        stmts,
+        diagnostics,
+        errors,
+        stats,
+    })
+}
@@ -0,0 +1,91 @@
+use super::*;
+use crate::diagnostic::Severity;
+
+#[test]
+fn test_parse_document() {
+    let doc = parse_document("\u{feff}SELECT 1;".to_string(), ParseOptions::default())
+        .expect("Failed to parse code.");
+    assert!(doc.bom);
+    assert!(doc.header.is_empty());
+    assert_eq!(doc.stmts.len(), 2); // SELECT and EOF
+
+    let options = ParseOptions {
+        header_lines: 1,
+        ..Default::default()
+    };
+    let doc =
+        parse_document("-- header\nSELECT 1;".to_string(), options).expect("Failed to parse code.");
+    assert!(!doc.bom);
+    assert_eq!(doc.header, vec![Token::new(1, 1, "-- header".to_string())]);
+    assert_eq!(doc.stmts.len(), 2);
+    assert!(!doc.stmts[0].children.contains_key("leading_comments"));
+}
+
+#[test]
+fn test_parse_document_diagnostics() {
+    let options = ParseOptions {
+        allow_invalid_escapes: true,
+        ..Default::default()
+    };
+    let doc = parse_document("SELECT 1 <> 2, '\\q', 3 != 4;".to_string(), options)
+        .expect("Failed to parse code.");
+    let codes: Vec<&str> = doc.diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(codes, vec!["W001", "W002"]);
+    assert!(doc
+        .diagnostics
+        .iter()
+        .all(|d| d.severity == Severity::Warning));
+
+    // misplaced DECLARE is not fatal by default
+    let doc = parse_document(
+        "CREATE TEMP FUNCTION f() AS (1);\nDECLARE x INT64;".to_string(),
+        ParseOptions::default(),
+    )
+    .expect("Failed to parse code.");
+    let codes: Vec<&str> = doc.diagnostics.iter().map(|d| d.code.as_str()).collect();
+    assert_eq!(codes, vec!["P009"]);
+    assert_eq!(doc.stmts.len(), 3);
+}
+
+#[test]
+fn test_parse_document_recovery() {
+    let code = "SELECT 1 NOT x;\nSELECT 2;".to_string();
+    assert!(parse_document(code.clone(), ParseOptions::default()).is_err());
+    let options = ParseOptions {
+        recover_from_errors: true,
+        ..Default::default()
+    };
+    let doc = parse_document(code, options).expect("Failed to parse code.");
+    assert_eq!(doc.errors.len(), 1);
+    assert_eq!(doc.stmts.len(), 3); // UnparsedStatement, SELECT 2 and EOF
+}
+
+#[test]
+fn test_parse_document_jinja() {
+    let options = ParseOptions {
+        jinja_templates: true,
+        ..Default::default()
+    };
+    let doc = parse_document(
+        "{{ config(materialized='table') }}\n{% if true %}SELECT 1{% endif %}".to_string(),
+        options,
+    )
+    .expect("Failed to parse code.");
+    assert_eq!(doc.stmts.len(), 3);
+    assert_eq!(
+        doc.stmts[0].to_string(),
+        "self: {{ config(materialized='table') }} (SingleTokenStatement)\n"
+    );
+    assert_eq!(
+        doc.stmts[1].to_string(),
+        "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 1 (NumericLiteral)
+  trailing_comments:
+  - self: {% endif %} (Template)
+leading_comments:
+- self: {% if true %} (Template)
+"
+    );
+}
@@ -0,0 +1,293 @@
+#[cfg(test)]
+mod tests;
+
+use crate::analysis::{child_nodes, identifier_path, table_references};
+use crate::cst::{ContentType, Node, NodeType};
+use crate::error::{BQ2CSTError, BQ2CSTResult, ErrorCode};
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::token::Token;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Writes the statements back out as code.
+/// Tokens are emitted in the order of their positions,
+/// so the layout of the original code (line breaks, indentation) is kept.
+/// Edits made by this module keep the positions consistent,
+/// which means `unparse(parse(code))` reproduces `code` after any number of edits.
+pub fn unparse(stmts: &[Node]) -> String {
+    let mut tokens = Vec::new();
+    for stmt in stmts {
+        collect_tokens(stmt, &mut tokens);
+    }
+    tokens.retain(|t| !t.literal.is_empty()); // EOF
+    tokens.sort_by_key(|t| (t.line, t.column));
+
+    let mut res = String::new();
+    let (mut line, mut column) = (1, 1);
+    for token in tokens {
+        if line < token.line {
+            res.push_str(&"\n".repeat(token.line - line));
+            column = 1;
+        }
+        if column < token.column {
+            res.push_str(&" ".repeat(token.column - column));
+        } else if token.column < column && !res.ends_with(char::is_whitespace) {
+            // overlapping positions (e.g. a hand-crafted node)
+            res.push(' ');
+        }
+        res.push_str(&token.literal);
+        (line, column) = end_of(token);
+    }
+    res
+}
+
+/// Replaces every reference to the table `old` (e.g. `dataset.table`,
+/// without backticks) with `new`, which is inserted as is.
+/// The replaced identifiers become a single synthetic `Identifier` node
+/// and the following tokens on the same line are moved accordingly.
+/// Returns the number of replaced references.
+pub fn rename_table(stmts: &mut [Node], old: &str, new: &str) -> usize {
+    let mut targets = Vec::new();
+    for stmt in stmts.iter() {
+        for (pos, path) in table_references(stmt) {
+            if path == old {
+                targets.push(pos);
+            }
+        }
+    }
+    // from the end so that the remaining positions are not moved
+    targets.sort();
+    let mut count = 0;
+    for pos in targets.into_iter().rev() {
+        let mut shift = None;
+        for stmt in stmts.iter_mut() {
+            shift = replace_table(stmt, pos, old, new);
+            if shift.is_some() {
+                break;
+            }
+        }
+        if let Some((line, column, delta)) = shift {
+            shift_columns(stmts, line, column, delta);
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Appends the expressions in `code` (e.g. `col AS alias`)
+/// to the select list of `stmts[index]`, which should be a SELECT statement.
+/// The new tokens are placed right after the current last column and marked as synthetic.
+pub fn add_select_column(stmts: &mut [Node], index: usize, code: &str) -> BQ2CSTResult<()> {
+    let mut new_exprs = parse_select_list(code)?;
+    let stmt = match stmts.get(index) {
+        Some(stmt) => stmt,
+        None => {
+            return Err(BQ2CSTError::new(
+                0,
+                0,
+                ErrorCode::Internal,
+                format!("There is no statement at index {}.", index),
+            ))
+        }
+    };
+    if stmt.node_type != NodeType::SelectStatement {
+        let message = "Expected SELECT statement.";
+        return Err(match &stmt.token {
+            Some(t) => BQ2CSTError::from_token(t, ErrorCode::UnexpectedToken, message.to_string()),
+            None => BQ2CSTError::new(0, 0, ErrorCode::UnexpectedToken, message.to_string()),
+        }
+        .with_expected(&["SELECT"]));
+    }
+    let last = match stmt.children.get("exprs") {
+        Some(ContentType::NodeVec(ns)) => ns.last(),
+        _ => None,
+    };
+    let (line, column) = match last.and_then(last_token).map(end_of) {
+        Some(position) => position,
+        None => {
+            // e.g. a hand-crafted node
+            let message = "The select list has no tokens.";
+            return Err(match &stmt.token {
+                Some(t) => BQ2CSTError::from_token(t, ErrorCode::Internal, message.to_string()),
+                None => BQ2CSTError::new(0, 0, ErrorCode::Internal, message.to_string()),
+            });
+        }
+    };
+
+    let cursor = place_tokens(&mut new_exprs, line, column + 2); // after `, `
+    shift_columns(stmts, line, column, (cursor - column) as isize);
+
+    let list = match stmts[index].children.get_mut("exprs") {
+        Some(ContentType::NodeVec(ns)) => ns,
+        _ => unreachable!(),
+    };
+    if let Some(last) = list.last_mut() {
+        last.push_node(
+            "comma",
+            Node::new(Token::synthetic(line, column, ","), NodeType::Symbol),
+        );
+    }
+    list.append(&mut new_exprs);
+    Ok(())
+}
+
+// ----- utilities -----
+fn parse_select_list(code: &str) -> BQ2CSTResult<Vec<Node>> {
+    let mut stmt = parse_select(&format!("SELECT {}", code))?;
+    match stmt.children.remove("exprs") {
+        Some(ContentType::NodeVec(ns)) => Ok(ns),
+        _ => unreachable!(),
+    }
+}
+
+/// Parses `code` which should consist of exactly one SELECT statement.
+pub(crate) fn parse_select(code: &str) -> BQ2CSTResult<Node> {
+    let tokens = Lexer::new(code.to_string()).tokenize_code()?;
+    let mut stmts = Parser::new(tokens).parse_code()?;
+    if stmts.len() != 2 || stmts[0].node_type != NodeType::SelectStatement {
+        return Err(BQ2CSTError::new(
+            1,
+            1,
+            ErrorCode::UnexpectedToken,
+            format!("Expected a single SELECT statement but got `{}`.", code),
+        ));
+    }
+    Ok(stmts.remove(0))
+}
+
+/// Moves the tokens of `nodes` to `line` starting at `column`,
+/// keeping the spacing between them, and marks them as synthetic.
+/// Returns the column right after the last token.
+pub(crate) fn place_tokens(nodes: &mut [Node], line: usize, column: usize) -> usize {
+    let mut tokens = Vec::new();
+    for node in nodes.iter_mut() {
+        collect_tokens_mut(node, &mut tokens);
+    }
+    tokens.sort_by_key(|t| (t.line, t.column));
+    let mut cursor = column;
+    let mut prev: Option<(usize, usize)> = None;
+    for token in tokens {
+        let gap = match prev {
+            Some((l, c)) if l == token.line => token.column - c,
+            Some(_) => 1,
+            None => 0,
+        };
+        prev = Some(end_of(token));
+        token.line = line;
+        token.column = cursor + gap;
+        token.synthetic = true;
+        cursor = token.column + token.literal.chars().count();
+    }
+    cursor
+}
+
+fn collect_tokens<'a>(node: &'a Node, tokens: &mut Vec<&'a Token>) {
+    if let Some(t) = &node.token {
+        tokens.push(t);
+    }
+    for (_, child) in child_nodes(node) {
+        collect_tokens(child, tokens);
+    }
+}
+
+fn collect_tokens_mut<'a>(node: &'a mut Node, tokens: &mut Vec<&'a mut Token>) {
+    if let Some(t) = &mut node.token {
+        tokens.push(t);
+    }
+    for content in node.children.values_mut() {
+        match content {
+            ContentType::Node(n) => collect_tokens_mut(n, tokens),
+            ContentType::NodeVec(ns) => {
+                for n in ns {
+                    collect_tokens_mut(n, tokens);
+                }
+            }
+        }
+    }
+}
+
+fn last_token(node: &Node) -> Option<&Token> {
+    let mut tokens = Vec::new();
+    collect_tokens(node, &mut tokens);
+    tokens
+        .into_iter()
+        .filter(|t| !t.is_comment())
+        .max_by_key(|t| (t.line, t.column))
+}
+
+/// Returns the position right after `token`.
+fn end_of(token: &Token) -> (usize, usize) {
+    match token.literal.rsplit_once('\n') {
+        Some((before, after)) => (
+            token.line + before.matches('\n').count() + 1,
+            after.chars().count() + 1,
+        ),
+        None => (token.line, token.column + token.literal.chars().count()),
+    }
+}
+
+/// Moves the tokens at or after `line:column` (on the same line) by `delta`.
+fn shift_columns(stmts: &mut [Node], line: usize, column: usize, delta: isize) {
+    let mut tokens = Vec::new();
+    for stmt in stmts.iter_mut() {
+        collect_tokens_mut(stmt, &mut tokens);
+    }
+    for token in tokens {
+        if token.line == line && column <= token.column {
+            token.column = token.column.saturating_add_signed(delta);
+        }
+    }
+}
+
+fn path_tokens<'a>(node: &'a Node, tokens: &mut Vec<&'a Token>) {
+    if let Some(t) = &node.token {
+        tokens.push(t);
+    }
+    for (key, child) in child_nodes(node) {
+        if ["left", "right", "trailing_idents"].contains(&key.as_str()) {
+            path_tokens(child, tokens);
+        }
+    }
+}
+
+/// Returns the line, the former end column and the change of width.
+fn replace_table(
+    node: &mut Node,
+    pos: (usize, usize),
+    old: &str,
+    new: &str,
+) -> Option<(usize, usize, isize)> {
+    if let Some((p, path)) = identifier_path(node) {
+        if p == pos && path == old {
+            let mut tokens = Vec::new();
+            path_tokens(node, &mut tokens);
+            let (line, column) = tokens.iter().map(|t| end_of(t)).max()?;
+            if line != pos.0 {
+                return None;
+            }
+            node.token = Some(Token::synthetic(pos.0, pos.1, new));
+            node.node_type = NodeType::Identifier;
+            for key in ["left", "right", "trailing_idents"] {
+                node.children.remove(key);
+            }
+            let delta = new.chars().count() as isize - (column - pos.1) as isize;
+            return Some((line, column, delta));
+        }
+    }
+    for content in node.children.values_mut() {
+        let nodes = match content {
+            ContentType::Node(n) => core::slice::from_mut(n),
+            ContentType::NodeVec(ns) => ns.as_mut_slice(),
+        };
+        for n in nodes {
+            if let Some(res) = replace_table(n, pos, old, new) {
+                return Some(res);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,84 @@
+use super::*;
+use crate::utils::parse;
+
+#[test]
+fn test_unparse() {
+    let code = "\
+-- comment
+SELECT
+  a.b, -- trailing
+  f(x) AS y /* block */
+FROM `p.d.t`
+WHERE c = 'x';
+
+SELECT 1;";
+    assert_eq!(unparse(&parse(code)), code);
+
+    // comments before CREATE
+    let code = "\
+/** doc */
+CREATE SCHEMA s;
+-- a
+-- b
+CREATE TABLE t (x INT64);";
+    assert_eq!(unparse(&parse(code)), code);
+}
+
+#[test]
+fn test_rename_table() {
+    let code = "\
+WITH cte AS (SELECT * FROM d.t)
+SELECT * FROM d.t AS x JOIN cte USING(id) WHERE d.t = 1;
+DELETE d.t WHERE true;";
+    let mut stmts = parse(code);
+    assert_eq!(
+        rename_table(&mut stmts, "d.t", "`project.dataset.table`"),
+        3
+    );
+    assert_eq!(
+        unparse(&stmts),
+        "\
+WITH cte AS (SELECT * FROM `project.dataset.table`)
+SELECT * FROM `project.dataset.table` AS x JOIN cte USING(id) WHERE d.t = 1;
+DELETE `project.dataset.table` WHERE true;"
+    );
+    assert_eq!(rename_table(&mut stmts, "cte", "other"), 0);
+    let renamed = unparse(&stmts);
+    assert_eq!(unparse(&parse(&renamed)), renamed);
+    // rename back
+    assert_eq!(rename_table(&mut stmts, "project.dataset.table", "d.t"), 3);
+    assert_eq!(unparse(&stmts), code);
+}
+
+#[test]
+fn test_add_select_column() {
+    let mut stmts = parse("SELECT a FROM t; SELECT 1;\nSELECT\n  b -- comment\nFROM u");
+    add_select_column(&mut stmts, 0, "x+1  AS y").unwrap();
+    add_select_column(&mut stmts, 1, "2").unwrap();
+    add_select_column(&mut stmts, 2, "c").unwrap();
+    assert_eq!(
+        unparse(&stmts),
+        "SELECT a, x+1  AS y FROM t; SELECT 1, 2;\nSELECT\n  b, c -- comment\nFROM u"
+    );
+    let exprs = match stmts[0].children.get("exprs") {
+        Some(ContentType::NodeVec(ns)) => ns,
+        _ => panic!(),
+    };
+    assert_eq!(exprs.len(), 2);
+    assert!(exprs[0].children.contains_key("comma"));
+    assert!(exprs[1].token.as_ref().unwrap().synthetic);
+    assert!(!exprs[0].token.as_ref().unwrap().synthetic);
+
+    let mut stmts = parse("DELETE t WHERE true");
+    let err = add_select_column(&mut stmts, 0, "c").unwrap_err();
+    assert_eq!(err.code, ErrorCode::UnexpectedToken);
+    assert!(add_select_column(&mut parse("SELECT 1"), 0, "2; SELECT 3").is_err());
+    // out of range
+    let err = add_select_column(&mut parse("SELECT 1"), 2, "2").unwrap_err();
+    assert_eq!(err.code, ErrorCode::Internal);
+    // the select list without tokens
+    let mut stmts = parse("SELECT 1");
+    stmts[0].children.remove("exprs");
+    let err = add_select_column(&mut stmts, 0, "2").unwrap_err();
+    assert_eq!(err.code, ErrorCode::Internal);
+}
@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::Node;
+use crate::diagnostic::Diagnostic;
+use crate::document::{self, Buffers, Document};
+use crate::error::{BQ2CSTError, BQ2CSTResult, ErrorCode};
+use crate::lexer::Lexer;
+use crate::options::ParseOptions;
+use crate::parser::Parser;
+use crate::token::Token;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+/// A file of `Project`.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFile {
+    pub path: String,
+    pub document: BQ2CSTResult<Document>,
+}
+
+/// A diagnostic of `Project` and the path of the file.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDiagnostic {
+    pub path: String,
+    pub diagnostic: Diagnostic,
+}
+
+/// The result of `ParserEngine::parse_project`.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    /// In the same order as the input.
+    pub files: Vec<ProjectFile>,
+    /// The diagnostics and the errors of all the files.
+    pub diagnostics: Vec<ProjectDiagnostic>,
+}
+
+/// Parses many inputs with the same options.
+/// `Lexer` and `Parser` are consumed by a single input,
+/// whereas the engine is constructed once and only borrowed by each call.
+/// It holds no mutable state, so `&ParserEngine` can be shared between threads
+/// (e.g. a linter parsing thousands of queries in parallel).
+/// The keyword tables are built at compile time (see `constants`).
+#[derive(Debug, Clone, Default)]
+pub struct ParserEngine {
+    options: ParseOptions,
+}
+
+impl ParserEngine {
+    pub fn new(options: ParseOptions) -> ParserEngine {
+        ParserEngine { options }
+    }
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+    pub fn tokenize(&self, code: &str) -> BQ2CSTResult<Vec<Token>> {
+        Lexer::with_options(code.to_string(), self.options.clone()).tokenize_code()
+    }
+    /// Same as `Parser::parse_code`.
+    pub fn parse(&self, code: &str) -> BQ2CSTResult<Vec<Node>> {
+        let tokens = self.tokenize(code)?;
+        Parser::with_options(tokens, self.options.clone()).parse_code()
+    }
+    /// Same as `document::parse_document`.
+    pub fn parse_document(&self, code: &str) -> BQ2CSTResult<Document> {
+        document::parse_document_with_buffers(code, self.options.clone(), &mut Buffers::default())
+    }
+    /// Parses every `(path, code)` as a document.
+    /// A file which cannot be parsed does not stop the others,
+    /// its error is reported in `Project.diagnostics` as `Severity::Error`.
+    ///
+    /// The files are parsed one by one, reusing the buffers of the lexer and the parser
+    /// (the characters of the code and the list of the tokens).
+    /// They are allocated for the largest file once, instead of for each file.
+    pub fn parse_project(&self, files: &[(&str, &str)]) -> Project {
+        let mut project = Project {
+            files: Vec::with_capacity(files.len()),
+            diagnostics: Vec::new(),
+        };
+        let mut buffers = Buffers::default();
+        for (path, code) in files {
+            let document =
+                document::parse_document_with_buffers(code, self.options.clone(), &mut buffers);
+            project.push_file(path, document);
+        }
+        project
+    }
+    /// Same as `parse_project` but the files are concatenated into `batch`
+    /// (e.g. read at once by a linter) and `files` has the path and the byte offset
+    /// where each file starts, in ascending order. Each file ends where the next one starts.
+    /// Lines and columns in the result are counted from the beginning of each file.
+    /// If the range of a file is out of `batch`, reversed or not on `char` boundaries,
+    /// the file is reported as `ErrorCode::Internal` and the others are parsed as usual.
+    pub fn parse_batch(&self, batch: &str, files: &[(&str, usize)]) -> Project {
+        let mut project = Project {
+            files: Vec::with_capacity(files.len()),
+            diagnostics: Vec::new(),
+        };
+        let mut buffers = Buffers::default();
+        for (i, (path, start)) in files.iter().enumerate() {
+            let end = files.get(i + 1).map_or(batch.len(), |(_, next)| *next);
+            let document = match batch.get(*start..end) {
+                Some(code) => {
+                    document::parse_document_with_buffers(code, self.options.clone(), &mut buffers)
+                }
+                None => Err(BQ2CSTError::new(
+                    1,
+                    1,
+                    ErrorCode::Internal,
+                    format!("The range {}..{} is not valid in the batch.", start, end),
+                )),
+            };
+            project.push_file(path, document);
+        }
+        project
+    }
+}
+
+impl Project {
+    fn push_file(&mut self, path: &str, document: BQ2CSTResult<Document>) {
+        let diagnostics = match &document {
+            Ok(d) => {
+                let mut diagnostics = d.diagnostics.clone();
+                diagnostics.extend(d.errors.iter().map(Diagnostic::from_error));
+                diagnostics.sort_by_key(|d| (d.span.start_line, d.span.start_column));
+                diagnostics
+            }
+            Err(e) => Vec::from([Diagnostic::from_error(e)]),
+        };
+        self.diagnostics
+            .extend(diagnostics.into_iter().map(|diagnostic| ProjectDiagnostic {
+                path: path.to_string(),
+                diagnostic,
+            }));
+        self.files.push(ProjectFile {
+            path: path.to_string(),
+            document,
+        });
+    }
+}
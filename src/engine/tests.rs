@@ -0,0 +1,144 @@
+use super::*;
+use crate::cst::NodeType;
+use crate::diagnostic::Severity;
+
+#[test]
+fn test_parser_engine() {
+    let engine = ParserEngine::new(ParseOptions {
+        jinja_templates: true,
+        ..Default::default()
+    });
+    let codes: Vec<String> = (0..16)
+        .map(|i| format!("SELECT {} FROM {{{{ ref('t') }}}};", i))
+        .collect();
+    let results: Vec<_> = std::thread::scope(|s| {
+        let handles: Vec<_> = codes
+            .iter()
+            .map(|code| s.spawn(|| engine.parse(code)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    for (i, res) in results.into_iter().enumerate() {
+        let stmts = res.unwrap();
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(stmts[0].node_type, NodeType::SelectStatement);
+        let expected = engine.parse(&codes[i]).unwrap();
+        assert_eq!(stmts, expected);
+    }
+
+    assert!(engine.parse("SELECT (").is_err());
+    assert!(engine.options().jinja_templates);
+    let document = engine.parse_document("SELECT 1").unwrap();
+    assert_eq!(document.stmts.len(), 2);
+}
+
+#[test]
+fn test_parse_project() {
+    let engine = ParserEngine::default();
+    let project = engine.parse_project(&[
+        ("a.sql", "SELECT 1 <> 2 FROM t WHERE x != y;"),
+        ("b.sql", "SELECT (;"),
+        ("c.sql", "SELECT 1;"),
+    ]);
+    assert_eq!(
+        project
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.document.is_ok()))
+            .collect::<Vec<_>>(),
+        vec![("a.sql", true), ("b.sql", false), ("c.sql", true)]
+    );
+    assert_eq!(
+        project
+            .diagnostics
+            .iter()
+            .map(|d| (
+                d.path.as_str(),
+                d.diagnostic.severity,
+                d.diagnostic.code.as_str()
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            ("a.sql", Severity::Warning, "W002"),
+            ("b.sql", Severity::Error, "P001"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_project_buffers() {
+    // the buffers of the previous (longer) file do not leak into the next one
+    let engine = ParserEngine::default();
+    let files = [
+        (
+            "a.sql",
+            "-- comment\nSELECT a, b, c FROM t WHERE x = 1;\nSELECT 2;",
+        ),
+        ("b.sql", "\u{feff}SELECT 1;"),
+        ("c.sql", "SELECT 'a"),
+        ("d.sql", "SELECT (;"),
+        ("e.sql", "SELECT 1"),
+    ];
+    let project = engine.parse_project(&files);
+    for ((path, code), file) in files.iter().zip(project.files) {
+        assert_eq!(file.document, engine.parse_document(code), "{}", path);
+    }
+}
+
+#[test]
+fn test_parse_batch() {
+    let engine = ParserEngine::default();
+    let files = ["SELECT 1;\n", "\nSELEC 1;", "SELECT é <> 1,\n  x != y;"];
+    let batch = files.concat();
+    let project = engine.parse_batch(
+        &batch,
+        &[
+            ("a.sql", 0),
+            ("b.sql", files[0].len()),
+            ("c.sql", files[0].len() + files[1].len()),
+        ],
+    );
+    let expected = engine.parse_project(&[
+        ("a.sql", files[0]),
+        ("b.sql", files[1]),
+        ("c.sql", files[2]),
+    ]);
+    assert_eq!(project, expected);
+    // positions are re-based to each file
+    let d = &project.diagnostics;
+    assert_eq!(d.len(), 2);
+    assert_eq!(d[0].path, "b.sql");
+    assert_eq!(d[0].diagnostic.span.start_line, 2);
+    assert_eq!(d[1].path, "c.sql");
+    assert_eq!(
+        (
+            d[1].diagnostic.span.start_line,
+            d[1].diagnostic.span.start_column
+        ),
+        (2, 5)
+    );
+    assert_eq!(engine.parse_batch("", &[]).files.len(), 0);
+
+    // invalid offsets do not stop the other files
+    let project = engine.parse_batch(
+        &batch,
+        &[
+            ("a.sql", 0),
+            ("b.sql", files[0].len()),
+            ("c.sql", batch.len() + 1),
+        ],
+    );
+    let codes: Vec<_> = project
+        .files
+        .iter()
+        .map(|f| f.document.as_ref().map(|_| ()).map_err(|e| e.code))
+        .collect();
+    assert_eq!(
+        codes,
+        vec![Ok(()), Err(ErrorCode::Internal), Err(ErrorCode::Internal)]
+    );
+    assert_eq!(project.diagnostics.len(), 2);
+    // not on a `char` boundary
+    let project = engine.parse_batch(&batch, &[("c.sql", batch.find('é').unwrap() + 1)]);
+    assert!(project.files[0].document.is_err());
+}
@@ -1,28 +1,157 @@
+#[cfg(test)]
+mod tests;
+
+use crate::diagnostic::Span;
 use crate::token::Token;
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Stable identifiers of errors. They are serialized as e.g. `"L001"`.
+/// Codes starting with `L` are raised by the lexer and `P` by the parser.
+/// `R` means that one of the limits in `ParseOptions` is exceeded (by either of them).
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ErrorCode {
+    /// unterminated string literal, quoted identifier, comment and so on
+    #[serde(rename = "L001")]
+    UnexpectedEofInLexer,
+    #[serde(rename = "L002")]
+    InvalidCharacter,
+    #[serde(rename = "L003")]
+    InvalidEscapeSequence,
+    /// e.g. `1.2.3`, `1e`
+    #[serde(rename = "L004")]
+    InvalidNumericLiteral,
+    #[serde(rename = "P001")]
+    UnexpectedEof,
+    #[serde(rename = "P002")]
+    UnexpectedToken,
+    #[serde(rename = "P003")]
+    UnknownStatement,
+    #[serde(rename = "P004")]
+    InvalidLabel,
+    /// syntax disabled by `ParseOptions.disabled_features`
+    #[serde(rename = "P005")]
+    DisabledFeature,
+    /// aborted by the callback given to `Parser::with_cancellation`
+    #[serde(rename = "P006")]
+    Cancelled,
+    /// `SELECT ... INTO x` of T-SQL
+    #[serde(rename = "P007")]
+    SelectInto,
+    /// e.g. `[project:dataset.table]`, `TABLE_DATE_RANGE()`
+    #[serde(rename = "P008")]
+    LegacySql,
+    /// `DECLARE` after other statements or outside of `BEGIN ... END`
+    #[serde(rename = "P009")]
+    MisplacedDeclare,
+    /// `max_input_bytes`, `max_tokens`, `max_nodes` or `max_depth` of `ParseOptions` is exceeded
+    #[serde(rename = "R001")]
+    ResourceLimitExceeded,
+    /// a bug of the parser or an invalid argument (e.g. an offset of `ParserEngine::parse_batch`)
+    #[serde(rename = "P999")]
+    Internal,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ErrorCategory {
+    Lexer,
+    Parser,
+    Resource,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::UnexpectedEofInLexer => "L001",
+            ErrorCode::InvalidCharacter => "L002",
+            ErrorCode::InvalidEscapeSequence => "L003",
+            ErrorCode::InvalidNumericLiteral => "L004",
+            ErrorCode::UnexpectedEof => "P001",
+            ErrorCode::UnexpectedToken => "P002",
+            ErrorCode::UnknownStatement => "P003",
+            ErrorCode::InvalidLabel => "P004",
+            ErrorCode::DisabledFeature => "P005",
+            ErrorCode::Cancelled => "P006",
+            ErrorCode::SelectInto => "P007",
+            ErrorCode::LegacySql => "P008",
+            ErrorCode::MisplacedDeclare => "P009",
+            ErrorCode::ResourceLimitExceeded => "R001",
+            ErrorCode::Internal => "P999",
+        }
+    }
+    pub fn category(&self) -> ErrorCategory {
+        match self.as_str().chars().next() {
+            Some('L') => ErrorCategory::Lexer,
+            Some('R') => ErrorCategory::Resource,
+            _ => ErrorCategory::Parser,
+        }
+    }
+}
+
+/// A possible fix of a misspelled keyword.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub line: usize,
+    pub column: usize,
+    pub original: String,
+    pub replacement: String,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct BQ2CSTError {
     pub line: usize,
     pub column: usize,
+    pub code: ErrorCode,
+    pub category: ErrorCategory,
     message: String,
+    /// Tokens which would have been accepted instead of the unexpected one.
+    pub expected: Vec<String>,
+    pub suggestion: Option<Box<Suggestion>>,
+    /// The whole range of the problem if it is longer than a token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
 }
 
 impl BQ2CSTError {
-    pub fn new(line: usize, column: usize, message: String) -> Self {
+    pub fn new(line: usize, column: usize, code: ErrorCode, message: String) -> Self {
         Self {
             line,
             column,
+            code,
+            category: code.category(),
             message,
+            expected: Vec::new(),
+            suggestion: None,
+            span: None,
         }
     }
-    pub fn from_token(token: &Token, message: String) -> Self {
+    pub fn from_token(token: &Token, code: ErrorCode, message: String) -> Self {
         Self {
             line: token.line,
             column: token.column,
+            code,
+            category: code.category(),
             message,
+            expected: Vec::new(),
+            suggestion: None,
+            span: None,
         }
     }
+    pub fn with_expected(mut self, expected: &[&str]) -> Self {
+        self.expected = expected.iter().map(|s| s.to_string()).collect();
+        self
+    }
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 pub type BQ2CSTResult<T> = Result<T, BQ2CSTError>;
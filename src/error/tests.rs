@@ -0,0 +1,14 @@
+use super::*;
+
+#[test]
+fn test_error_code() {
+    let error = BQ2CSTError::new(1, 2, ErrorCode::UnexpectedEofInLexer, "".to_string());
+    assert_eq!(error.category, ErrorCategory::Lexer);
+    let error = BQ2CSTError::from_token(
+        &Token::from_str(1, 2, "SELECT"),
+        ErrorCode::UnexpectedToken,
+        "".to_string(),
+    );
+    assert_eq!(error.category, ErrorCategory::Parser);
+    assert_eq!(error.code.as_str(), "P002");
+}
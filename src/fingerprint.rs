@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests;
+
+use crate::analysis::child_nodes;
+use crate::constants;
+use crate::cst::{Node, NodeType};
+use crate::token::Token;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Returns the canonical form of the code for deduplication and caching.
+/// Comments and whitespace are removed, keywords are uppercased and
+/// string and numeric literals are replaced with `?`.
+pub fn normalize_tokens(tokens: &[Token]) -> String {
+    let mut res: Vec<String> = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_comment() || token.literal.is_empty() {
+            continue;
+        }
+        if is_string_prefix(token, tokens.get(i + 1)) {
+            continue;
+        }
+        let literal = if token.is_string() || token.is_numeric() {
+            "?".to_string()
+        } else if token.literal == "<>" {
+            "!=".to_string()
+        } else if token.is_reserved_keyword()
+            || constants::STATEMENT_KEYWORDS.contains(&token.literal.to_uppercase().as_str())
+        {
+            token.literal.to_uppercase()
+        } else {
+            token.literal.clone()
+        };
+        res.push(literal);
+    }
+    res.join(" ")
+}
+
+// r'...', b'...', rb'...' or br'...'
+fn is_string_prefix(token: &Token, next: Option<&Token>) -> bool {
+    let next = match next {
+        Some(t) => t,
+        None => return false,
+    };
+    token.in_(&vec!["r", "b", "rb", "br"])
+        && next.is_string()
+        && next.line == token.line
+        && next.column == token.column + token.literal.len()
+}
+
+/// Returns a hash of the structure of `node`.
+/// Literals, comments, whitespace and the trailing `;` are ignored and keywords are case-insensitive,
+/// so structurally identical queries have the same fingerprint.
+/// The value is stable across platforms and releases (FNV-1a is used).
+pub fn fingerprint(node: &Node) -> u64 {
+    let mut hasher = Fnv1a::new();
+    hash_node(node, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_node(node: &Node, hasher: &mut Fnv1a) {
+    hasher.write(format!("{:?}", node.node_type).as_bytes());
+    if let Some(token) = &node.token {
+        match node.node_type {
+            NodeType::StringLiteral | NodeType::NumericLiteral => hasher.write(b"?"),
+            _ => {
+                if token.is_identifier() {
+                    hasher.write(token.literal.as_bytes())
+                } else {
+                    hasher.write(token.literal.to_uppercase().as_bytes())
+                }
+            }
+        }
+    }
+    hasher.write(b"(");
+    for (key, child) in child_nodes(node) {
+        if ["leading_comments", "trailing_comments", "semicolon"].contains(&key.as_str()) {
+            continue;
+        }
+        hasher.write(key.as_bytes());
+        hasher.write(b":");
+        hash_node(child, hasher);
+    }
+    hasher.write(b")");
+}
+
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Fnv1a {
+        Fnv1a(0xcbf29ce484222325)
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= *b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+        // separator
+        self.0 ^= 0xff;
+        self.0 = self.0.wrapping_mul(0x100000001b3);
+    }
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
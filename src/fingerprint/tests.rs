@@ -0,0 +1,71 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::utils::parse;
+
+fn tokenize(code: &str) -> Vec<Token> {
+    Lexer::new(code.to_string())
+        .tokenize_code()
+        .expect("Failed to tokenize code.")
+}
+
+#[test]
+fn test_normalize_tokens() {
+    let test_cases = vec![
+        (
+            "select a from t where b = 'x' -- comment",
+            "SELECT a FROM t WHERE b = ?",
+        ),
+        (
+            "SELECT\n  a,\n  1.5e3\nFROM   `p.d.t`",
+            "SELECT a , ? FROM `p.d.t`",
+        ),
+        ("select r'\\d', b\"x\", '''y'''", "SELECT ? , ? , ?"),
+        (
+            "select * from t where a <> @param",
+            "SELECT * FROM t WHERE a != @param",
+        ),
+        ("declare x int64 default 1;", "DECLARE x int64 DEFAULT ? ;"),
+    ];
+    for (code, expected) in test_cases {
+        assert_eq!(expected, normalize_tokens(&tokenize(code)), "{}", code);
+    }
+    assert_eq!(
+        normalize_tokens(&tokenize("SELECT 1 FROM t")),
+        normalize_tokens(&tokenize("select 2\nfrom t /* x */")),
+    );
+}
+
+#[test]
+fn test_fingerprint() {
+    let same = vec![
+        (
+            "SELECT a FROM t WHERE b = 1",
+            "select a\nfrom t -- comment\nwhere b = 100",
+        ),
+        ("SELECT 'x' AS c", "SELECT \"yyy\" as c;"),
+    ];
+    for (left, right) in same {
+        assert_eq!(
+            fingerprint(&parse(left)[0]),
+            fingerprint(&parse(right)[0]),
+            "{} / {}",
+            left,
+            right
+        );
+    }
+    let different = vec![
+        ("SELECT a FROM t", "SELECT b FROM t"),
+        ("SELECT a FROM t WHERE b = 1", "SELECT a FROM t WHERE b > 1"),
+        ("SELECT 1 + 2 * 3", "SELECT (1 + 2) * 3"),
+        ("SELECT a, b FROM t", "SELECT a FROM t, b"),
+    ];
+    for (left, right) in different {
+        assert_ne!(
+            fingerprint(&parse(left)[0]),
+            fingerprint(&parse(right)[0]),
+            "{} / {}",
+            left,
+            right
+        );
+    }
+}
@@ -0,0 +1,349 @@
+#[cfg(test)]
+mod tests;
+
+/// The number of arguments a builtin function takes.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct FunctionSignature {
+    /// e.g. `SUBSTR`, `NET.HOST` (without `SAFE.`)
+    pub name: &'static str,
+    pub min_args: usize,
+    /// `None` if the function is variadic (e.g. `CONCAT`).
+    pub max_args: Option<usize>,
+}
+
+impl FunctionSignature {
+    pub fn accepts(&self, args: usize) -> bool {
+        self.min_args <= args && self.max_args.is_none_or(|max| args <= max)
+    }
+}
+
+/// Namespaces of builtin functions (e.g. `NET.HOST()`).
+/// Functions in other namespaces are regarded as user-defined functions.
+pub const NAMESPACES: [&str; 4] = ["AEAD", "HLL_COUNT", "KEYS", "NET"];
+
+/// Builtin functions sorted by name, which are used by `crate::analysis::check_function_calls`.
+/// Table functions and functions whose arguments are not expressions
+/// (e.g. `ML.PREDICT()`) are not included.
+const FUNCTIONS: &[(&str, usize, Option<usize>)] = &[
+    ("ABS", 1, Some(1)),
+    ("ACOS", 1, Some(1)),
+    ("ACOSH", 1, Some(1)),
+    ("AEAD.DECRYPT_BYTES", 3, Some(3)),
+    ("AEAD.DECRYPT_STRING", 3, Some(3)),
+    ("AEAD.ENCRYPT", 3, Some(3)),
+    ("ANY_VALUE", 1, Some(1)),
+    ("APPROX_COUNT_DISTINCT", 1, Some(1)),
+    ("APPROX_QUANTILES", 2, Some(2)),
+    ("APPROX_TOP_COUNT", 2, Some(2)),
+    ("APPROX_TOP_SUM", 3, Some(3)),
+    ("ARRAY", 1, Some(1)),
+    ("ARRAY_AGG", 1, Some(1)),
+    ("ARRAY_CONCAT", 1, None),
+    ("ARRAY_CONCAT_AGG", 1, Some(1)),
+    ("ARRAY_FIRST", 1, Some(1)),
+    ("ARRAY_LAST", 1, Some(1)),
+    ("ARRAY_LENGTH", 1, Some(1)),
+    ("ARRAY_REVERSE", 1, Some(1)),
+    ("ARRAY_SLICE", 3, Some(3)),
+    ("ARRAY_TO_STRING", 2, Some(3)),
+    ("ASCII", 1, Some(1)),
+    ("ASIN", 1, Some(1)),
+    ("ASINH", 1, Some(1)),
+    ("ATAN", 1, Some(1)),
+    ("ATAN2", 2, Some(2)),
+    ("ATANH", 1, Some(1)),
+    ("AVG", 1, Some(1)),
+    ("BIT_AND", 1, Some(1)),
+    ("BIT_COUNT", 1, Some(1)),
+    ("BIT_OR", 1, Some(1)),
+    ("BIT_XOR", 1, Some(1)),
+    ("BOOL", 1, Some(1)),
+    ("BYTE_LENGTH", 1, Some(1)),
+    ("CAST", 1, Some(1)),
+    ("CBRT", 1, Some(1)),
+    ("CEIL", 1, Some(1)),
+    ("CEILING", 1, Some(1)),
+    ("CHARACTER_LENGTH", 1, Some(1)),
+    ("CHAR_LENGTH", 1, Some(1)),
+    ("CHR", 1, Some(1)),
+    ("COALESCE", 1, None),
+    ("CODE_POINTS_TO_BYTES", 1, Some(1)),
+    ("CODE_POINTS_TO_STRING", 1, Some(1)),
+    ("COLLATE", 2, Some(2)),
+    ("CONCAT", 1, None),
+    ("CONTAINS_SUBSTR", 2, Some(3)),
+    ("CORR", 2, Some(2)),
+    ("COS", 1, Some(1)),
+    ("COSH", 1, Some(1)),
+    ("COSINE_DISTANCE", 2, Some(2)),
+    ("COT", 1, Some(1)),
+    ("COTH", 1, Some(1)),
+    ("COUNT", 1, Some(1)),
+    ("COUNTIF", 1, Some(1)),
+    ("COVAR_POP", 2, Some(2)),
+    ("COVAR_SAMP", 2, Some(2)),
+    ("CSC", 1, Some(1)),
+    ("CSCH", 1, Some(1)),
+    ("CUME_DIST", 0, Some(0)),
+    ("CURRENT_DATE", 0, Some(1)),
+    ("CURRENT_DATETIME", 0, Some(1)),
+    ("CURRENT_TIME", 0, Some(1)),
+    ("CURRENT_TIMESTAMP", 0, Some(0)),
+    ("DATE", 1, Some(3)),
+    ("DATETIME", 1, Some(6)),
+    ("DATETIME_ADD", 2, Some(2)),
+    ("DATETIME_BUCKET", 2, Some(3)),
+    ("DATETIME_DIFF", 3, Some(3)),
+    ("DATETIME_SUB", 2, Some(2)),
+    ("DATETIME_TRUNC", 2, Some(2)),
+    ("DATE_ADD", 2, Some(2)),
+    ("DATE_BUCKET", 2, Some(3)),
+    ("DATE_DIFF", 3, Some(3)),
+    ("DATE_FROM_UNIX_DATE", 1, Some(1)),
+    ("DATE_SUB", 2, Some(2)),
+    ("DATE_TRUNC", 2, Some(3)),
+    ("DENSE_RANK", 0, Some(0)),
+    ("DETERMINISTIC_DECRYPT_BYTES", 3, Some(3)),
+    ("DETERMINISTIC_DECRYPT_STRING", 3, Some(3)),
+    ("DETERMINISTIC_ENCRYPT", 3, Some(3)),
+    ("DIV", 2, Some(2)),
+    ("EDIT_DISTANCE", 2, Some(3)),
+    ("ENDS_WITH", 2, Some(2)),
+    ("ERROR", 1, Some(1)),
+    ("EUCLIDEAN_DISTANCE", 2, Some(2)),
+    ("EXP", 1, Some(1)),
+    ("EXTRACT", 1, Some(1)),
+    ("FARM_FINGERPRINT", 1, Some(1)),
+    ("FIRST_VALUE", 1, Some(1)),
+    ("FLOAT64", 1, Some(2)),
+    ("FLOOR", 1, Some(1)),
+    ("FORMAT", 1, None),
+    ("FORMAT_DATE", 2, Some(2)),
+    ("FORMAT_DATETIME", 2, Some(2)),
+    ("FORMAT_TIME", 2, Some(2)),
+    ("FORMAT_TIMESTAMP", 2, Some(3)),
+    ("FROM_BASE32", 1, Some(1)),
+    ("FROM_BASE64", 1, Some(1)),
+    ("FROM_HEX", 1, Some(1)),
+    ("GENERATE_ARRAY", 2, Some(3)),
+    ("GENERATE_DATE_ARRAY", 2, Some(3)),
+    ("GENERATE_RANGE_ARRAY", 2, Some(3)),
+    ("GENERATE_TIMESTAMP_ARRAY", 3, Some(3)),
+    ("GENERATE_UUID", 0, Some(0)),
+    ("GREATEST", 1, None),
+    ("GROUPING", 1, Some(1)),
+    ("HLL_COUNT.EXTRACT", 1, Some(1)),
+    ("HLL_COUNT.INIT", 1, Some(2)),
+    ("HLL_COUNT.MERGE", 1, Some(1)),
+    ("HLL_COUNT.MERGE_PARTIAL", 1, Some(1)),
+    ("IEEE_DIVIDE", 2, Some(2)),
+    ("IF", 3, Some(3)),
+    ("IFNULL", 2, Some(2)),
+    ("INITCAP", 1, Some(2)),
+    ("INSTR", 2, Some(4)),
+    ("INT64", 1, Some(1)),
+    ("IS_INF", 1, Some(1)),
+    ("IS_NAN", 1, Some(1)),
+    ("JSON_ARRAY", 0, None),
+    ("JSON_ARRAY_APPEND", 3, None),
+    ("JSON_ARRAY_INSERT", 3, None),
+    ("JSON_CONTAINS", 2, Some(2)),
+    ("JSON_EXTRACT", 2, Some(2)),
+    ("JSON_EXTRACT_ARRAY", 1, Some(2)),
+    ("JSON_EXTRACT_SCALAR", 1, Some(2)),
+    ("JSON_EXTRACT_STRING_ARRAY", 1, Some(2)),
+    ("JSON_KEYS", 1, Some(3)),
+    ("JSON_OBJECT", 0, None),
+    ("JSON_QUERY", 2, Some(2)),
+    ("JSON_QUERY_ARRAY", 1, Some(2)),
+    ("JSON_REMOVE", 2, None),
+    ("JSON_SET", 3, None),
+    ("JSON_STRIP_NULLS", 1, Some(3)),
+    ("JSON_TYPE", 1, Some(1)),
+    ("JSON_VALUE", 1, Some(2)),
+    ("JSON_VALUE_ARRAY", 1, Some(2)),
+    ("JUSTIFY_DAYS", 1, Some(1)),
+    ("JUSTIFY_HOURS", 1, Some(1)),
+    ("JUSTIFY_INTERVAL", 1, Some(1)),
+    ("KEYS.ADD_KEY_FROM_RAW_BYTES", 3, Some(3)),
+    ("KEYS.KEYSET_CHAIN", 2, Some(3)),
+    ("KEYS.KEYSET_FROM_JSON", 1, Some(1)),
+    ("KEYS.KEYSET_LENGTH", 1, Some(1)),
+    ("KEYS.KEYSET_TO_JSON", 1, Some(1)),
+    ("KEYS.NEW_KEYSET", 1, Some(1)),
+    ("KEYS.ROTATE_KEYSET", 2, Some(2)),
+    ("LAG", 1, Some(3)),
+    ("LAST_DAY", 1, Some(2)),
+    ("LAST_VALUE", 1, Some(1)),
+    ("LAX_BOOL", 1, Some(1)),
+    ("LAX_FLOAT64", 1, Some(1)),
+    ("LAX_INT64", 1, Some(1)),
+    ("LAX_STRING", 1, Some(1)),
+    ("LEAD", 1, Some(3)),
+    ("LEAST", 1, None),
+    ("LEFT", 2, Some(2)),
+    ("LENGTH", 1, Some(1)),
+    ("LN", 1, Some(1)),
+    ("LOG", 1, Some(2)),
+    ("LOG10", 1, Some(1)),
+    ("LOGICAL_AND", 1, Some(1)),
+    ("LOGICAL_OR", 1, Some(1)),
+    ("LOWER", 1, Some(1)),
+    ("LPAD", 2, Some(3)),
+    ("LTRIM", 1, Some(2)),
+    ("MAKE_INTERVAL", 0, Some(6)),
+    ("MAX", 1, Some(1)),
+    ("MAX_BY", 2, Some(2)),
+    ("MD5", 1, Some(1)),
+    ("MIN", 1, Some(1)),
+    ("MIN_BY", 2, Some(2)),
+    ("MOD", 2, Some(2)),
+    ("NET.HOST", 1, Some(1)),
+    ("NET.IPV4_FROM_INT64", 1, Some(1)),
+    ("NET.IPV4_TO_INT64", 1, Some(1)),
+    ("NET.IP_FROM_STRING", 1, Some(1)),
+    ("NET.IP_NET_MASK", 2, Some(2)),
+    ("NET.IP_TO_STRING", 1, Some(1)),
+    ("NET.IP_TRUNC", 2, Some(2)),
+    ("NET.PUBLIC_SUFFIX", 1, Some(1)),
+    ("NET.REG_DOMAIN", 1, Some(1)),
+    ("NET.SAFE_IP_FROM_STRING", 1, Some(1)),
+    ("NORMALIZE", 1, Some(2)),
+    ("NORMALIZE_AND_CASEFOLD", 1, Some(2)),
+    ("NTH_VALUE", 2, Some(2)),
+    ("NTILE", 1, Some(1)),
+    ("NULLIF", 2, Some(2)),
+    ("NULLIFZERO", 1, Some(1)),
+    ("OCTET_LENGTH", 1, Some(1)),
+    ("PARSE_BIGNUMERIC", 1, Some(1)),
+    ("PARSE_DATE", 2, Some(2)),
+    ("PARSE_DATETIME", 2, Some(2)),
+    ("PARSE_JSON", 1, Some(2)),
+    ("PARSE_NUMERIC", 1, Some(1)),
+    ("PARSE_TIME", 2, Some(2)),
+    ("PARSE_TIMESTAMP", 2, Some(3)),
+    ("PERCENTILE_CONT", 2, Some(2)),
+    ("PERCENTILE_DISC", 2, Some(2)),
+    ("PERCENT_RANK", 0, Some(0)),
+    ("POW", 2, Some(2)),
+    ("POWER", 2, Some(2)),
+    ("RAND", 0, Some(0)),
+    ("RANGE", 2, Some(2)),
+    ("RANGE_BUCKET", 2, Some(2)),
+    ("RANGE_CONTAINS", 2, Some(2)),
+    ("RANGE_END", 1, Some(1)),
+    ("RANGE_INTERSECT", 2, Some(2)),
+    ("RANGE_OVERLAPS", 2, Some(2)),
+    ("RANGE_START", 1, Some(1)),
+    ("RANK", 0, Some(0)),
+    ("REGEXP_CONTAINS", 2, Some(2)),
+    ("REGEXP_EXTRACT", 2, Some(4)),
+    ("REGEXP_EXTRACT_ALL", 2, Some(2)),
+    ("REGEXP_INSTR", 2, Some(5)),
+    ("REGEXP_REPLACE", 3, Some(3)),
+    ("REGEXP_SUBSTR", 2, Some(4)),
+    ("REPEAT", 2, Some(2)),
+    ("REPLACE", 3, Some(3)),
+    ("REVERSE", 1, Some(1)),
+    ("RIGHT", 2, Some(2)),
+    ("ROUND", 1, Some(3)),
+    ("ROW_NUMBER", 0, Some(0)),
+    ("RPAD", 2, Some(3)),
+    ("RTRIM", 1, Some(2)),
+    ("SAFE_ADD", 2, Some(2)),
+    ("SAFE_CAST", 1, Some(1)),
+    ("SAFE_CONVERT_BYTES_TO_STRING", 1, Some(1)),
+    ("SAFE_DIVIDE", 2, Some(2)),
+    ("SAFE_MULTIPLY", 2, Some(2)),
+    ("SAFE_NEGATE", 1, Some(1)),
+    ("SAFE_SUBTRACT", 2, Some(2)),
+    ("SEARCH", 2, None),
+    ("SEC", 1, Some(1)),
+    ("SECH", 1, Some(1)),
+    ("SESSION_USER", 0, Some(0)),
+    ("SHA1", 1, Some(1)),
+    ("SHA256", 1, Some(1)),
+    ("SHA512", 1, Some(1)),
+    ("SIGN", 1, Some(1)),
+    ("SIN", 1, Some(1)),
+    ("SINH", 1, Some(1)),
+    ("SOUNDEX", 1, Some(1)),
+    ("SPLIT", 1, Some(2)),
+    ("SQRT", 1, Some(1)),
+    ("STARTS_WITH", 2, Some(2)),
+    ("STDDEV", 1, Some(1)),
+    ("STDDEV_POP", 1, Some(1)),
+    ("STDDEV_SAMP", 1, Some(1)),
+    ("STRING", 1, Some(2)),
+    ("STRING_AGG", 1, Some(2)),
+    ("STRPOS", 2, Some(2)),
+    ("ST_AREA", 1, Some(2)),
+    ("ST_ASGEOJSON", 1, Some(1)),
+    ("ST_ASTEXT", 1, Some(1)),
+    ("ST_BUFFER", 2, Some(6)),
+    ("ST_CENTROID", 1, Some(1)),
+    ("ST_CONTAINS", 2, Some(2)),
+    ("ST_DISTANCE", 2, Some(3)),
+    ("ST_DWITHIN", 3, Some(4)),
+    ("ST_GEOGFROMGEOJSON", 1, Some(2)),
+    ("ST_GEOGFROMTEXT", 1, Some(3)),
+    ("ST_GEOGPOINT", 2, Some(2)),
+    ("ST_INTERSECTS", 2, Some(2)),
+    ("ST_LENGTH", 1, Some(2)),
+    ("ST_MAKELINE", 1, Some(2)),
+    ("ST_UNION", 1, None),
+    ("ST_X", 1, Some(1)),
+    ("ST_Y", 1, Some(1)),
+    ("SUBSTR", 2, Some(3)),
+    ("SUBSTRING", 2, Some(3)),
+    ("SUM", 1, Some(1)),
+    ("TAN", 1, Some(1)),
+    ("TANH", 1, Some(1)),
+    ("TIME", 1, Some(3)),
+    ("TIMESTAMP", 1, Some(2)),
+    ("TIMESTAMP_ADD", 2, Some(2)),
+    ("TIMESTAMP_BUCKET", 2, Some(3)),
+    ("TIMESTAMP_DIFF", 3, Some(3)),
+    ("TIMESTAMP_MICROS", 1, Some(1)),
+    ("TIMESTAMP_MILLIS", 1, Some(1)),
+    ("TIMESTAMP_SECONDS", 1, Some(1)),
+    ("TIMESTAMP_SUB", 2, Some(2)),
+    ("TIMESTAMP_TRUNC", 2, Some(3)),
+    ("TIME_ADD", 2, Some(2)),
+    ("TIME_DIFF", 3, Some(3)),
+    ("TIME_SUB", 2, Some(2)),
+    ("TIME_TRUNC", 2, Some(2)),
+    ("TO_BASE32", 1, Some(1)),
+    ("TO_BASE64", 1, Some(1)),
+    ("TO_CODE_POINTS", 1, Some(1)),
+    ("TO_HEX", 1, Some(1)),
+    ("TO_JSON", 1, Some(2)),
+    ("TO_JSON_STRING", 1, Some(2)),
+    ("TRANSLATE", 3, Some(3)),
+    ("TRIM", 1, Some(2)),
+    ("TRUNC", 1, Some(2)),
+    ("UNICODE", 1, Some(1)),
+    ("UNIX_DATE", 1, Some(1)),
+    ("UNIX_MICROS", 1, Some(1)),
+    ("UNIX_MILLIS", 1, Some(1)),
+    ("UNIX_SECONDS", 1, Some(1)),
+    ("UPPER", 1, Some(1)),
+    ("VARIANCE", 1, Some(1)),
+    ("VAR_POP", 1, Some(1)),
+    ("VAR_SAMP", 1, Some(1)),
+    ("ZEROIFNULL", 1, Some(1)),
+];
+
+/// `name` is case-insensitive.
+pub fn lookup(name: &str) -> Option<FunctionSignature> {
+    let name = name.to_ascii_uppercase();
+    let idx = FUNCTIONS
+        .binary_search_by(|(n, _, _)| n.cmp(&name.as_str()))
+        .ok()?;
+    let (name, min_args, max_args) = FUNCTIONS[idx];
+    Some(FunctionSignature {
+        name,
+        min_args,
+        max_args,
+    })
+}
@@ -0,0 +1,19 @@
+use super::*;
+
+#[test]
+fn test_functions_are_sorted() {
+    for pair in FUNCTIONS.windows(2) {
+        assert!(pair[0].0 < pair[1].0, "{} {}", pair[0].0, pair[1].0);
+    }
+}
+
+#[test]
+fn test_lookup() {
+    let substr = lookup("substr").unwrap();
+    assert_eq!((substr.min_args, substr.max_args), (2, Some(3)));
+    assert!(!substr.accepts(1));
+    assert!(substr.accepts(3));
+    assert!(lookup("concat").unwrap().accepts(10));
+    assert!(lookup("Net.Host").is_some());
+    assert!(lookup("unknown_function").is_none());
+}
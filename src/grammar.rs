@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::error::BQ2CSTResult;
+use crate::lexer::Lexer;
+use crate::parser::{token_range, Parser};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+/// Statements which use as many clauses as possible.
+/// `grammar()` parses them, so the result always reflects what the parser accepts.
+/// Add an example here when a new statement or clause is implemented.
+const EXAMPLES: &[&str] = &[
+    "WITH a AS (SELECT 1) SELECT DISTINCT x, y FROM t AS t1 JOIN u USING (id) WHERE true GROUP BY x HAVING true QUALIFY true WINDOW w AS (PARTITION BY x) ORDER BY x LIMIT 1 OFFSET 1;",
+    "SELECT AS STRUCT 1 UNION ALL SELECT AS STRUCT 2 ORDER BY 1 LIMIT 1;",
+    "INSERT INTO t (a, b) VALUES (1, 2);",
+    "INSERT t SELECT 1;",
+    "DELETE FROM t AS t1 WHERE true;",
+    "TRUNCATE TABLE t;",
+    "UPDATE t AS t1 SET a = 1 FROM u WHERE true;",
+    "MERGE t AS t1 USING u ON t1.id = u.id WHEN MATCHED AND true THEN UPDATE SET a = 1 WHEN NOT MATCHED BY TARGET THEN INSERT ROW WHEN NOT MATCHED BY SOURCE THEN DELETE;",
+    "CREATE OR REPLACE TEMP TABLE IF NOT EXISTS t (a INT64 NOT NULL, PRIMARY KEY (a) NOT ENFORCED) DEFAULT COLLATE 'und:ci' PARTITION BY DATE(ts) CLUSTER BY a OPTIONS (description = 'x') AS SELECT 1;",
+    "CREATE TABLE t LIKE u;",
+    "CREATE TABLE t COPY u;",
+    "CREATE SNAPSHOT TABLE t CLONE u FOR SYSTEM_TIME AS OF CURRENT_TIMESTAMP();",
+    "CREATE EXTERNAL TABLE t WITH CONNECTION c WITH PARTITION COLUMNS (a INT64) OPTIONS (format = 'CSV');",
+    "CREATE OR REPLACE MATERIALIZED VIEW IF NOT EXISTS v PARTITION BY a CLUSTER BY a OPTIONS (a = 1) AS SELECT 1;",
+    "CREATE VIEW v (a OPTIONS (description = 'x')) AS SELECT 1;",
+    "CREATE SCHEMA IF NOT EXISTS s DEFAULT COLLATE 'und:ci' OPTIONS (a = 1);",
+    "CREATE OR REPLACE TEMP FUNCTION f(x INT64) RETURNS INT64 LANGUAGE js OPTIONS (a = 1) AS '''return x''';",
+    "CREATE TABLE FUNCTION f(x INT64) RETURNS TABLE<a INT64> AS (SELECT 1);",
+    "CREATE PROCEDURE p(IN x INT64) OPTIONS (a = 1) BEGIN SELECT 1; END;",
+    "CREATE ROW ACCESS POLICY IF NOT EXISTS p ON t GRANT TO ('user:a@example.com') FILTER USING (true);",
+    "CREATE SEARCH INDEX IF NOT EXISTS i ON t (ALL COLUMNS) OPTIONS (a = 1);",
+    "CREATE CAPACITY `project.region.commitment_id` OPTIONS (slot_count = 100);",
+    "CREATE MODEL m TRANSFORM (a) INPUT (a INT64) OUTPUT (b INT64) REMOTE WITH CONNECTION c OPTIONS (a = 1) AS SELECT 1;",
+    "ALTER SCHEMA IF EXISTS s SET DEFAULT COLLATE 'und:ci';",
+    "ALTER TABLE IF EXISTS t ADD COLUMN IF NOT EXISTS a INT64, ADD COLUMN b INT64;",
+    "ALTER TABLE t ADD PRIMARY KEY (a) NOT ENFORCED;",
+    "ALTER TABLE t DROP COLUMN IF EXISTS a, DROP PRIMARY KEY;",
+    "ALTER TABLE t RENAME COLUMN a TO b;",
+    "ALTER TABLE t RENAME TO u;",
+    "ALTER TABLE t SET OPTIONS (a = 1);",
+    "ALTER TABLE t SET DEFAULT COLLATE 'und:ci';",
+    "ALTER TABLE t ALTER COLUMN IF EXISTS a SET DATA TYPE NUMERIC;",
+    "ALTER VIEW IF EXISTS v SET OPTIONS (a = 1);",
+    "ALTER ORGANIZATION SET OPTIONS (a = 1);",
+    "ALTER PROJECT p SET OPTIONS (a = 1);",
+    "ALTER BI_CAPACITY `project.region.default` SET OPTIONS (a = 1);",
+    "ALTER CAPACITY `project.region.commitment_id` SET OPTIONS (a = 1);",
+    "ALTER MODEL IF EXISTS m SET OPTIONS (a = 1);",
+    "DROP TABLE IF EXISTS t;",
+    "DROP SCHEMA s CASCADE;",
+    "DROP ROW ACCESS POLICY IF EXISTS p ON t;",
+    "DROP ALL ROW ACCESS POLICIES ON t;",
+    "UNDROP SCHEMA IF NOT EXISTS s;",
+    "GRANT `roles/bigquery.dataViewer` ON TABLE t TO 'user:a@example.com';",
+    "REVOKE `roles/bigquery.dataViewer` ON TABLE t FROM 'user:a@example.com';",
+    "DECLARE x, y INT64 DEFAULT 1;",
+    "SET x = 1;",
+    "SET (x, y) = (1, 2);",
+    "SET @@query_label = 'x';",
+    "EXECUTE IMMEDIATE 'SELECT ?' INTO x USING 1;",
+    "IF true THEN SELECT 1; ELSEIF true THEN SELECT 2; ELSE SELECT 3; END IF;",
+    "BEGIN SELECT 1; EXCEPTION WHEN ERROR THEN SELECT 2; END;",
+    "BEGIN TRANSACTION;",
+    "COMMIT TRANSACTION;",
+    "ROLLBACK TRANSACTION;",
+    "CASE x WHEN 1 THEN SELECT 1; ELSE SELECT 2; END CASE;",
+    "LOAD DATA OVERWRITE t (a INT64) PARTITION BY a CLUSTER BY a OPTIONS (a = 1) FROM FILES (format = 'AVRO') WITH PARTITION COLUMNS WITH CONNECTION c;",
+    "label: LOOP BREAK label; END LOOP label;",
+    "REPEAT SET x = 1; LEAVE; UNTIL true END REPEAT;",
+    "WHILE true DO CONTINUE; END WHILE;",
+    "FOR x IN (SELECT 1) DO ITERATE; END FOR;",
+    "RAISE USING MESSAGE = 'x';",
+    "RETURN;",
+    "CALL p(1);",
+    "ASSERT true AS 'x';",
+    "EXPORT DATA WITH CONNECTION c OPTIONS (uri = 'gs://x/*') AS SELECT 1;",
+    "EXPORT MODEL m OPTIONS (uri = 'gs://x');",
+];
+
+/// The node types reported by `grammar()`.
+/// Add a node type here when a new statement is implemented.
+const STATEMENT_TYPES: &[NodeType] = &[
+    NodeType::AlterBICapacityStatement,
+    NodeType::AlterColumnStatement,
+    NodeType::AlterModelStatement,
+    NodeType::AlterOrganizationStatement,
+    NodeType::AlterProjectStatement,
+    NodeType::AlterReservationStatement,
+    NodeType::AlterSchemaStatement,
+    NodeType::AlterTableStatement,
+    NodeType::AlterViewStatement,
+    NodeType::AssertStatement,
+    NodeType::BeginStatement,
+    NodeType::BreakContinueStatement,
+    NodeType::CallStatement,
+    NodeType::CaseStatement,
+    NodeType::CreateFunctionStatement,
+    NodeType::CreateIndexStatement,
+    NodeType::CreateModelStatement,
+    NodeType::CreateProcedureStatement,
+    NodeType::CreateReservationStatement,
+    NodeType::CreateRowAccessPolicyStatement,
+    NodeType::CreateSchemaStatement,
+    NodeType::CreateTableStatement,
+    NodeType::CreateViewStatement,
+    NodeType::DeclareStatement,
+    NodeType::DeleteStatement,
+    NodeType::DropRowAccessPolicyStatement,
+    NodeType::DropStatement,
+    NodeType::ExecuteStatement,
+    NodeType::ExportDataStatement,
+    NodeType::ExportModelStatement,
+    NodeType::ForStatement,
+    NodeType::GrantStatement,
+    NodeType::IfStatement,
+    NodeType::InsertStatement,
+    NodeType::LoadStatement,
+    NodeType::LoopStatement,
+    NodeType::MergeStatement,
+    NodeType::RaiseStatement,
+    NodeType::RepeatStatement,
+    NodeType::RevokeStatement,
+    NodeType::SelectStatement,
+    NodeType::SetStatement,
+    NodeType::SetSystemVariableStatement,
+    NodeType::SingleTokenStatement,
+    NodeType::TransactionStatement,
+    NodeType::TruncateStatement,
+    NodeType::UndropStatement,
+    NodeType::UpdateStatement,
+    NodeType::WhileStatement,
+];
+
+/// A statement and the clauses which are implemented.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct StatementRule {
+    pub node_type: NodeType,
+    /// The literal of the statement node in upper case (e.g. `CREATE`).
+    pub keyword: String,
+    /// The keys of the children in order of appearance.
+    /// `self` is the position of `keyword` (e.g. `with` comes before `self` in SELECT statement).
+    pub clauses: Vec<String>,
+}
+
+/// Returns the statements (and their clauses) implemented by the parser.
+/// An error means that one of the examples is no longer accepted by the parser.
+pub fn grammar() -> BQ2CSTResult<Vec<StatementRule>> {
+    let mut rules: Vec<StatementRule> = Vec::new();
+    for code in EXAMPLES {
+        let tokens = Lexer::new(code.to_string()).tokenize_code()?;
+        let stmts = Parser::new(tokens).parse_code()?;
+        for stmt in &stmts {
+            collect_rules(stmt, &mut rules);
+        }
+    }
+    Ok(rules)
+}
+
+/// Writes `rules` in EBNF.
+/// Every clause is written as optional because the examples do not tell whether it is required.
+pub fn to_ebnf(rules: &[StatementRule]) -> String {
+    let mut res = String::new();
+    for rule in rules {
+        let items: Vec<String> = rule
+            .clauses
+            .iter()
+            .map(|c| match c.as_str() {
+                "self" => format!("\"{}\"", rule.keyword),
+                _ => format!("[ {} ]", c),
+            })
+            .collect();
+        res.push_str(&format!("{:?} ::= {} ;\n", rule.node_type, items.join(" ")));
+    }
+    res
+}
+
+fn collect_rules(node: &Node, rules: &mut Vec<StatementRule>) {
+    if STATEMENT_TYPES.contains(&node.node_type) {
+        let mut clauses: Vec<(_, String)> = node
+            .children
+            .iter()
+            .filter(|(k, _)| !k.ends_with("_comments"))
+            .filter_map(|(k, content)| {
+                let first = match content {
+                    ContentType::Node(n) => token_range(n).map(|(first, _)| first),
+                    ContentType::NodeVec(ns) => {
+                        ns.iter().find_map(token_range).map(|(first, _)| first)
+                    }
+                };
+                first.map(|first| (first, k.clone()))
+            })
+            .collect();
+        if let Some(t) = &node.token {
+            clauses.push(((t.line, t.column), "self".to_string()));
+        }
+        clauses.sort();
+        let clauses = clauses.into_iter().map(|(_, k)| k);
+        match rules.iter_mut().find(|r| r.node_type == node.node_type) {
+            Some(rule) => merge_clauses(&mut rule.clauses, clauses),
+            None => rules.push(StatementRule {
+                node_type: node.node_type.clone(),
+                keyword: node
+                    .token
+                    .as_ref()
+                    .map_or(String::new(), |t| t.literal.to_uppercase()),
+                clauses: clauses.collect(),
+            }),
+        }
+    }
+    for content in node.children.values() {
+        match content {
+            ContentType::Node(n) => collect_rules(n, rules),
+            ContentType::NodeVec(ns) => {
+                for n in ns {
+                    collect_rules(n, rules);
+                }
+            }
+        }
+    }
+}
+
+/// Inserts the new clauses right after the known clause which precedes them.
+fn merge_clauses(merged: &mut Vec<String>, clauses: impl Iterator<Item = String>) {
+    let mut cursor = 0;
+    for clause in clauses {
+        match merged.iter().position(|c| c == &clause) {
+            Some(i) => cursor = i + 1,
+            None => {
+                merged.insert(cursor, clause);
+                cursor += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::*;
+use crate::constants;
+
+#[test]
+fn test_grammar() {
+    let rules = grammar().unwrap();
+    let select = rules
+        .iter()
+        .find(|r| r.node_type == NodeType::SelectStatement)
+        .unwrap();
+    assert_eq!(select.keyword, "SELECT");
+    let position = |key: &str| select.clauses.iter().position(|c| c == key).unwrap();
+    assert!(position("with") < position("self"));
+    assert!(position("self") < position("exprs"));
+    assert!(position("from") < position("where"));
+    assert!(position("orderby") < position("limit"));
+    // from the second example
+    assert!(select.clauses.contains(&"as_struct_or_value".to_string()));
+
+    // every statement keyword is used
+    for keyword in constants::STATEMENT_KEYWORDS {
+        assert!(
+            EXAMPLES.iter().any(|e| e
+                .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .any(|word| word == keyword)),
+            "{}",
+            keyword
+        );
+    }
+    // every statement has an example
+    for node_type in STATEMENT_TYPES {
+        assert!(
+            rules.iter().any(|r| &r.node_type == node_type),
+            "{:?}",
+            node_type
+        );
+    }
+    assert_eq!(rules.len(), STATEMENT_TYPES.len());
+}
+
+#[test]
+fn test_to_ebnf() {
+    let rules = vec![StatementRule {
+        node_type: NodeType::DropStatement,
+        keyword: "DROP".to_string(),
+        clauses: vec![
+            "self".to_string(),
+            "what".to_string(),
+            "ident".to_string(),
+            "semicolon".to_string(),
+        ],
+    }];
+    assert_eq!(
+        to_ebnf(&rules),
+        "DropStatement ::= \"DROP\" [ what ] [ ident ] [ semicolon ] ;\n"
+    );
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests;
+
+use crate::analysis::child_nodes;
+use crate::cst::Node;
+use crate::diagnostic::Span;
+use alloc::vec::Vec;
+
+/// An index from positions to nodes, which is built once per parse result.
+/// Lookups take O(log n) time in the number of tokens instead of walking the trees,
+/// which is useful for editor features (e.g. hover, go to the enclosing clause).
+pub struct NodeIndex<'a> {
+    /// The span of each token and the index of its node, sorted by position.
+    tokens: Vec<(Span, usize)>,
+    /// Every node and the index of its parent.
+    nodes: Vec<(&'a Node, Option<usize>)>,
+}
+
+impl<'a> NodeIndex<'a> {
+    pub fn new(stmts: &'a [Node]) -> NodeIndex<'a> {
+        let mut index = NodeIndex {
+            tokens: Vec::new(),
+            nodes: Vec::new(),
+        };
+        for stmt in stmts {
+            index.push(stmt, None);
+        }
+        index
+            .tokens
+            .sort_by_key(|(s, _)| (s.start_line, s.start_column));
+        index
+    }
+    /// Returns the node whose token contains `line:column`.
+    /// `None` is returned between tokens (e.g. on whitespace).
+    pub fn node_at(&self, line: usize, column: usize) -> Option<&'a Node> {
+        self.find(line, column).map(|i| self.nodes[i].0)
+    }
+    /// Returns the node at `line:column` followed by its ancestors up to the statement.
+    pub fn path_at(&self, line: usize, column: usize) -> Vec<&'a Node> {
+        let mut res = Vec::new();
+        let mut idx = self.find(line, column);
+        while let Some(i) = idx {
+            let (node, parent) = self.nodes[i];
+            res.push(node);
+            idx = parent;
+        }
+        res
+    }
+    fn push(&mut self, node: &'a Node, parent: Option<usize>) {
+        let idx = self.nodes.len();
+        self.nodes.push((node, parent));
+        if let Some(token) = &node.token {
+            if !token.literal.is_empty() {
+                self.tokens.push((Span::from_token(token), idx));
+            }
+        }
+        for (_, child) in child_nodes(node) {
+            self.push(child, Some(idx));
+        }
+    }
+    fn find(&self, line: usize, column: usize) -> Option<usize> {
+        let pos = (line, column);
+        let n = self
+            .tokens
+            .partition_point(|(s, _)| (s.start_line, s.start_column) <= pos);
+        let (span, idx) = self.tokens.get(n.checked_sub(1)?)?;
+        (pos < (span.end_line, span.end_column)).then_some(*idx)
+    }
+}
@@ -0,0 +1,45 @@
+use super::*;
+use crate::cst::NodeType;
+use crate::utils::parse;
+
+#[test]
+fn test_node_index() {
+    let stmts = parse(
+        "\
+SELECT a + 1 AS x
+FROM t /* multi
+line */
+WHERE 'abc' = b;
+",
+    );
+    let index = NodeIndex::new(&stmts);
+    let literal = |n: Option<&Node>| n.map(|n| n.token.as_ref().unwrap().literal.clone());
+    assert_eq!(literal(index.node_at(1, 1)), Some("SELECT".to_string()));
+    assert_eq!(literal(index.node_at(1, 6)), Some("SELECT".to_string()));
+    assert_eq!(literal(index.node_at(1, 7)), None);
+    assert_eq!(literal(index.node_at(1, 10)), Some("+".to_string()));
+    assert_eq!(
+        literal(index.node_at(3, 3)),
+        Some("/* multi\nline */".to_string())
+    );
+    assert_eq!(literal(index.node_at(4, 9)), Some("'abc'".to_string()));
+    assert_eq!(literal(index.node_at(0, 1)), None);
+    assert_eq!(literal(index.node_at(9, 1)), None);
+
+    let types: Vec<NodeType> = index
+        .path_at(4, 15)
+        .iter()
+        .map(|n| n.node_type.clone())
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            NodeType::Identifier,
+            NodeType::BinaryOperator,
+            NodeType::KeywordWithExpr,
+            NodeType::SelectStatement,
+        ]
+    );
+    assert!(index.path_at(1, 7).is_empty());
+    assert!(NodeIndex::new(&[]).node_at(1, 1).is_none());
+}
@@ -1,50 +1,156 @@
 #[cfg(test)]
 mod tests;
 
-use crate::error::{BQ2CSTError, BQ2CSTResult};
+use crate::diagnostic::{Diagnostic, Span};
+use crate::error::{BQ2CSTError, BQ2CSTResult, ErrorCode};
+use crate::options::ParseOptions;
 use crate::token::Token;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::char::is_combining_mark;
 
 pub struct Lexer {
     input: Vec<char>,
     position: usize,
     line: usize,
     column: usize,
-    type_declaration_depth: usize,
+    options: ParseOptions,
+    input_bytes: usize,
+    /// whitespace which will be the `leading_trivia` of the next token
+    leading_trivia: String,
+    pub bom: bool,
+    pub header: Vec<Token>,
     pub tokens: Vec<Token>,
+    pub warnings: Vec<Diagnostic>,
 }
 
 impl Lexer {
     // ----- pub -----
     pub fn new(input: String) -> Lexer {
-        let chars: Vec<char> = input.chars().collect();
-        Lexer {
+        Lexer::with_options(input, ParseOptions::default())
+    }
+    pub fn with_options(input: String, options: ParseOptions) -> Lexer {
+        Lexer::with_buffers(&input, options, Vec::new(), Vec::new())
+    }
+    /// Same as `with_options` but `chars` and `tokens` are cleared and reused
+    /// instead of allocating new ones for each input (see `document::Buffers`).
+    pub(crate) fn with_buffers(
+        input: &str,
+        options: ParseOptions,
+        mut chars: Vec<char>,
+        mut tokens: Vec<Token>,
+    ) -> Lexer {
+        chars.clear();
+        tokens.clear();
+        let input_bytes = input.len();
+        // NOTE the code is not read at all if it is too long (see `tokenize_code`)
+        let too_long = options.max_input_bytes.is_some_and(|max| max < input_bytes);
+        if !too_long {
+            chars.extend(input.chars());
+        }
+        let bom = chars.first() == Some(&'\u{feff}');
+        if bom {
+            chars.remove(0);
+        }
+        let mut lexer = Lexer {
             input: chars,
             position: 0,
             line: 1,
             column: 1,
-            type_declaration_depth: 0,
-            tokens: Vec::new(),
-        }
+            options,
+            input_bytes,
+            leading_trivia: String::new(),
+            bom,
+            header: Vec::new(),
+            tokens,
+            warnings: Vec::new(),
+        };
+        lexer.read_header();
+        lexer
+    }
+    pub fn tokenize_code(self) -> BQ2CSTResult<Vec<Token>> {
+        let (tokens, _) = self.tokenize_code_with_warnings()?;
+        Ok(tokens)
+    }
+    pub fn tokenize_code_with_warnings(mut self) -> BQ2CSTResult<(Vec<Token>, Vec<Diagnostic>)> {
+        self.tokenize()?;
+        Ok((self.tokens, self.warnings))
     }
-    pub fn tokenize_code(mut self) -> BQ2CSTResult<Vec<Token>> {
-        let mut token = self.next_token()?;
-        while !token.is_none() {
-            token = self.next_token()?;
+    /// Reads the whole input into `tokens` (and `warnings`).
+    /// Unlike `tokenize_code`, the lexer is kept to return its buffer (see `into_chars`).
+    pub(crate) fn tokenize(&mut self) -> BQ2CSTResult<()> {
+        if let Some(max) = self.options.max_input_bytes {
+            if max < self.input_bytes {
+                return Err(BQ2CSTError::new(
+                    1,
+                    1,
+                    ErrorCode::ResourceLimitExceeded,
+                    format!(
+                        "The code is {} bytes, which exceeds `max_input_bytes` ({}).",
+                        self.input_bytes, max
+                    ),
+                ));
+            }
+        }
+        while self.next_token()?.is_some() {
+            if let Some(max) = self.options.max_tokens {
+                if max < self.tokens.len() {
+                    let last = self.tokens.last().unwrap();
+                    return Err(BQ2CSTError::from_token(
+                        last,
+                        ErrorCode::ResourceLimitExceeded,
+                        format!("The code has more tokens than `max_tokens` ({}).", max),
+                    ));
+                }
+            }
         }
-        self.tokens.push(Token::eof());
-        Ok(self.tokens)
+        let mut eof = Token::new(self.line, self.column, String::new());
+        eof.leading_trivia = core::mem::take(&mut self.leading_trivia);
+        eof.offset = Some(self.input_bytes);
+        self.tokens.push(eof);
+        Ok(())
+    }
+    pub(crate) fn into_chars(self) -> Vec<char> {
+        self.input
     }
     // ----- core -----
+    fn read_header(&mut self) {
+        for _ in 0..self.options.header_lines {
+            if self.get_char(0).is_none() {
+                break;
+            }
+            let line = self.line;
+            let column = self.column;
+            let mut literal = String::new();
+            while let Some(ch) = self.get_char(0) {
+                self.position += 1;
+                if ch == '\n' {
+                    self.line += 1;
+                    self.column = 1;
+                    break;
+                }
+                self.column += 1;
+                literal.push(ch);
+            }
+            self.header.push(Token::new(line, column, literal));
+        }
+    }
     fn construct_token(&mut self, line: usize, column: usize, literal: String) -> &Token {
-        let token = Token::new(line, column, literal);
+        let mut token = Token::new(line, column, literal);
+        token.leading_trivia = core::mem::take(&mut self.leading_trivia);
         self.tokens.push(token);
-        &self.tokens.last().unwrap()
+        self.tokens.last().unwrap()
     }
     fn get_char(&self, offset: usize) -> Option<char> {
         if self.position + offset < self.input.len() {
-            return Some(self.input[self.position + offset]);
+            Some(self.input[self.position + offset])
         } else {
-            return None; // EOF
+            None // EOF
         }
     }
     fn next_char(&mut self) -> BQ2CSTResult<()> {
@@ -61,6 +167,7 @@ impl Lexer {
             Err(BQ2CSTError::new(
                 self.line,
                 self.column,
+                ErrorCode::UnexpectedEofInLexer,
                 "Unexpected EOF.".to_string(),
             ))
         } else {
@@ -79,7 +186,8 @@ impl Lexer {
         let column = self.column;
         let token = match ch {
             '.' => match self.get_char(1) {
-                Some('0'..='9') => {
+                // NOTE `.1` in `region-us.reservation.1` is not a number
+                Some('0'..='9') if !self.follows_identifier() => {
                     let literal = self.read_number()?;
                     self.construct_token(line, column, literal)
                 }
@@ -149,21 +257,14 @@ impl Lexer {
                     self.next_char()?;
                     self.construct_token(line, column, "<>".to_string())
                 } else {
-                    if self.tokens.last().unwrap().literal.to_uppercase() == "ARRAY"
-                        || self.tokens.last().unwrap().literal.to_uppercase() == "STRUCT"
-                    {
-                        self.type_declaration_depth += 1;
-                    }
                     self.next_char()?;
                     self.construct_token(line, column, ch.to_string())
                 }
             }
             '>' => {
-                if 0 < self.type_declaration_depth {
-                    self.type_declaration_depth -= 1;
-                    self.next_char()?;
-                    self.construct_token(line, column, ch.to_string())
-                } else if self.get_char(1) == Some('>') {
+                // NOTE `>>` may close nested type declarations (e.g. `ARRAY<ARRAY<INT64>>`),
+                // which is split by the parser
+                if self.get_char(1) == Some('>') {
                     self.next_char()?;
                     self.next_char()?;
                     self.construct_token(line, column, ">>".to_string())
@@ -196,12 +297,23 @@ impl Lexer {
                     self.construct_token(line, column, ch.to_string())
                 }
             }
+            // hint
+            '@' if self.options.hints && self.get_char(1) == Some('{') => {
+                let literal = self.read_hint()?;
+                self.construct_token(line, column, literal)
+            }
             // parameter
             '@' => {
                 let literal = self.read_parameter()?;
                 self.construct_token(line, column, literal)
             }
             // template
+            '{' if self.options.jinja_templates
+                && matches!(self.get_char(1), Some('{') | Some('%') | Some('#')) =>
+            {
+                let literal = self.read_jinja_template()?;
+                self.construct_token(line, column, literal)
+            }
             '{' => {
                 let first_position = self.position;
                 let mut count = 0;
@@ -222,9 +334,7 @@ impl Lexer {
                 for _ in 0..count {
                     self.next_char()?
                 } // } ->
-                let res = self.input[first_position..self.position]
-                    .into_iter()
-                    .collect();
+                let res = self.input[first_position..self.position].iter().collect();
                 self.construct_token(line, column, res)
             }
             // int64 or float64 literal
@@ -245,24 +355,53 @@ impl Lexer {
         };
         Ok(Some(token))
     }
+    /// The whitespace up to the first line break becomes the `trailing_trivia` of the last token
+    /// and the rest becomes the `leading_trivia` of the next token.
     fn skip_whitespace(&mut self) -> BQ2CSTResult<()> {
+        let first_position = self.position;
         while is_whitespace(&self.get_char(0)) {
             self.next_char()?;
         }
+        if !self.options.keep_trivia {
+            return Ok(());
+        }
+        let whitespace = &self.input[first_position..self.position];
+        let leading = match self.tokens.last_mut() {
+            Some(token) => {
+                let end = whitespace
+                    .iter()
+                    .position(|ch| ch == &'\n')
+                    .unwrap_or(whitespace.len());
+                token.trailing_trivia = whitespace[..end].iter().collect();
+                &whitespace[end..]
+            }
+            None => whitespace,
+        };
+        self.leading_trivia = leading.iter().collect();
         Ok(())
     }
     // ----- read -----
     fn read_back_quoted(&mut self) -> BQ2CSTResult<String> {
-        let quote = self.get_char(0);
+        // escape sequences are the same as those of strings (e.g. `a\`b`, `\u00e9`)
         let first_position = self.position;
-        self.next_char()?;
-        while self.get_char(0) != quote {
+        let mut odd_backslashes = false;
+        self.next_char()?; // ` ->
+        loop {
+            if !odd_backslashes && self.get_char(0) == Some('`') {
+                break;
+            }
+            if self.get_char(0) == Some('\\') {
+                if !odd_backslashes {
+                    self.validate_escape_sequence()?;
+                }
+                odd_backslashes = !odd_backslashes;
+            } else {
+                odd_backslashes = false;
+            }
             self.next_char()?;
         }
-        self.next_char()?; // ' -> next_ch
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        self.next_char()?; // ` ->
+        let res = self.input[first_position..self.position].iter().collect();
         Ok(res)
     }
     fn read_comment(&mut self) -> BQ2CSTResult<String> {
@@ -271,10 +410,14 @@ impl Lexer {
             self.next_char()?;
         }
         let res = self.input[first_position..self.position]
-            .into_iter()
+            .iter()
             .collect::<String>()
             .trim_end()
             .to_string();
+        // trailing whitespace is left for `skip_whitespace`
+        let trimmed = self.input[first_position..self.position].len() - res.chars().count();
+        self.position -= trimmed;
+        self.column -= trimmed;
         Ok(res)
     }
     fn read_identifier(&mut self) -> BQ2CSTResult<String> {
@@ -284,6 +427,7 @@ impl Lexer {
             return Err(BQ2CSTError::new(
                 self.line,
                 self.column,
+                ErrorCode::InvalidCharacter,
                 "Invalid character as an identifier.".to_string(),
             ));
         }
@@ -291,27 +435,85 @@ impl Lexer {
         while is_valid_char_of_ident(&self.get_char(0)) {
             self.next_char()?;
         }
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = self.input[first_position..self.position].iter().collect();
+        Ok(res)
+    }
+    fn read_jinja_template(&mut self) -> BQ2CSTResult<String> {
+        // {{ ... }}, {% ... %} or {# ... #}
+        let first_position = self.position;
+        let closing = match self.get_char(1) {
+            Some('{') => '}',
+            Some(c) => c,
+            None => unreachable!(),
+        };
+        self.next_char()?; // { ->
+        self.next_char()?; // {, % or # ->
+        let mut quote: Option<char> = None;
+        loop {
+            let ch = self.get_char(0);
+            match quote {
+                Some(q) => {
+                    if ch == Some('\\') {
+                        self.next_char()?;
+                    } else if ch == Some(q) {
+                        quote = None;
+                    }
+                }
+                None => {
+                    if ch == Some(closing) && self.get_char(1) == Some('}') {
+                        break;
+                    }
+                    if closing != '#' && (ch == Some('\'') || ch == Some('"')) {
+                        quote = ch;
+                    }
+                }
+            }
+            self.next_char()?;
+        }
+        self.next_char()?; // }, % or # -> }
+        self.next_char()?; // } ->
+        let res = self.input[first_position..self.position].iter().collect();
         Ok(res)
     }
     fn read_multiline_comment(&mut self) -> BQ2CSTResult<String> {
         let first_position = self.position;
-        while !(self.get_char(0) == Some('*') && self.get_char(1) == Some('/')) {
+        self.next_char()?; // / -> *
+        self.next_char()?; // * ->
+        let mut depth = 1;
+        let mut warned = false;
+        loop {
+            if self.get_char(0) == Some('*') && self.get_char(1) == Some('/') {
+                depth -= 1;
+                if depth == 0 || !self.options.nested_comments {
+                    break;
+                }
+                self.next_char()?; // * -> /
+            } else if self.get_char(0) == Some('/') && self.get_char(1) == Some('*') {
+                if self.options.nested_comments {
+                    depth += 1;
+                    self.next_char()?; // / -> *
+                } else if !warned {
+                    self.warnings.push(Diagnostic::warning(
+                        "W004",
+                        Span::new(self.line, self.column, self.line, self.column + 2),
+                        "`/*` in a comment is not nested. The comment ends at the first `*/`."
+                            .to_string(),
+                    ));
+                    warned = true;
+                }
+            }
             self.next_char()?;
         }
         self.next_char()?; // * -> /
         self.next_char()?; // / -> next_char
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = self.input[first_position..self.position].iter().collect();
         Ok(res)
     }
     fn read_multiline_string(&mut self) -> BQ2CSTResult<String> {
         // NOTE '''abc''' is OK. ''''abc'''' should throw an error.
         let first_position = self.position;
         let ch = self.get_char(0);
+        let raw = self.follows_raw_prefix();
         let mut odd_backslashes = false;
         self.next_char()?; // 1st ' -> 2nd '
         self.next_char()?; // 2nd ' -> 3rd '
@@ -325,6 +527,9 @@ impl Lexer {
                 break;
             }
             if self.get_char(0) == Some('\\') {
+                if !odd_backslashes && !raw {
+                    self.validate_escape_sequence()?;
+                }
                 odd_backslashes = !odd_backslashes;
             } else {
                 odd_backslashes = false;
@@ -334,13 +539,83 @@ impl Lexer {
         self.next_char()?; // 1st ' -> 2nd '
         self.next_char()?; // 2nd ' -> 3rd '
         self.next_char()?; // 3rd ' ->  next_ch
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = self.input[first_position..self.position].iter().collect();
         Ok(res)
     }
+    fn follows_raw_prefix(&self) -> bool {
+        // r'...', rb'...' or br'...'
+        if self.position == 0 || !self.input[self.position - 1].is_alphabetic() {
+            return false;
+        }
+        match self.tokens.last() {
+            Some(token) => token.in_(&vec!["r", "rb", "br"]),
+            None => false,
+        }
+    }
+    fn validate_escape_sequence(&mut self) -> BQ2CSTResult<()> {
+        // self.get_char(0) is `\`
+        let valid = match self.get_char(1) {
+            Some('a' | 'b' | 'f' | 'n' | 'r' | 't' | 'v' | '\\' | '?' | '"' | '\'' | '`') => true,
+            Some('0'..='7') => (2..4).all(|i| matches!(self.get_char(i), Some('0'..='7'))),
+            Some('x' | 'X') => self.followed_by_hex_digits(2),
+            Some('u') => self.followed_by_hex_digits(4),
+            Some('U') => self.followed_by_hex_digits(8),
+            _ => false,
+        };
+        if valid {
+            return Ok(());
+        }
+        let sequence: String = self.input[self.position..]
+            .iter()
+            .take_while(|c| !c.is_whitespace())
+            .take(10)
+            .collect();
+        let message = format!("Invalid escape sequence: {}", sequence);
+        if self.options.allow_invalid_escapes {
+            self.warnings.push(Diagnostic::warning(
+                "W001",
+                Span::new(self.line, self.column, self.line, self.column + 2),
+                message,
+            ));
+            Ok(())
+        } else {
+            Err(BQ2CSTError::new(
+                self.line,
+                self.column,
+                ErrorCode::InvalidEscapeSequence,
+                message,
+            ))
+        }
+    }
+    fn followed_by_hex_digits(&self, n: usize) -> bool {
+        // the first two characters are `\` and `x`, `u` or `U`
+        (2..2 + n).all(|i| match self.get_char(i) {
+            Some(c) => c.is_ascii_hexdigit(),
+            None => false,
+        })
+    }
     fn read_number(&mut self) -> BQ2CSTResult<String> {
         let first_position = self.position;
+        let (line, column) = (self.line, self.column);
+        // NOTE in a path (e.g. `dataset.1table`, `project-1e`), it may be a part of an identifier
+        // (but not in `-1e`, where `-` is not preceded by an identifier)
+        let before = self
+            .position
+            .checked_sub(2)
+            .map(|i| (self.input[i], self.input[i + 1]));
+        let in_path =
+            matches!(before, Some((c, '.' | '-')) if c.is_alphanumeric() || c == '_' || c == '`');
+        if let (Some('0'), Some('x' | 'X')) = (self.get_char(0), self.get_char(1)) {
+            if self.get_char(2).is_some_and(|c| c.is_ascii_hexdigit()) {
+                self.next_char()?; // 0 -> x
+                self.next_char()?; // x -> hex digit
+                while self.get_char(0).is_some_and(|c| c.is_ascii_hexdigit()) {
+                    self.next_char()?;
+                }
+                let res = self.input[first_position..self.position].iter().collect();
+                return Ok(res);
+            }
+        }
         while is_digit(&self.get_char(0)) {
             self.next_char()?;
         } // 9 -> .
@@ -350,18 +625,68 @@ impl Lexer {
                 self.next_char()?;
             }
         }
+        let mut valid = true;
         if let Some('E') | Some('e') = self.get_char(0) {
             self.next_char()?; // e -> 9, +, -
             if let Some('+') | Some('-') = self.get_char(0) {
                 self.next_char()?; // +, - -> 9
             }
+            valid = is_digit(&self.get_char(0));
             while is_digit(&self.get_char(0)) {
                 self.next_char()?;
             }
         }
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        if self.get_char(0) == Some('.') && is_digit(&self.get_char(1)) {
+            valid = false; // e.g. `1.2.3`
+        }
+        if !valid && !in_path {
+            // the whole malformed text (e.g. `1.2.3`, not `1.2`)
+            let end = self.input[self.position..]
+                .iter()
+                .position(|c| !(c.is_alphanumeric() || *c == '.' || *c == '_'))
+                .map_or(self.input.len(), |i| self.position + i);
+            let text: String = self.input[first_position..end].iter().collect();
+            return Err(BQ2CSTError::new(
+                line,
+                column,
+                ErrorCode::InvalidNumericLiteral,
+                format!("Invalid numeric literal: {}", text),
+            ));
+        }
+        let res: String = self.input[first_position..self.position].iter().collect();
+        Ok(res)
+    }
+    fn follows_identifier(&self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        let prev = self.input[self.position - 1];
+        prev.is_alphanumeric() || prev == '_' || prev == '`' || is_combining_mark(prev)
+    }
+    fn read_hint(&mut self) -> BQ2CSTResult<String> {
+        // @{ key=value, ... }
+        let first_position = self.position;
+        self.next_char()?; // @ -> {
+        let mut depth = 0;
+        loop {
+            match self.get_char(0) {
+                Some('{') => depth += 1,
+                Some('}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                Some('\'') | Some('"') => {
+                    self.read_string()?;
+                    continue;
+                }
+                _ => (),
+            }
+            self.next_char()?;
+        }
+        self.next_char()?; // } ->
+        let res = self.input[first_position..self.position].iter().collect();
         Ok(res)
     }
     fn read_parameter(&mut self) -> BQ2CSTResult<String> {
@@ -374,14 +699,13 @@ impl Lexer {
         } else {
             self.read_identifier()?;
         }
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = self.input[first_position..self.position].iter().collect();
         Ok(res)
     }
     fn read_string(&mut self) -> BQ2CSTResult<String> {
         let quote = self.get_char(0);
         let first_position = self.position;
+        let raw = self.follows_raw_prefix();
         let mut odd_backslashes = false;
         self.next_char()?; // " ->
         loop {
@@ -389,6 +713,9 @@ impl Lexer {
                 break;
             }
             if self.get_char(0) == Some('\\') {
+                if !odd_backslashes && !raw {
+                    self.validate_escape_sequence()?;
+                }
                 odd_backslashes = !odd_backslashes;
             } else {
                 odd_backslashes = false;
@@ -396,16 +723,14 @@ impl Lexer {
             self.next_char()?;
         }
         self.next_char()?; // " ->
-        let res = self.input[first_position..self.position]
-            .into_iter()
-            .collect();
+        let res = self.input[first_position..self.position].iter().collect();
         Ok(res)
     }
 }
 
 fn is_digit(ch: &Option<char>) -> bool {
     match ch {
-        Some(ch) => ch.is_digit(10),
+        Some(ch) => ch.is_ascii_digit(),
         None => false,
     }
 }
@@ -424,9 +749,19 @@ fn is_valid_1st_char_of_ident(ch: &Option<char>) -> bool {
     }
 }
 
+/// Combining marks are recognized only with the `unicode-normalization` feature.
+#[cfg(not(feature = "unicode-normalization"))]
+fn is_combining_mark(_: char) -> bool {
+    false
+}
+
+/// Combining marks are accepted after the first character
+/// so that decomposed letters (e.g. `e` + U+0301) are read as a part of the identifier.
 fn is_valid_char_of_ident(ch: &Option<char>) -> bool {
     match ch {
-        Some(ch) => ch.is_alphabetic() || ch.is_digit(10) || ch == &'_',
+        Some(ch) => {
+            ch.is_alphabetic() || ch.is_ascii_digit() || ch == &'_' || is_combining_mark(*ch)
+        }
         None => false,
     }
 }
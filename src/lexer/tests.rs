@@ -1,4 +1,5 @@
 use super::*;
+use crate::token::CommentKind;
 
 trait TestCase {
     fn test(&self);
@@ -17,7 +18,15 @@ impl SuccessTestCase {
         let tokens = l.tokenize_code();
         let result_tokens = tokens.expect("Failed to tokenize code.");
         let mut expected_tokens = expected_tokens_without_eof;
-        expected_tokens.push(Token::eof());
+        // EOF is placed right after the last character
+        let line = code.matches('\n').count() + 1;
+        let last_line = code
+            .trim_start_matches('\u{feff}')
+            .rsplit('\n')
+            .next()
+            .unwrap();
+        let column = last_line.chars().count() + 1;
+        expected_tokens.push(Token::from_str(line, column, ""));
         SuccessTestCase {
             code,
             expected_tokens,
@@ -173,12 +182,42 @@ SELECT ''''xxx''''",
             1,
             19, // unclosed string literal
         )),
-        // NOTE this is wrong syntax but difficult to ditect
-        // Box::new(ErrorTestCase::new(
-        //     "SELECT '\\1'",
-        //     1,
-        //     11,
-        // )),
+        // escape sequence
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT '\\n\\'\\\"\\\\\\101\\x41\\X41\\u0041\\U00000041', '''\\t'''",
+            vec![
+                Token::from_str(1, 1, "SELECT"),
+                Token::from_str(1, 8, "'\\n\\'\\\"\\\\\\101\\x41\\X41\\u0041\\U00000041'"),
+                Token::from_str(1, 46, ","),
+                Token::from_str(1, 48, "'''\\t'''"),
+            ],
+        )),
+        Box::new(ErrorTestCase::new(
+            "\
+SELECT '\\1'",
+            1,
+            9,
+        )),
+        Box::new(ErrorTestCase::new(
+            "\
+SELECT 'a\\qb'",
+            1,
+            10,
+        )),
+        Box::new(ErrorTestCase::new(
+            "\
+SELECT '\\x4'",
+            1,
+            9,
+        )),
+        Box::new(ErrorTestCase::new(
+            "\
+SELECT '''
+\\u12'''",
+            2,
+            1,
+        )),
         // string literal (raw)
         Box::new(SuccessTestCase::new(
             "\
@@ -279,8 +318,7 @@ SELECT
                 Token::from_str(3, 16, "INT64"),
                 Token::from_str(3, 21, ","),
                 Token::from_str(3, 22, "INT64"),
-                Token::from_str(3, 27, ">"),
-                Token::from_str(3, 28, ">"),
+                Token::from_str(3, 27, ">>"),
                 Token::from_str(3, 29, "["),
                 Token::from_str(3, 30, "("),
                 Token::from_str(3, 31, "0"),
@@ -391,8 +429,327 @@ SELECT f(a1, a2)",
         )),
         // empty
         Box::new(SuccessTestCase::new("", vec![])),
+        // BOM
+        Box::new(SuccessTestCase::new(
+            "\u{feff}SELECT 1",
+            vec![Token::from_str(1, 1, "SELECT"), Token::from_str(1, 8, "1")],
+        )),
     ];
     for t in test_cases {
         t.test();
     }
 }
+
+#[test]
+fn test_header_lines() {
+    let options = ParseOptions {
+        header_lines: 2,
+        ..Default::default()
+    };
+    let l = Lexer::with_options(
+        "\u{feff}exported by some tool\nversion: 1\nSELECT 1".to_string(),
+        options,
+    );
+    assert!(l.bom);
+    assert_eq!(
+        l.header,
+        vec![
+            Token::from_str(1, 1, "exported by some tool"),
+            Token::from_str(2, 1, "version: 1"),
+        ]
+    );
+    let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+    assert_eq!(
+        tokens,
+        vec![
+            Token::from_str(3, 1, "SELECT"),
+            Token::from_str(3, 8, "1"),
+            Token::from_str(3, 9, ""),
+        ]
+    );
+}
+
+#[test]
+fn test_allow_invalid_escapes() {
+    let options = ParseOptions {
+        allow_invalid_escapes: true,
+        ..Default::default()
+    };
+    let l = Lexer::with_options("SELECT '\\q', r'\\q'".to_string(), options);
+    let (tokens, warnings) = l
+        .tokenize_code_with_warnings()
+        .expect("Failed to tokenize code.");
+    assert_eq!(tokens[1], Token::from_str(1, 8, "'\\q'"));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].code, "W001");
+    assert_eq!(warnings[0].span, Span::new(1, 9, 1, 11));
+}
+
+#[test]
+fn test_error_code() {
+    let test_cases = vec![
+        ("SELECT 'foo", ErrorCode::UnexpectedEofInLexer),
+        ("SELECT '\\q'", ErrorCode::InvalidEscapeSequence),
+        ("SELECT 1.2.3", ErrorCode::InvalidNumericLiteral),
+        ("SELECT .1.2", ErrorCode::InvalidNumericLiteral),
+        ("SELECT 1e", ErrorCode::InvalidNumericLiteral),
+        ("SELECT 1.5E+", ErrorCode::InvalidNumericLiteral),
+        ("SELECT -1e", ErrorCode::InvalidNumericLiteral),
+        ("SELECT x - -1.2.3", ErrorCode::InvalidNumericLiteral),
+    ];
+    for (code, expected) in test_cases {
+        let error = Lexer::new(code.to_string())
+            .tokenize_code()
+            .expect_err("Unexpectedly succeeded to tokenize code.");
+        assert_eq!(expected, error.code, "{}", code);
+    }
+    let error = Lexer::new("SELECT 1.2.3".to_string())
+        .tokenize_code()
+        .unwrap_err();
+    assert_eq!(error.message(), "Invalid numeric literal: 1.2.3");
+    // a part of an identifier
+    assert!(
+        Lexer::new("SELECT * FROM project-1e.dataset.1table".to_string())
+            .tokenize_code()
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_jinja_templates() {
+    let options = ParseOptions {
+        jinja_templates: true,
+        ..Default::default()
+    };
+    let l = Lexer::with_options(
+        "{{ ref('}}') }} {% if x == '%}' %} {# '} #}".to_string(),
+        options,
+    );
+    let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+    assert_eq!(
+        tokens,
+        vec![
+            Token::from_str(1, 1, "{{ ref('}}') }}"),
+            Token::from_str(1, 17, "{% if x == '%}' %}"),
+            Token::from_str(1, 36, "{# '} #}"),
+            Token::from_str(1, 44, ""),
+        ]
+    );
+    assert_eq!(tokens[1].comment_kind(), Some(CommentKind::TemplateTag));
+}
+
+#[test]
+fn test_nested_comments() {
+    let code = "/* outer /* inner */ SELECT 1 /* x */";
+    let (tokens, warnings) = Lexer::new(code.to_string())
+        .tokenize_code_with_warnings()
+        .expect("Failed to tokenize code.");
+    assert_eq!(tokens[0], Token::from_str(1, 1, "/* outer /* inner */"));
+    assert_eq!(tokens[1], Token::from_str(1, 22, "SELECT"));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].code, "W004");
+    assert_eq!(warnings[0].span, Span::new(1, 10, 1, 12));
+
+    let options = ParseOptions {
+        nested_comments: true,
+        ..Default::default()
+    };
+    let code = "/* outer /* inner */ still comment */ SELECT";
+    let (tokens, warnings) = Lexer::with_options(code.to_string(), options)
+        .tokenize_code_with_warnings()
+        .expect("Failed to tokenize code.");
+    assert_eq!(
+        tokens,
+        vec![
+            Token::from_str(1, 1, "/* outer /* inner */ still comment */"),
+            Token::from_str(1, 39, "SELECT"),
+            Token::from_str(1, 45, ""),
+        ]
+    );
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_hints() {
+    let code = "FROM t @{FORCE_INDEX=idx, k='}'} JOIN @{JOIN_METHOD=HASH_JOIN} u".to_string();
+    assert!(Lexer::new(code.clone()).tokenize_code().is_err());
+    let options = ParseOptions {
+        hints: true,
+        ..Default::default()
+    };
+    let tokens = Lexer::with_options(code, options)
+        .tokenize_code()
+        .expect("Failed to tokenize code.");
+    assert_eq!(
+        tokens,
+        vec![
+            Token::from_str(1, 1, "FROM"),
+            Token::from_str(1, 6, "t"),
+            Token::from_str(1, 8, "@{FORCE_INDEX=idx, k='}'}"),
+            Token::from_str(1, 34, "JOIN"),
+            Token::from_str(1, 39, "@{JOIN_METHOD=HASH_JOIN}"),
+            Token::from_str(1, 64, "u"),
+            Token::from_str(1, 65, ""),
+        ]
+    );
+    assert!(tokens[2].is_hint());
+    assert!(!tokens[2].is_parameter());
+}
+
+#[test]
+fn test_numeric_path_segment() {
+    let tokens = Lexer::new("region-us.res.123 .5".to_string())
+        .tokenize_code()
+        .expect("Failed to tokenize code.");
+    assert_eq!(
+        tokens,
+        vec![
+            Token::from_str(1, 1, "region"),
+            Token::from_str(1, 7, "-"),
+            Token::from_str(1, 8, "us"),
+            Token::from_str(1, 10, "."),
+            Token::from_str(1, 11, "res"),
+            Token::from_str(1, 14, "."),
+            Token::from_str(1, 15, "123"),
+            Token::from_str(1, 19, ".5"),
+            Token::from_str(1, 21, ""),
+        ]
+    );
+}
+
+#[test]
+fn test_hex_literal() {
+    let tokens = Lexer::new("0x1F 0Xab 0xyz".to_string())
+        .tokenize_code()
+        .expect("Failed to tokenize code.");
+    assert_eq!(
+        tokens,
+        vec![
+            Token::from_str(1, 1, "0x1F"),
+            Token::from_str(1, 6, "0Xab"),
+            Token::from_str(1, 11, "0"),
+            Token::from_str(1, 12, "xyz"),
+            Token::from_str(1, 15, ""),
+        ]
+    );
+}
+
+#[test]
+fn test_trivia() {
+    let code = "\n  SELECT\t1, -- comment  \r\n  2\n\n;  ";
+    let options = ParseOptions {
+        keep_trivia: true,
+        ..Default::default()
+    };
+    let tokens = Lexer::with_options(code.to_string(), options)
+        .tokenize_code()
+        .expect("Failed to tokenize code.");
+    let trivia: Vec<(&str, &str, &str)> = tokens
+        .iter()
+        .map(|t| (t.leading_trivia(), t.literal.as_str(), t.trailing_trivia()))
+        .collect();
+    assert_eq!(
+        trivia,
+        vec![
+            ("\n  ", "SELECT", "\t"),
+            ("", "1", ""),
+            ("", ",", " "),
+            ("", "-- comment", "  \r"),
+            ("\n  ", "2", ""),
+            ("\n\n", ";", "  "),
+            ("", "", ""),
+        ]
+    );
+    let reconstructed: String = trivia
+        .iter()
+        .map(|(leading, literal, trailing)| format!("{}{}{}", leading, literal, trailing))
+        .collect();
+    assert_eq!(reconstructed, code);
+    assert_eq!(tokens[3], Token::from_str(2, 13, "-- comment"));
+
+    // not kept by default
+    let tokens = Lexer::new(code.to_string())
+        .tokenize_code()
+        .expect("Failed to tokenize code.");
+    assert!(tokens
+        .iter()
+        .all(|t| t.leading_trivia().is_empty() && t.trailing_trivia().is_empty()));
+}
+
+#[test]
+fn test_resource_limits() {
+    let options = ParseOptions {
+        max_input_bytes: Some(8),
+        ..Default::default()
+    };
+    assert!(Lexer::with_options("SELECT 1".to_string(), options.clone())
+        .tokenize_code()
+        .is_ok());
+    let err = Lexer::with_options("SELECT 12".to_string(), options)
+        .tokenize_code()
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::ResourceLimitExceeded);
+    assert_eq!((err.line, err.column), (1, 1));
+
+    let options = ParseOptions {
+        max_tokens: Some(3),
+        ..Default::default()
+    };
+    assert!(
+        Lexer::with_options("SELECT 1;".to_string(), options.clone())
+            .tokenize_code()
+            .is_ok()
+    );
+    let err = Lexer::with_options("SELECT 1; -- comment".to_string(), options)
+        .tokenize_code()
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::ResourceLimitExceeded);
+    assert_eq!((err.line, err.column), (1, 11));
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn test_combining_mark() {
+    // `e` followed by U+0301 (COMBINING ACUTE ACCENT)
+    let tokens = Lexer::new("SELECT cafe\u{301}.x".to_string())
+        .tokenize_code()
+        .unwrap();
+    assert_eq!(tokens[1], Token::from_str(1, 8, "cafe\u{301}"));
+    assert_eq!(tokens[2], Token::from_str(1, 13, "."));
+    // a combining mark cannot be the first character
+    let tokens = Lexer::new("SELECT \u{301}a".to_string())
+        .tokenize_code()
+        .unwrap();
+    assert_eq!(tokens[1], Token::from_str(1, 8, "\u{301}"));
+    assert_eq!(tokens[2], Token::from_str(1, 9, "a"));
+}
+
+#[test]
+fn test_back_quoted_escape() {
+    let tokens = Lexer::new(r"SELECT `weird\`name`, `a\\`".to_string())
+        .tokenize_code()
+        .unwrap();
+    assert_eq!(tokens[1], Token::from_str(1, 8, r"`weird\`name`"));
+    assert_eq!(tokens[2], Token::from_str(1, 21, ","));
+    assert_eq!(tokens[3], Token::from_str(1, 23, r"`a\\`"));
+    let err = Lexer::new(r"SELECT `a\qb`".to_string())
+        .tokenize_code()
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidEscapeSequence);
+}
+
+#[test]
+fn test_eof_position() {
+    let code = "\u{feff}SELECT 'é'\n  -- comment\n  ";
+    let options = ParseOptions {
+        keep_trivia: true,
+        ..Default::default()
+    };
+    let tokens = Lexer::with_options(code.to_string(), options)
+        .tokenize_code()
+        .unwrap();
+    let eof = tokens.last().unwrap();
+    assert_eq!(eof, &Token::from_str(3, 3, ""));
+    assert_eq!(eof.offset, Some(code.len()));
+    assert_eq!(eof.leading_trivia(), "\n  ");
+}
@@ -1,59 +1,34 @@
-// https://github.com/rustwasm/wasm-bindgen/issues/2882
-#![allow(non_upper_case_globals)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+// `check` measures time with `std` also in the command line interface
+#[cfg(all(feature = "cli", not(feature = "std")))]
+extern crate std;
+
+pub mod analysis;
+#[cfg(feature = "json")]
+pub mod borrowed;
+pub mod builder;
+pub mod check;
 mod constants;
-mod cst;
-mod error;
-mod lexer;
-mod parser;
-mod token;
-mod types;
+pub mod cst;
+pub mod diagnostic;
+pub mod diff;
+pub mod document;
+pub mod edit;
+pub mod engine;
+pub mod error;
+pub mod fingerprint;
+pub mod functions;
+pub mod grammar;
+pub mod index;
+pub mod lexer;
+pub mod literal;
+pub mod options;
+pub mod parser;
+pub mod precedence;
+pub mod scope;
+pub mod split;
+pub mod stats;
+pub mod token;
 mod utils;
-
-use serde::Serialize;
-use serde_wasm_bindgen::Serializer;
-use wasm_bindgen::prelude::*;
-
-#[wasm_bindgen(skip_typescript)]
-pub fn parse(code: String) -> Result<JsValue, JsValue> {
-    utils::set_panic_hook();
-    let l = lexer::Lexer::new(code);
-    let s = Serializer::json_compatible();
-    let mut p = parser::Parser::new(match l.tokenize_code() {
-        Ok(tokens) => tokens,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
-        }
-    });
-    let stmts = match p.parse_code() {
-        Ok(stmts) => stmts,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
-        }
-    };
-    Ok(stmts
-        .serialize(&s)
-        .expect("Problem converting stmts to json."))
-}
-
-#[wasm_bindgen(skip_typescript)]
-pub fn tokenize(code: String) -> Result<JsValue, JsValue> {
-    utils::set_panic_hook();
-    let l = lexer::Lexer::new(code);
-    let s = Serializer::json_compatible();
-    let tokens = match l.tokenize_code() {
-        Ok(tokens) => tokens,
-        Err(bq2cst_error) => {
-            return Err(bq2cst_error
-                .serialize(&s)
-                .expect("Problem converting error struct to json."))
-        }
-    };
-    Ok(tokens
-        .serialize(&s)
-        .expect("Problem converting tokens to json."))
-}
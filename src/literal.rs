@@ -0,0 +1,394 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node, NodeType};
+use crate::error::BQ2CSTResult;
+use crate::lexer::Lexer;
+use crate::token::Token;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// The decoded value of a string or bytes literal.
+#[derive(PartialEq, Debug, Clone)]
+pub enum LiteralValue {
+    String(String),
+    Bytes(Vec<u8>),
+}
+
+/// Decodes a `StringLiteral` node, which may be wrapped by its prefix
+/// (`r'...'`, `b'...'` and `rb'...'` are parsed as `UnaryOperator`).
+/// Returns `None` if `node` is not a string literal
+/// or it contains an invalid escape sequence (see `ParseOptions.allow_invalid_escapes`).
+pub fn decode_string_literal(node: &Node) -> Option<LiteralValue> {
+    match node.node_type {
+        NodeType::StringLiteral => decode_string(&node.token.as_ref()?.literal, ""),
+        NodeType::UnaryOperator => {
+            let prefix = &node.token.as_ref()?.literal;
+            match node.children.get("right") {
+                Some(ContentType::Node(n)) if n.node_type == NodeType::StringLiteral => {
+                    decode_string(&n.token.as_ref()?.literal, prefix)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Decodes the literal of a string token (e.g. `'''a\nb'''`).
+/// `prefix` is one of `""`, `r`, `b`, `rb` and `br` (case-insensitive).
+pub fn decode_string(literal: &str, prefix: &str) -> Option<LiteralValue> {
+    let prefix = prefix.to_lowercase();
+    let (raw, bytes) = match prefix.as_str() {
+        "" => (false, false),
+        "r" => (true, false),
+        "b" => (false, true),
+        "rb" | "br" => (true, true),
+        _ => return None,
+    };
+    let body = unquote(literal)?;
+    let decoded = if raw {
+        body.as_bytes().to_vec()
+    } else {
+        unescape(body, bytes)?
+    };
+    if bytes {
+        Some(LiteralValue::Bytes(decoded))
+    } else {
+        String::from_utf8(decoded).ok().map(LiteralValue::String)
+    }
+}
+
+/// Returns the name of an `Identifier` (or `Unknown`) node
+/// with the backticks removed and the escape sequences decoded (e.g. `` `a\`b` `` -> ``a`b``).
+/// Returns `None` if `node` has no token or the identifier cannot be decoded.
+pub fn decode_identifier(node: &Node) -> Option<String> {
+    match node.node_type {
+        NodeType::Identifier | NodeType::Unknown => {
+            decode_identifier_literal(&node.token.as_ref()?.literal)
+        }
+        _ => None,
+    }
+}
+
+/// Decodes the literal of an identifier token.
+/// Unquoted identifiers are returned as they are.
+pub fn decode_identifier_literal(literal: &str) -> Option<String> {
+    let body = match literal.strip_prefix('`') {
+        Some(rest) => rest.strip_suffix('`')?,
+        None => return Some(literal.to_string()),
+    };
+    String::from_utf8(unescape(body, false)?).ok()
+}
+
+// ----- utilities -----
+fn unquote(literal: &str) -> Option<&str> {
+    for quote in ["'''", "\"\"\"", "'", "\""] {
+        if quote.len() * 2 <= literal.len()
+            && literal.starts_with(quote)
+            && literal.ends_with(quote)
+        {
+            return Some(&literal[quote.len()..literal.len() - quote.len()]);
+        }
+    }
+    None
+}
+
+fn unescape(body: &str, bytes: bool) -> Option<Vec<u8>> {
+    let mut res = Vec::new();
+    let mut chars = body.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            let mut buf = [0; 4];
+            res.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let byte = match chars.next()? {
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0C,
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            'v' => 0x0B,
+            c @ ('\\' | '?' | '"' | '\'' | '`') => c as u8,
+            c @ '0'..='7' => {
+                let digits: String = core::iter::once(c).chain(chars.by_ref().take(2)).collect();
+                u8::from_str_radix(&digits, 8)
+                    .ok()
+                    .filter(|_| digits.len() == 3)?
+            }
+            'x' | 'X' => read_hex(&mut chars, 2)? as u8,
+            c @ ('u' | 'U') => {
+                if bytes {
+                    return None;
+                }
+                let n = if c == 'u' { 4 } else { 8 };
+                let ch = char::from_u32(read_hex(&mut chars, n)?)?;
+                let mut buf = [0; 4];
+                res.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+                continue;
+            }
+            _ => return None,
+        };
+        res.push(byte);
+    }
+    Some(res)
+}
+
+fn read_hex(chars: &mut core::str::Chars, n: usize) -> Option<u32> {
+    let digits: String = chars.take(n).collect();
+    if digits.len() != n || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(&digits, 16).ok()
+}
+
+/// Splits the mode keywords (`lax`, `strict` and `recursive`, case-insensitive)
+/// from a JSONPath passed to `JSON_QUERY`, `JSON_VALUE` and so on (e.g. `lax recursive $.a`).
+/// Returns the keywords in order of appearance and the rest of the path.
+/// The path should be decoded beforehand (see `decode_string_literal`).
+pub fn split_json_path_mode(path: &str) -> (Vec<&str>, &str) {
+    let mut modes = Vec::new();
+    let mut rest = path.trim_start();
+    while let Some(end) = rest.find(char::is_whitespace) {
+        let word = &rest[..end];
+        if !["lax", "strict", "recursive"]
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case(word))
+        {
+            break;
+        }
+        modes.push(word);
+        rest = rest[end..].trim_start();
+    }
+    (modes, rest)
+}
+
+/// Checks the value of a typed literal (e.g. `'2024-02-30'` of `DATE '2024-02-30'`).
+/// `type_name` is one of `DATE`, `DATETIME`, `TIME` and `TIMESTAMP` (case-insensitive),
+/// and the value should be decoded beforehand (see `decode_string_literal`).
+/// Returns the reason if the value is obviously invalid.
+/// NOTE Time zone names (e.g. `America/Los_Angeles`) are not checked.
+pub fn validate_datetime_literal(type_name: &str, value: &str) -> Result<(), &'static str> {
+    let mut rest = value.trim();
+    match type_name.to_uppercase().as_str() {
+        "DATE" => rest = read_date(rest)?,
+        "TIME" => rest = read_time(rest)?,
+        "DATETIME" | "TIMESTAMP" => {
+            rest = read_date(rest)?;
+            if let Some(time) = rest.strip_prefix([' ', 'T', 't']) {
+                let time = time.trim_start();
+                if time.starts_with(|c: char| c.is_ascii_digit()) {
+                    rest = read_time(time)?;
+                }
+            }
+            if type_name.eq_ignore_ascii_case("TIMESTAMP") {
+                rest = read_time_zone(rest.trim_start())?;
+            }
+        }
+        _ => return Ok(()),
+    }
+    if rest.is_empty() {
+        Ok(())
+    } else {
+        Err("unexpected characters")
+    }
+}
+
+/// `YYYY-[M]M-[D]D`
+fn read_date(s: &str) -> Result<&str, &'static str> {
+    let (year, s) = read_digits(s, 1, 4).ok_or("invalid date")?;
+    let s = s.strip_prefix('-').ok_or("invalid date")?;
+    let (month, s) = read_digits(s, 1, 2).ok_or("invalid date")?;
+    let s = s.strip_prefix('-').ok_or("invalid date")?;
+    let (day, s) = read_digits(s, 1, 2).ok_or("invalid date")?;
+    if year == 0 {
+        return Err("year is out of range");
+    }
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => return Err("month is out of range"),
+    };
+    if day == 0 || days < day {
+        return Err("day is out of range");
+    }
+    Ok(s)
+}
+
+/// `[H]H:[M]M[:[S]S[.F]]`
+fn read_time(s: &str) -> Result<&str, &'static str> {
+    let (hour, s) = read_digits(s, 1, 2).ok_or("invalid time")?;
+    let s = s.strip_prefix(':').ok_or("invalid time")?;
+    let (minute, mut s) = read_digits(s, 1, 2).ok_or("invalid time")?;
+    let mut second = 0;
+    if let Some(rest) = s.strip_prefix(':') {
+        (second, s) = read_digits(rest, 1, 2).ok_or("invalid time")?;
+        if let Some(rest) = s.strip_prefix('.') {
+            let (_, rest) = read_digits(rest, 1, usize::MAX).ok_or("invalid time")?;
+            s = rest;
+        }
+    }
+    if 23 < hour {
+        Err("hour is out of range")
+    } else if 59 < minute {
+        Err("minute is out of range")
+    } else if 60 < second {
+        // 60 is accepted as a leap second
+        Err("second is out of range")
+    } else {
+        Ok(s)
+    }
+}
+
+/// `Z`, `+HH[:MM]`, `-HH[:MM]` or a time zone name
+fn read_time_zone(s: &str) -> Result<&str, &'static str> {
+    if let Some(rest) = s.strip_prefix(['Z', 'z']) {
+        return Ok(rest);
+    }
+    if let Some(offset) = s.strip_prefix(['+', '-']) {
+        let (hour, mut rest) = read_digits(offset, 1, 2).ok_or("invalid time zone")?;
+        let mut minute = 0;
+        if let Some(m) = rest.strip_prefix(':') {
+            (minute, rest) = read_digits(m, 2, 2).ok_or("invalid time zone")?;
+        }
+        if 14 < hour || 59 < minute {
+            return Err("time zone offset is out of range");
+        }
+        return Ok(rest);
+    }
+    Ok(s.trim_start_matches(|c: char| {
+        c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '+' | '-')
+    }))
+}
+
+/// Reads `min` to `max` ASCII digits.
+fn read_digits(s: &str, min: usize, max: usize) -> Option<(u32, &str)> {
+    let len = s
+        .char_indices()
+        .take_while(|(i, c)| *i < max && c.is_ascii_digit())
+        .count();
+    if len < min {
+        return None;
+    }
+    let value = s[..len].parse().unwrap_or(u32::MAX);
+    Some((value, &s[len..]))
+}
+
+/// Replaces the string literals with `'?'` (or `"?"`) and the numeric literals with `0`
+/// so that queries can be logged without their values.
+/// The other tokens (including comments) and the positions of all the tokens are kept.
+/// Empty strings are not replaced because the placeholder is longer than them.
+/// Returns the number of replaced tokens.
+pub fn scrub_literals(tokens: &mut [Token]) -> usize {
+    let mut count = 0;
+    let targets: Vec<Option<&'static str>> =
+        (0..tokens.len()).map(|i| placeholder(tokens, i)).collect();
+    for (token, placeholder) in tokens.iter_mut().zip(targets) {
+        if let Some(placeholder) = placeholder {
+            token.literal = placeholder.to_string();
+            token.synthetic = true;
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Same as `scrub_literals` but returns the code.
+/// Each placeholder is padded with spaces (and the line breaks of the literal),
+/// so every other token stays at the same line and column as in `code`.
+pub fn scrub_code(code: &str) -> BQ2CSTResult<String> {
+    let tokens = Lexer::new(code.to_string()).tokenize_code()?;
+    let mut targets: Vec<(&Token, &str)> = (0..tokens.len())
+        .filter_map(|i| placeholder(&tokens, i).map(|p| (&tokens[i], p)))
+        .collect();
+    targets.reverse();
+    let mut res = String::with_capacity(code.len());
+    let mut chars = code.chars().peekable();
+    if chars.peek() == Some(&'\u{feff}') {
+        res.extend(chars.next());
+    }
+    let (mut line, mut column) = (1, 1);
+    // the rest of the placeholder and the number of the remaining chars of the literal
+    let mut current: Option<(core::str::Chars, usize)> = None;
+    for ch in chars {
+        if current.is_none() {
+            if let Some((token, placeholder)) = targets.last() {
+                if (token.line, token.column) == (line, column) {
+                    current = Some((placeholder.chars(), token.literal.chars().count()));
+                    targets.pop();
+                }
+            }
+        }
+        match &mut current {
+            Some((placeholder, rest)) => {
+                if ch == '\n' {
+                    res.push('\n');
+                } else {
+                    res.push(placeholder.next().unwrap_or(' '));
+                }
+                *rest -= 1;
+                if *rest == 0 {
+                    current = None;
+                }
+            }
+            None => res.push(ch),
+        }
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    Ok(res)
+}
+
+fn placeholder(tokens: &[Token], i: usize) -> Option<&'static str> {
+    let token = &tokens[i];
+    if token.is_string() && 2 < token.literal.chars().count() {
+        if token.literal.starts_with('"') {
+            Some("\"?\"")
+        } else {
+            Some("'?'")
+        }
+    } else if token.is_numeric() && !in_identifier_path(tokens, i) {
+        Some("0")
+    } else {
+        None
+    }
+}
+
+/// e.g. `123.` of `my-project-123.dataset.table`, `123` of `region-us.res.123`
+/// (but not `100` of `amount-100`, which has no `.` after it)
+fn in_identifier_path(tokens: &[Token], i: usize) -> bool {
+    let adjacent = |left: &Token, right: &Token| {
+        left.line == right.line && left.column + left.literal.chars().count() == right.column
+    };
+    let after_dot = i
+        .checked_sub(1)
+        .is_some_and(|j| tokens[j].literal == "." && adjacent(&tokens[j], &tokens[i]));
+    if after_dot {
+        return true;
+    }
+    // follow the parts joined by `-` (e.g. `123-project`) up to `.`
+    let mut j = i;
+    while let Some(next) = tokens.get(j + 1).filter(|n| adjacent(&tokens[j], n)) {
+        if next.literal == "." || tokens[j].literal.ends_with('.') && next.is_identifier() {
+            return true;
+        }
+        let part = tokens.get(j + 2).filter(|p| {
+            next.literal == "-" && adjacent(next, p) && (p.is_identifier() || p.is_numeric())
+        });
+        if part.is_none() {
+            break;
+        }
+        j += 2;
+    }
+    false
+}
@@ -0,0 +1,222 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[test]
+fn test_decode_string() {
+    let test_cases = vec![
+        ("'abc'", "", Some(LiteralValue::String("abc".to_string()))),
+        (
+            "\"it's\"",
+            "",
+            Some(LiteralValue::String("it's".to_string())),
+        ),
+        (
+            "'''a\nb'''",
+            "",
+            Some(LiteralValue::String("a\nb".to_string())),
+        ),
+        (
+            "\"\"\"\"\"\"",
+            "",
+            Some(LiteralValue::String("".to_string())),
+        ),
+        (
+            r"'a\tb\n\'\\'",
+            "",
+            Some(LiteralValue::String("a\tb\n'\\".to_string())),
+        ),
+        (
+            r"'\x41\101é\U0001F600'",
+            "",
+            Some(LiteralValue::String("AAé😀".to_string())),
+        ),
+        (
+            r"'\d+'",
+            "r",
+            Some(LiteralValue::String(r"\d+".to_string())),
+        ),
+        (
+            r"'\xff\n'",
+            "b",
+            Some(LiteralValue::Bytes(vec![0xff, b'\n'])),
+        ),
+        (
+            r"'\xff'",
+            "RB",
+            Some(LiteralValue::Bytes(br"\xff".to_vec())),
+        ),
+        // invalid
+        (r"'\q'", "", None),
+        (r"'\x4'", "", None),
+        (r"'\xff'", "", None), // not UTF-8
+        ("abc", "", None),
+        ("'abc'", "x", None),
+    ];
+    for (literal, prefix, expected) in test_cases {
+        assert_eq!(
+            decode_string(literal, prefix),
+            expected,
+            "{}{}",
+            prefix,
+            literal
+        );
+    }
+}
+
+#[test]
+fn test_decode_string_literal() {
+    let code = r"SELECT 'a\'b', r'\n', b'\x00', 1";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let exprs = match stmts[0].children.get("exprs") {
+        Some(ContentType::NodeVec(ns)) => ns,
+        _ => panic!(),
+    };
+    let values: Vec<Option<LiteralValue>> = exprs.iter().map(decode_string_literal).collect();
+    assert_eq!(
+        values,
+        vec![
+            Some(LiteralValue::String("a'b".to_string())),
+            Some(LiteralValue::String(r"\n".to_string())),
+            Some(LiteralValue::Bytes(vec![0])),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn test_decode_identifier() {
+    let code = r"SELECT `weird\`name`, `caf\u00e9`, `a\\`, abc, 1";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let exprs = match stmts[0].children.get("exprs") {
+        Some(ContentType::NodeVec(ns)) => ns,
+        _ => panic!(),
+    };
+    let names: Vec<Option<String>> = exprs.iter().map(decode_identifier).collect();
+    assert_eq!(
+        names,
+        vec![
+            Some("weird`name".to_string()),
+            Some("café".to_string()),
+            Some(r"a\".to_string()),
+            Some("abc".to_string()),
+            None,
+        ]
+    );
+    assert_eq!(
+        decode_identifier_literal("`project-id.dataset.table`"),
+        Some("project-id.dataset.table".to_string())
+    );
+}
+
+#[test]
+fn test_split_json_path_mode() {
+    let test_cases = [
+        ("$.a", (vec![], "$.a")),
+        ("lax $.a", (vec!["lax"], "$.a")),
+        ("LAX  Recursive $.a.b", (vec!["LAX", "Recursive"], "$.a.b")),
+        ("strict $", (vec!["strict"], "$")),
+        ("lax", (vec![], "lax")),
+    ];
+    for (path, expected) in test_cases {
+        assert_eq!(split_json_path_mode(path), expected, "{}", path);
+    }
+}
+
+#[test]
+fn test_scrub_literals() {
+    let code = r#"SELECT 'secret', "x", '', 12.5, b'\x00', col1 FROM t -- 'comment'"#;
+    let mut tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    assert_eq!(scrub_literals(&mut tokens), 4);
+    let literals: Vec<&str> = tokens.iter().map(|t| t.literal.as_str()).collect();
+    assert_eq!(
+        literals,
+        vec![
+            "SELECT",
+            "'?'",
+            ",",
+            r#""?""#,
+            ",",
+            "''",
+            ",",
+            "0",
+            ",",
+            "b",
+            "'?'",
+            ",",
+            "col1",
+            "FROM",
+            "t",
+            "-- 'comment'",
+            "",
+        ]
+    );
+    // positions are not changed
+    assert_eq!((tokens[7].line, tokens[7].column), (1, 27));
+}
+
+#[test]
+fn test_scrub_code() {
+    let code = "\
+SELECT '''multi
+line''' AS a, 100 AS b, x
+FROM t WHERE y = 'abc' -- 'abc'
+";
+    assert_eq!(
+        scrub_code(code).unwrap(),
+        "SELECT '?'     \n        AS a, 0   AS b, x\nFROM t WHERE y = '?'   -- 'abc'\n"
+    );
+    assert!(scrub_code("SELECT '").is_err());
+    // numbers in table paths are not literals
+    assert_eq!(
+        scrub_code("SELECT a FROM my-project-123.dataset.t, region-us.res.123 WHERE x = -1")
+            .unwrap(),
+        "SELECT a FROM my-project-123.dataset.t, region-us.res.123 WHERE x = -0"
+    );
+    // but numbers in arithmetic without spaces are
+    assert_eq!(
+        scrub_code("SELECT a FROM 123-project.d.t WHERE amount-100 > 0 AND 5000-x < 1").unwrap(),
+        "SELECT a FROM 123-project.d.t WHERE amount-0   > 0 AND 0   -x < 0"
+    );
+}
+
+#[test]
+fn test_validate_datetime_literal() {
+    let test_cases = vec![
+        ("DATE", "2024-2-9", Ok(())),
+        ("date", "2000-02-29", Ok(())),
+        ("DATE", "1900-02-29", Err("day is out of range")),
+        ("DATE", "0000-01-01", Err("year is out of range")),
+        ("DATE", "2024/01/01", Err("invalid date")),
+        ("DATE", "2024-01-01 00:00:00", Err("unexpected characters")),
+        ("TIME", "23:59:60", Ok(())),
+        ("TIME", "12:60:00", Err("minute is out of range")),
+        ("DATETIME", "2024-01-01", Ok(())),
+        ("DATETIME", "2024-01-01 10:00:00.5", Ok(())),
+        (
+            "DATETIME",
+            "2024-01-01 10:00:00Z",
+            Err("unexpected characters"),
+        ),
+        ("TIMESTAMP", "2024-01-01T10:00:00Z", Ok(())),
+        ("TIMESTAMP", "2024-01-01 10:00:00 UTC", Ok(())),
+        ("TIMESTAMP", "2024-01-01 10:00:00-07:30", Ok(())),
+        (
+            "TIMESTAMP",
+            "2024-01-01 10:00:00+15",
+            Err("time zone offset is out of range"),
+        ),
+        ("INT64", "not checked", Ok(())),
+    ];
+    for (type_name, value, expected) in test_cases {
+        assert_eq!(
+            validate_datetime_literal(type_name, value),
+            expected,
+            "{} '{}'",
+            type_name,
+            value
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use alloc::{vec, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+/// Options shared by the lexer and the parser.
+/// `ParseOptions::default()` behaves the same as BigQuery.
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParseOptions {
+    /// The number of leading lines which are not SQL
+    /// (e.g. headers written by export tools).
+    /// They are skipped by the lexer and kept as `Document.header`.
+    pub header_lines: usize,
+    /// If `true`, invalid escape sequences in string literals (e.g. `'\q'`, `'\x4'`)
+    /// are reported as warnings instead of errors.
+    pub allow_invalid_escapes: bool,
+    /// If `true`, parsing continues after errors and they are kept as `Document.errors`
+    /// (see `Parser::parse_code_with_recovery`). Lexer errors are still fatal.
+    pub recover_from_errors: bool,
+    /// If `true`, Jinja (or dbt) templating `{{ ... }}`, `{% ... %}` and `{# ... #}` is recognized.
+    /// `{{ ... }}` is parsed as `Template` and the others are kept like comments.
+    pub jinja_templates: bool,
+    /// If `true`, query parameters (`@var`, `?`) and templates (`{...}`) are accepted
+    /// where a statement, a subquery or an `IN` list is expected.
+    /// This is useful for queries generated by string substitution.
+    pub placeholders: bool,
+    /// If `true`, `/* ... */` can be nested (e.g. `/* outer /* inner */ still comment */`).
+    /// BigQuery does not support it.
+    pub nested_comments: bool,
+    /// If `true`, hints such as `@{FORCE_INDEX=idx}` are parsed as `Hint`
+    /// and attached to the following statement or join, or to the preceding table.
+    pub hints: bool,
+    /// Syntax which is rejected with `ErrorCode::DisabledFeature` even though it is supported.
+    /// This is useful to forbid preview features.
+    pub disabled_features: Vec<SyntaxFeature>,
+    /// The lexer refuses code longer than this (in bytes) with `ErrorCode::ResourceLimitExceeded`.
+    pub max_input_bytes: Option<usize>,
+    /// The lexer aborts with `ErrorCode::ResourceLimitExceeded`
+    /// once it reads more tokens (including comments) than this.
+    pub max_tokens: Option<usize>,
+    /// The parser aborts with `ErrorCode::ResourceLimitExceeded`
+    /// once it creates more nodes than this (including those of abandoned alternatives).
+    pub max_nodes: Option<usize>,
+    /// The parser aborts with `ErrorCode::ResourceLimitExceeded`
+    /// once statements, queries, tables and expressions are nested deeper than this
+    /// instead of overflowing the stack. `None` means `parser::DEFAULT_MAX_DEPTH`.
+    pub max_depth: Option<usize>,
+    /// If `true`, the literals of `Identifier` (and `Unknown`) nodes are normalized to NFC
+    /// (e.g. `e` followed by U+0301 becomes `é`) so that they can be compared as they are.
+    /// The tokens returned by the lexer are kept as written.
+    /// `line` and `column` are not changed, so the end of a normalized identifier
+    /// computed from its literal (e.g. `Span::from_token`) may differ from the code.
+    /// It requires the `unicode-normalization` feature and is ignored without it.
+    pub normalize_identifiers: bool,
+    /// If `true`, DECLARE which is not at the beginning of a script or `BEGIN ... END` block
+    /// is rejected with `ErrorCode::MisplacedDeclare` as BigQuery does.
+    /// Otherwise it is parsed as usual and reported by `analysis::check_declare`.
+    pub strict_declare: bool,
+    /// If `true`, tokens keep the whitespace around them (see `Token::leading_trivia`).
+    /// It is off by default because it makes the serialized CST larger.
+    pub keep_trivia: bool,
+}
+
+impl ParseOptions {
+    pub fn is_enabled(&self, feature: SyntaxFeature) -> bool {
+        !self.disabled_features.contains(&feature)
+    }
+}
+
+/// Syntax which can be disabled by `ParseOptions.disabled_features`.
+/// Pipe syntax and graph queries (GQL) are not supported yet.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyntaxFeature {
+    /// `SELECT WITH DIFFERENTIAL_PRIVACY ...`
+    DifferentialPrivacy,
+    /// `FROM t PIVOT (...)`
+    Pivot,
+    /// `FROM t UNPIVOT (...)`
+    Unpivot,
+    /// `FROM t TABLESAMPLE SYSTEM (...)`
+    Tablesample,
+    /// `QUALIFY ...`
+    Qualify,
+    /// `FROM t MATCH_RECOGNIZE (...)`
+    MatchRecognize,
+}
+
+impl SyntaxFeature {
+    /// The name used in serialized `ParseOptions` (e.g. `"differential_privacy"`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            SyntaxFeature::DifferentialPrivacy => "differential_privacy",
+            SyntaxFeature::Pivot => "pivot",
+            SyntaxFeature::Unpivot => "unpivot",
+            SyntaxFeature::Tablesample => "tablesample",
+            SyntaxFeature::Qualify => "qualify",
+            SyntaxFeature::MatchRecognize => "match_recognize",
+        }
+    }
+    /// Whether the syntax is still in preview in BigQuery.
+    pub fn is_preview(&self) -> bool {
+        matches!(
+            self,
+            SyntaxFeature::DifferentialPrivacy
+                | SyntaxFeature::Tablesample
+                | SyntaxFeature::MatchRecognize
+        )
+    }
+}
+
+/// Returns all the syntax features which the parser supports.
+/// All of them are enabled by default.
+pub fn supported_features() -> Vec<SyntaxFeature> {
+    vec![
+        SyntaxFeature::DifferentialPrivacy,
+        SyntaxFeature::Pivot,
+        SyntaxFeature::Unpivot,
+        SyntaxFeature::Tablesample,
+        SyntaxFeature::Qualify,
+        SyntaxFeature::MatchRecognize,
+    ]
+}
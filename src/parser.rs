@@ -1,10 +1,36 @@
 #[cfg(test)]
 mod tests;
 
+use crate::analysis::identifier_path;
+use crate::constants;
+use crate::cst::ContentType;
 use crate::cst::Node;
 use crate::cst::NodeType;
-use crate::error::{BQ2CSTError, BQ2CSTResult};
-use crate::token::Token;
+use crate::cst::Script;
+use crate::diagnostic::Span;
+use crate::error::{BQ2CSTError, BQ2CSTResult, ErrorCode, Suggestion};
+use crate::options::{ParseOptions, SyntaxFeature};
+use crate::precedence;
+use crate::split::Splitter;
+use crate::token::{FormatDirective, Token};
+use crate::utils;
+use alloc::collections::BTreeMap;
+use alloc::{
+    boxed::Box,
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cell::Cell;
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+/// The default of `ParseOptions.max_depth`.
+/// Code nested this deep fits in an 8 MiB stack in release builds.
+/// Smaller stacks (e.g. spawned threads, WebAssembly) or debug builds need a smaller limit.
+pub const DEFAULT_MAX_DEPTH: usize = 500;
 
 #[derive(Clone)]
 pub struct Parser {
@@ -12,15 +38,49 @@ pub struct Parser {
     leading_comment_indices: Vec<usize>,
     trailing_comment_indices: Vec<usize>,
     tokens: Vec<Token>,
+    options: ParseOptions,
+    /// the number of nodes created so far (see `ParseOptions.max_nodes`)
+    node_count: Cell<usize>,
+    /// the number of rules being parsed (see `ParseOptions.max_depth`)
+    depth: usize,
+    is_cancelled: Option<Rc<dyn Fn() -> bool>>,
+    trace: Option<Rc<TraceFn>>,
+}
+
+type TraceFn = dyn Fn(&TraceEvent);
+
+/// Passed to the callback given to `Parser::with_trace`.
+#[derive(PartialEq, Debug, Clone)]
+pub enum TraceEvent<'a> {
+    /// `rule` (the name of a parse function) is called at `token`.
+    Enter {
+        rule: &'static str,
+        token: &'a Token,
+    },
+    /// `rule` returned and `token` is the last token it consumed
+    /// (or the token where it failed).
+    Exit {
+        rule: &'static str,
+        token: &'a Token,
+        ok: bool,
+    },
 }
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Parser {
+        Parser::with_options(tokens, ParseOptions::default())
+    }
+    pub fn with_options(tokens: Vec<Token>, options: ParseOptions) -> Parser {
         let mut p = Parser {
             position: 0,
             leading_comment_indices: Vec::new(),
             trailing_comment_indices: Vec::new(),
             tokens,
+            options,
+            node_count: Cell::new(0),
+            depth: 0,
+            is_cancelled: None,
+            trace: None,
         };
         while p.tokens[p.position].is_comment() {
             p.leading_comment_indices.push(p.position);
@@ -38,22 +98,267 @@ impl Parser {
         }
         p
     }
+    /// Returns the tokens to reuse their buffer (see `document::Buffers`).
+    pub(crate) fn into_tokens(self) -> Vec<Token> {
+        self.tokens
+    }
+    /// Calls `is_cancelled` before parsing each statement
+    /// (including the ones in scripting blocks) and aborts with `ErrorCode::Cancelled`
+    /// if it returns `true` (e.g. the code was edited again in an editor).
+    pub fn with_cancellation(mut self, is_cancelled: impl Fn() -> bool + 'static) -> Parser {
+        self.is_cancelled = Some(Rc::new(is_cancelled));
+        self
+    }
+    /// Calls `trace` when statements, SELECT statements, tables and expressions
+    /// start and finish being parsed, which helps to find out how the code was misparsed.
+    /// The events of alternatives which are tried and abandoned are also emitted.
+    pub fn with_trace(mut self, trace: impl Fn(&TraceEvent) + 'static) -> Parser {
+        self.trace = Some(Rc::new(trace));
+        self
+    }
     pub fn parse_code(&mut self) -> BQ2CSTResult<Vec<Node>> {
         let mut stmts: Vec<Node> = Vec::new();
         while !self.is_eof(0) {
-            let stmt = self.parse_statement(true)?;
+            let stmt = match self
+                .check_declare(&stmts, true)
+                .and_then(|_| self.parse_statement(true))
+            {
+                Ok(stmt) => stmt,
+                Err(error) => return Err(self.suggest_keyword(error)),
+            };
             stmts.push(stmt);
             self.next_token()?;
         }
         stmts.push(self.construct_node(NodeType::EOF)?);
+        mark_format_exempt(&self.tokens, &mut stmts);
+        #[cfg(feature = "unicode-normalization")]
+        if self.options.normalize_identifiers {
+            normalize_identifiers(&mut stmts);
+        }
         Ok(stmts)
     }
+    /// Same as `parse_code` but the statements are wrapped in a `Script`.
+    pub fn parse_script(&mut self) -> BQ2CSTResult<Script> {
+        let mut script = Script::new(self.parse_code()?);
+        script.eof = self.tokens.last().filter(|t| t.offset.is_some()).cloned();
+        Ok(script)
+    }
+    /// Parses the code without stopping at the first error.
+    /// A statement which fails to be parsed is skipped until the next `;`
+    /// and returned as `UnparsedStatement`, whose `unknown` children hold the rest of the tokens
+    /// in order of appearance (so no token is lost).
+    /// Before skipping, the rest of the statement is checked from the next clause
+    /// (e.g. `WHERE`, `GROUP BY`, `ORDER BY`) so that later errors are also reported.
+    pub fn parse_code_with_recovery(&mut self) -> (Vec<Node>, Vec<BQ2CSTError>) {
+        let mut stmts: Vec<Node> = Vec::new();
+        let mut errors: Vec<BQ2CSTError> = Vec::new();
+        while !self.is_eof(0) {
+            let start = self.position;
+            let leading_comment_indices = self.leading_comment_indices.clone();
+            match self
+                .check_declare(&stmts, true)
+                .and_then(|_| self.parse_statement(true))
+            {
+                Ok(stmt) => stmts.push(stmt),
+                Err(error)
+                    if [ErrorCode::Cancelled, ErrorCode::ResourceLimitExceeded]
+                        .contains(&error.code) =>
+                {
+                    errors.push(error);
+                    break;
+                }
+                Err(error) => {
+                    let error = self.suggest_keyword(error);
+                    let end = self.find_statement_end(start);
+                    let error_idx = self
+                        .tokens
+                        .iter()
+                        .position(|t| (error.line, error.column) <= (t.line, t.column))
+                        .unwrap_or(end)
+                        .min(end);
+                    let is_legacy_sql = error.code == ErrorCode::LegacySql;
+                    errors.push(error);
+                    // the rest of the statement is likely to be legacy SQL too
+                    let clause = if is_legacy_sql {
+                        None
+                    } else {
+                        self.find_next_clause(start, error_idx, end)
+                    };
+                    if let Some(clause) = clause {
+                        let (_, mut clause_errors) = Parser::with_options(
+                            self.tokens_from_clause(clause, end),
+                            self.options.clone(),
+                        )
+                        .parse_code_with_recovery();
+                        errors.append(&mut clause_errors);
+                    }
+                    stmts.push(self.unparsed_statement(start, end, &leading_comment_indices));
+                    self.position = end;
+                    self.leading_comment_indices = Vec::new();
+                    self.trailing_comment_indices = Vec::new();
+                }
+            }
+            if self.is_eof(0) || self.next_token().is_err() {
+                break;
+            }
+        }
+        // unwrap is safe because the last token is always EOF
+        stmts.push(self.construct_node(NodeType::EOF).unwrap());
+        mark_format_exempt(&self.tokens, &mut stmts);
+        #[cfg(feature = "unicode-normalization")]
+        if self.options.normalize_identifiers {
+            normalize_identifiers(&mut stmts);
+        }
+        (stmts, errors)
+    }
+    /// Keeps the tokens from `start` to `end` (`;` or EOF) as they are.
+    fn unparsed_statement(
+        &self,
+        start: usize,
+        end: usize,
+        leading_comment_indices: &[usize],
+    ) -> Node {
+        let mut stmt = Node::new(self.tokens[start].clone(), NodeType::UnparsedStatement);
+        if !leading_comment_indices.is_empty() {
+            stmt.push_node_vec(
+                "leading_comments",
+                leading_comment_indices
+                    .iter()
+                    .map(|i| comment_node(&self.tokens[*i]))
+                    .collect(),
+            );
+        }
+        let unknown: Vec<Node> = self.tokens[start + 1..end]
+            .iter()
+            .map(|t| {
+                if t.is_comment() {
+                    comment_node(t)
+                } else {
+                    Node::new(t.clone(), NodeType::Unknown)
+                }
+            })
+            .collect();
+        if !unknown.is_empty() {
+            stmt.push_node_vec("unknown", unknown);
+        }
+        if start < end && self.tokens[end].is(";") {
+            stmt.push_node(
+                "semicolon",
+                Node::new(self.tokens[end].clone(), NodeType::Symbol),
+            );
+        }
+        stmt
+    }
+    /// Returns the index of `;` which terminates the statement starting at `start`
+    /// (or EOF if there is no such `;`).
+    /// `;` in procedural blocks (e.g. `BEGIN ... END`) does not terminate the statement.
+    fn find_statement_end(&self, start: usize) -> usize {
+        let mut splitter = Splitter::default();
+        for idx in start..self.tokens.len() - 1 {
+            let token = &self.tokens[idx];
+            if token.is_comment() {
+                continue;
+            }
+            let next = self.tokens[idx + 1..].iter().find(|t| !t.is_comment());
+            if splitter.push(token, next) {
+                return idx;
+            }
+        }
+        self.tokens.len() - 1
+    }
+    /// Returns the index of the first clause keyword after `from`
+    /// which belongs to the statement starting at `start`.
+    fn find_next_clause(&self, start: usize, from: usize, end: usize) -> Option<usize> {
+        let mut depth = 0;
+        for idx in start..end {
+            let token = &self.tokens[idx];
+            match token.literal.as_str() {
+                "(" | "[" => depth += 1,
+                ")" | "]" => depth -= 1,
+                _ => (),
+            }
+            if idx <= from || depth != 0 {
+                continue;
+            }
+            if token.in_(&vec!["WHERE", "HAVING", "QUALIFY", "WINDOW", "LIMIT"]) {
+                return Some(idx);
+            }
+            if token.in_(&vec!["GROUP", "ORDER"]) && idx + 1 < end && self.tokens[idx + 1].is("BY")
+            {
+                return Some(idx);
+            }
+        }
+        None
+    }
+    /// Builds tokens of `SELECT * <clause>...;` to check the clause independently.
+    fn tokens_from_clause(&self, clause: usize, end: usize) -> Vec<Token> {
+        let first = &self.tokens[clause];
+        let mut tokens = vec![
+            Token::new(first.line, first.column, "SELECT".to_string()),
+            Token::new(first.line, first.column, "*".to_string()),
+        ];
+        tokens.extend(self.tokens[clause..end].iter().cloned());
+        tokens.push(Token::eof());
+        tokens
+    }
+    /// Looks for a misspelled keyword at or before the position of `error`.
+    fn suggest_keyword(&self, mut error: BQ2CSTError) -> BQ2CSTError {
+        if [
+            ErrorCode::Cancelled,
+            ErrorCode::ResourceLimitExceeded,
+            ErrorCode::SelectInto,
+            ErrorCode::LegacySql,
+            ErrorCode::MisplacedDeclare,
+        ]
+        .contains(&error.code)
+        {
+            return error;
+        }
+        for token in self.tokens.iter().rev() {
+            if (error.line, error.column) < (token.line, token.column) || token.is_comment() {
+                continue;
+            }
+            let at_error = (error.line, error.column) == (token.line, token.column);
+            // short words (e.g. `id`) are likely to be identifiers
+            if (!at_error && token.literal.chars().count() < 4) || !token.is_identifier() {
+                continue;
+            }
+            let expected: Vec<&str> = error.expected.iter().map(|s| s.as_str()).collect();
+            let candidates: &[&str] = if at_error && !expected.is_empty() {
+                &expected
+            } else {
+                &constants::ALL_KEYWORDS
+            };
+            if let Some(kw) = utils::closest_keyword(&token.literal, candidates) {
+                error.suggestion = Some(Box::new(Suggestion {
+                    line: token.line,
+                    column: token.column,
+                    original: token.literal.clone(),
+                    replacement: kw.to_string(),
+                }));
+                break;
+            }
+        }
+        error
+    }
     // ----- core -----
     fn construct_node(&self, node_type: NodeType) -> BQ2CSTResult<Node> {
         // NOTE
         // It is possible to avoid cloning tokens (see #20)
         // but it does not improve execution time.
         let curr_token = self.get_token(0)?;
+        // EOF is not counted so that it can always be added
+        if node_type != NodeType::EOF {
+            self.node_count.set(self.node_count.get() + 1);
+            let count = self.node_count.get();
+            if let Some(max) = self.options.max_nodes.filter(|max| *max < count) {
+                return Err(BQ2CSTError::from_token(
+                    curr_token,
+                    ErrorCode::ResourceLimitExceeded,
+                    format!("The code has more nodes than `max_nodes` ({}).", max),
+                ));
+            }
+        }
         let mut node = match node_type {
             NodeType::EOF => Node::empty(node_type),
             NodeType::Unknown => {
@@ -72,6 +377,9 @@ impl Parser {
                     node.node_type = NodeType::Template;
                 } else if curr_token.literal.to_uppercase() == "NULL" {
                     node.node_type = NodeType::NullLiteral;
+                } else if curr_token.is("DEFAULT") {
+                    // e.g. VALUES (DEFAULT), SET x = DEFAULT
+                    node.node_type = NodeType::Keyword;
                 } else if let "(" | "." = self.get_token(1)?.literal.as_str() {
                     node.node_type = NodeType::Identifier;
                 }
@@ -82,64 +390,43 @@ impl Parser {
         // leading_comments
         let mut leading_comment_nodes = Vec::new();
         for idx in &self.leading_comment_indices {
-            leading_comment_nodes.push(Node::new(self.tokens[*idx].clone(), NodeType::Comment))
+            leading_comment_nodes.push(comment_node(&self.tokens[*idx]))
         }
-        if 0 < leading_comment_nodes.len() {
+        if !leading_comment_nodes.is_empty() {
             node.push_node_vec("leading_comments", leading_comment_nodes);
         }
         // trailing comments
         let mut trailing_comment_nodes = Vec::new();
         for idx in &self.trailing_comment_indices {
-            trailing_comment_nodes.push(Node::new(self.tokens[*idx].clone(), NodeType::Comment))
+            trailing_comment_nodes.push(comment_node(&self.tokens[*idx]))
         }
-        if 0 < trailing_comment_nodes.len() {
+        if !trailing_comment_nodes.is_empty() {
             node.push_node_vec("trailing_comments", trailing_comment_nodes);
         }
         Ok(node)
     }
     fn get_precedence(&self, offset: usize) -> BQ2CSTResult<usize> {
-        // https://cloud.google.com/bigquery/docs/reference/standard-sql/operators
-        // 001... - (identifier e.g. region-us)
-        // 002... DATE, TIMESTAMP, r'', b'' (literal)
-        // 101... [], ., ( (calling function. it's not mentioned in documentation)
-        // 102... +, - , ~ (unary operator)
-        // 103... *, / , ||
-        // 104... +, - (binary operator)
-        // 105... <<, >>
-        // 106... & (bit operator)
-        // 107... ^ (bit operator)
-        // 108... | (bit operator)
-        // 109... =, <, >, like, between, in
-        // 110... NOT
-        // 111... AND
-        // 112... OR
-        // 200... => (ST_GEOGFROMGEOJSON)
-        let precedence = match self.get_token(offset)?.literal.to_uppercase().as_str() {
-            // return precedence of BINARY operator
-            "(" | "." | "[" => 101,
-            "*" | "/" | "||" => 103,
-            "+" | "-" => 104,
-            "<<" | ">>" => 105,
-            "&" => 106,
-            "^" => 107,
-            "|" => 108,
-            "=" | "<" | ">" | "<=" | ">=" | "!=" | "<>" | "LIKE" | "BETWEEN" | "IN" | "IS" => 109,
+        // see `crate::precedence`
+        let literal = self.get_token(offset)?.literal.to_uppercase();
+        if let Some(precedence) = precedence::binary_precedence(&literal) {
+            return Ok(precedence);
+        }
+        let precedence = match literal.as_str() {
             "NOT" => match self.get_token(offset + 1)?.literal.to_uppercase().as_str() {
-                "IN" | "LIKE" | "BETWEEN" => 109,
+                "IN" | "LIKE" | "BETWEEN" => precedence::COMPARISON,
                 "ENFORCED" => usize::MAX,
                 _ => {
                     return Err(BQ2CSTError::from_token(
                         self.get_token(offset + 1)?,
+                        ErrorCode::UnexpectedToken,
                         format!(
                             "Expected `IN`, `LIKE` or `BETWEEN` but got: {:?}",
                             self.get_token(offset + 1)?
                         ),
-                    ))
+                    )
+                    .with_expected(&["IN", "LIKE", "BETWEEN"]))
                 }
             },
-            "AND" => 111,
-            "OR" => 112,
-            "=>" => 200,
             _ => usize::MAX,
         };
         Ok(precedence)
@@ -162,6 +449,7 @@ impl Parser {
             } else {
                 return Err(BQ2CSTError::from_token(
                     &self.tokens[self.tokens.len() - 1],
+                    ErrorCode::UnexpectedEof,
                     "Followed by unexpected EOF".to_string(),
                 ));
             }
@@ -172,6 +460,112 @@ impl Parser {
         let idx = self.get_offset_index(offset)?;
         Ok(&self.tokens[idx])
     }
+    fn check_feature(&self, feature: SyntaxFeature, offset: usize) -> BQ2CSTResult<()> {
+        if self.options.is_enabled(feature) {
+            return Ok(());
+        }
+        Err(BQ2CSTError::from_token(
+            self.get_token(offset)?,
+            ErrorCode::DisabledFeature,
+            format!("`{}` is disabled.", feature.name()),
+        ))
+    }
+    fn traced<T>(
+        &mut self,
+        rule: &'static str,
+        f: impl FnOnce(&mut Parser) -> BQ2CSTResult<T>,
+    ) -> BQ2CSTResult<T> {
+        let max = self.options.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+        if max <= self.depth {
+            return Err(BQ2CSTError::from_token(
+                self.get_token(0)?,
+                ErrorCode::ResourceLimitExceeded,
+                format!("The code is nested deeper than `max_depth` ({}).", max),
+            ));
+        }
+        let trace = self.trace.clone();
+        if let Some(trace) = &trace {
+            trace(&TraceEvent::Enter {
+                rule,
+                token: &self.tokens[self.position],
+            });
+        }
+        self.depth += 1;
+        let res = f(self);
+        self.depth -= 1;
+        if let Some(trace) = &trace {
+            trace(&TraceEvent::Exit {
+                rule,
+                token: &self.tokens[self.position],
+                ok: res.is_ok(),
+            });
+        }
+        res
+    }
+    fn select_into_error(&self, offset: usize) -> BQ2CSTResult<BQ2CSTError> {
+        Ok(BQ2CSTError::from_token(
+            self.get_token(offset)?,
+            ErrorCode::SelectInto,
+            "`SELECT ... INTO` is not supported. \
+            Use `SET x = (SELECT ...)` or `SET (x, y) = (SELECT AS STRUCT ...)` to assign variables, \
+            or `CREATE TABLE ... AS SELECT ...` to create a table."
+                .to_string(),
+        ))
+    }
+    /// Returns an error if the current token starts a table in legacy SQL
+    /// (`[project:dataset.table]` or a table wildcard function).
+    /// NOTE A comma between tables (`FROM t1, t2`) means UNION ALL in legacy SQL
+    /// but it is a valid cross join in GoogleSQL.
+    fn check_legacy_table(&self) -> BQ2CSTResult<()> {
+        let token = self.get_token(0)?;
+        let end = if token.is("[") {
+            let mut depth = 0;
+            let mut end = None;
+            for t in &self.tokens[self.position..] {
+                if t.is("[") {
+                    depth += 1;
+                } else if t.is("]") {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(t);
+                        break;
+                    }
+                }
+            }
+            end.unwrap_or(token)
+        } else if token.in_(&vec![
+            "TABLE_DATE_RANGE",
+            "TABLE_DATE_RANGE_STRICT",
+            "TABLE_QUERY",
+        ]) && self.get_token(1)?.is("(")
+        {
+            token
+        } else {
+            return Ok(());
+        };
+        let end_span = Span::from_token(end);
+        Err(BQ2CSTError::from_token(
+            token,
+            ErrorCode::LegacySql,
+            "Legacy SQL is not supported. \
+            Use `project.dataset.table` instead of `[project:dataset.table]` \
+            and wildcard tables instead of table wildcard functions."
+                .to_string(),
+        )
+        .with_span(Span::new(
+            token.line,
+            token.column,
+            end_span.end_line,
+            end_span.end_column,
+        )))
+    }
+    fn is_placeholder(&self, offset: usize) -> BQ2CSTResult<bool> {
+        if !self.options.placeholders {
+            return Ok(false);
+        }
+        let token = self.get_token(offset)?;
+        Ok(token.is_template() || token.is_parameter() && !token.literal.starts_with("@@"))
+    }
     fn is_eof(&self, offset: usize) -> bool {
         let idx = match self.get_offset_index(offset) {
             Ok(i) => i,
@@ -206,6 +600,25 @@ impl Parser {
         }
         Ok(())
     }
+    /// Splits `>>` into two `>` when closing nested type declarations
+    /// (e.g. `ARRAY<ARRAY<INT64>>`), which the lexer cannot tell from the shift operator.
+    fn split_closing_angle_bracket(&mut self) {
+        let token = &self.tokens[self.position];
+        if token.literal != ">>" {
+            return;
+        }
+        let mut second = token.clone();
+        second.literal = ">".to_string();
+        second.column += 1;
+        // the whitespace before `>>` belongs to the first one and that after to the second one
+        second.leading_trivia = String::new();
+        let first = &mut self.tokens[self.position];
+        first.literal = ">".to_string();
+        first.trailing_trivia = String::new();
+        self.tokens.insert(self.position + 1, second);
+        // trailing comments belong to the second one
+        self.trailing_comment_indices = Vec::new();
+    }
     fn parse_between_operator(&mut self, left: Node) -> BQ2CSTResult<Node> {
         let precedence = self.get_precedence(0)?;
         let mut between = self.construct_node(NodeType::BetweenOperator)?;
@@ -284,11 +697,16 @@ impl Parser {
             self.next_token()?; // -> NOT | ENFORCED
             res.push_node("enforced", self.parse_enforced()?);
         }
-        return Ok(res);
+        Ok(res)
     }
     fn parse_cte(&mut self) -> BQ2CSTResult<Node> {
         let mut query = self.construct_node(NodeType::WithQuery)?;
-        self.next_token()?; // ident -> AS
+        // e.g. `WITH t(a, b) AS (SELECT 1, 2)`
+        if self.get_token(1)?.is("(") {
+            self.next_token()?; // ident -> (
+            query.push_node("columns", self.parse_grouped_exprs(false)?);
+        }
+        self.next_token()?; // ident -> AS, ) -> AS
         query.push_node("as", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // AS -> (
         query.push_node("stmt", self.parse_select_statement(false, true)?);
@@ -315,6 +733,18 @@ impl Parser {
         alias: bool,
         as_table: bool,
         after_dot: bool,
+    ) -> BQ2CSTResult<Node> {
+        self.traced("parse_expr", |p| {
+            p.parse_expr_untraced(precedence, alias, as_table, after_dot)
+        })
+    }
+    #[allow(clippy::collapsible_match)]
+    fn parse_expr_untraced(
+        &mut self,
+        precedence: usize,
+        alias: bool,
+        as_table: bool,
+        after_dot: bool,
     ) -> BQ2CSTResult<Node> {
         let mut left = if after_dot {
             self.construct_node(NodeType::Identifier)?
@@ -328,24 +758,16 @@ impl Parser {
             match self.get_token(0)?.literal.to_uppercase().as_str() {
                 "*" => {
                     left.node_type = NodeType::Asterisk;
-                    match self.get_token(1)?.literal.to_uppercase().as_str() {
-                        "REPLACE" => {
-                            self.next_token()?; // * -> REPLACE
-                            let mut replace =
-                                self.construct_node(NodeType::KeywordWithGroupedXXX)?;
-                            self.next_token()?; // REPLACE -> (
-                            replace.push_node("group", self.parse_grouped_exprs(true)?);
-                            left.push_node("replace", replace);
-                        }
-                        "EXCEPT" => {
-                            self.next_token()?; // * -> except
-                            let mut except =
-                                self.construct_node(NodeType::KeywordWithGroupedXXX)?;
-                            self.next_token()?; // except -> (
-                            except.push_node("group", self.parse_grouped_exprs(false)?);
-                            left.push_node("except", except);
-                        }
-                        _ => (),
+                    // NOTE
+                    // `EXCEPT` not followed by `(` is a set operator
+                    // e.g. `SELECT * EXCEPT DISTINCT SELECT ...`
+                    if self.get_token(1)?.is("EXCEPT") && self.get_token(2)?.is("(") {
+                        self.next_token()?; // * -> EXCEPT
+                        left.push_node("except", self.parse_keyword_with_grouped_exprs(false)?);
+                    }
+                    if self.get_token(1)?.is("REPLACE") {
+                        self.next_token()?; // * | ) -> REPLACE
+                        left.push_node("replace", self.parse_keyword_with_grouped_exprs(true)?);
                     }
                 }
                 // STRUCT
@@ -361,8 +783,8 @@ impl Parser {
                         left.node_type = NodeType::EmptyStruct;
                         left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
                     } else {
-                        exprs = self.parse_exprs(&vec![], true)?; // parse alias in the case of struct
-                        if exprs.len() == 1 {
+                        exprs = self.parse_exprs(&vec![")"], true)?; // parse alias in the case of struct
+                        if exprs.len() == 1 && !has_trailing_comma(&exprs) {
                             left.node_type = NodeType::GroupedExpr;
                             left.push_node("expr", exprs.pop().unwrap());
                         } else {
@@ -407,7 +829,8 @@ impl Parser {
                     if self.get_token(0)?.is("]") {
                         left.push_node_vec("exprs", vec![]);
                     } else {
-                        left.push_node_vec("exprs", self.parse_exprs(&vec![], false)?);
+                        let exprs = self.parse_exprs(&vec!["]"], false)?;
+                        left.push_node_vec("exprs", exprs);
                         self.next_token()?; // exprs -> ]
                     }
                     left.push_node("rparen", self.construct_node(NodeType::Symbol)?);
@@ -422,7 +845,8 @@ impl Parser {
                         if self.get_token(0)?.is("]") {
                             arr.push_node_vec("exprs", vec![]);
                         } else {
-                            arr.push_node_vec("exprs", self.parse_exprs(&vec![], false)?);
+                            let exprs = self.parse_exprs(&vec!["]"], false)?;
+                            arr.push_node_vec("exprs", exprs);
                             self.next_token()?; // exprs -> ]
                         }
                         arr.push_node("rparen", self.construct_node(NodeType::Symbol)?);
@@ -433,7 +857,7 @@ impl Parser {
                 "-" | "+" | "~" => {
                     left.node_type = NodeType::UnaryOperator;
                     self.next_token()?; // - -> expr
-                    let right = self.parse_expr(102, false, false, false)?;
+                    let right = self.parse_expr(precedence::UNARY, false, false, false)?;
                     left.push_node("right", right);
                 }
                 "DATE" | "TIME" | "DATETIME" | "TIMESTAMP" | "NUMERIC" | "BIGNUMERIC"
@@ -444,7 +868,8 @@ impl Parser {
                     {
                         left.node_type = NodeType::UnaryOperator;
                         self.next_token()?; // -> expr
-                        let right = self.parse_expr(002, false, false, false)?;
+                        let right =
+                            self.parse_expr(precedence::TYPED_LITERAL, false, false, false)?;
                         left.push_node("right", right);
                     }
                 }
@@ -465,13 +890,13 @@ impl Parser {
                 "TABLE" | "MODEL" => {
                     left.node_type = NodeType::UnaryOperator;
                     self.next_token()?; // TABLE -> ident
-                    let right = self.parse_expr(002, false, true, false)?;
+                    let right = self.parse_expr(precedence::TYPED_LITERAL, false, true, false)?;
                     left.push_node("right", right);
                 }
                 "B" | "R" | "BR" | "RB" => {
                     if self.get_token(1)?.is_string() {
                         self.next_token()?; // R -> 'string'
-                        let right = self.parse_expr(001, false, false, false)?;
+                        let right = self.parse_expr(precedence::PREFIX, false, false, false)?;
                         left.push_node("right", right);
                         left.node_type = NodeType::UnaryOperator;
                     }
@@ -482,7 +907,7 @@ impl Parser {
                 }
                 "NOT" => {
                     self.next_token()?; // NOT -> boolean
-                    let right = self.parse_expr(110, false, false, false)?;
+                    let right = self.parse_expr(precedence::NOT, false, false, false)?;
                     left.push_node("right", right);
                     left.node_type = NodeType::UnaryOperator;
                 }
@@ -531,6 +956,12 @@ impl Parser {
                         node.push_node("distinct", self.construct_node(NodeType::Keyword)?);
                     }
                     self.next_token()?; // ( -> args
+
+                    // e.g. AI.GENERATE(), ML.GENERATE_EMBEDDING()
+                    let ai_function = left.node_type == NodeType::DotOperator
+                        && identifier_path(&left).is_some_and(|(_, path)| {
+                            AI_FUNCTIONS.contains(&path.to_uppercase().as_str())
+                        });
                     node.push_node("func", left);
                     if !self.get_token(0)?.is(")") {
                         match func.as_str() {
@@ -581,10 +1012,21 @@ impl Parser {
                                 }
                                 node.push_node_vec("args", vec![from]);
                             }
+                            _ if ai_function => {
+                                node.push_node_vec("args", self.parse_ai_function_args()?);
+                            }
                             _ => {
                                 node.push_node_vec("args", self.parse_exprs(&vec![], false)?);
                             }
                         }
+                        // e.g. JSON_VALUE(json, '$.a' RETURNING INT64)
+                        if self.get_token(1)?.is("RETURNING") {
+                            self.next_token()?; // expr -> RETURNING
+                            let mut returning = self.construct_node(NodeType::KeywordWithType)?;
+                            self.next_token()?; // RETURNING -> type
+                            returning.push_node("type", self.parse_type(false, false)?);
+                            node.push_node("returning", returning);
+                        }
                         if self.get_token(1)?.in_(&vec!["RESPECT", "IGNORE"]) {
                             self.next_token()?; // expr -> RESPECT, IGNORE
                             let ignore_or_respect = self.construct_node(NodeType::Keyword)?;
@@ -703,16 +1145,19 @@ impl Parser {
                     } else {
                         return Err(BQ2CSTError::from_token(
                             self.get_token(1)?,
+                            ErrorCode::UnexpectedToken,
                             format!(
                                 "Expected `LIKE`, `BETWEEN` or `IN` but got: {:?}",
                                 self.get_token(1)?
                             ),
-                        ));
+                        )
+                        .with_expected(&["LIKE", "BETWEEN", "IN"]));
                     }
                 }
                 _ => {
                     return Err(BQ2CSTError::from_token(
                         self.get_token(0)?,
+                        ErrorCode::Internal,
                         "Something went wrong.".to_string(),
                     ))
                 }
@@ -772,6 +1217,42 @@ impl Parser {
         }
         Ok(exprs)
     }
+    /// Parses the arguments of AI and ML functions,
+    /// where `CONNECTION` and `PROMPT` may precede arguments like `MODEL` and `TABLE`
+    /// (e.g. `AI.GENERATE_TABLE(MODEL m, PROMPT (SELECT ...), CONNECTION DEFAULT)`).
+    fn parse_ai_function_args(&mut self) -> BQ2CSTResult<Vec<Node>> {
+        let mut args = Vec::new();
+        loop {
+            // NOTE CONNECTION and PROMPT are not reserved keywords
+            let keyword = self.get_token(0)?.in_(&vec!["CONNECTION", "PROMPT"])
+                && (self.get_token(1)?.in_(&vec!["(", "DEFAULT"])
+                    || self.get_token(1)?.is_string()
+                    || self.get_token(1)?.is_identifier());
+            let mut arg = if keyword {
+                let mut arg = self.construct_node(NodeType::UnaryOperator)?;
+                self.next_token()?; // -> expr | DEFAULT
+                if self.get_token(0)?.is("DEFAULT") {
+                    arg.push_node("right", self.construct_node(NodeType::Keyword)?);
+                } else {
+                    let right = self.parse_expr(usize::MAX, false, false, false)?;
+                    arg.push_node("right", right);
+                }
+                arg
+            } else {
+                self.parse_expr(usize::MAX, false, false, false)?
+            };
+            if self.get_token(1)?.is(",") {
+                self.next_token()?; // expr -> ,
+                arg.push_node("comma", self.construct_node(NodeType::Symbol)?);
+                args.push(arg);
+                self.next_token()?; // , -> expr
+            } else {
+                args.push(arg);
+                break;
+            }
+        }
+        Ok(args)
+    }
     fn parse_grouped_exprs(&mut self, alias: bool) -> BQ2CSTResult<Node> {
         let mut group = self.construct_node(NodeType::GroupedExprs)?;
         if !self.get_token(1)?.is(")") {
@@ -829,7 +1310,7 @@ impl Parser {
             }
             type_declarations.push(type_declaration);
         }
-        if 0 < type_declarations.len() {
+        if !type_declarations.is_empty() {
             group.push_node_vec("declarations", type_declarations);
         }
         group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
@@ -844,27 +1325,27 @@ impl Parser {
             let mut trailing_idents = vec![];
             loop {
                 let curr_token = parser.get_token(0)?;
-                if curr_token.literal.chars().next().unwrap() == '`' {
+                if curr_token.literal.starts_with('`') {
                     break;
                 }
                 let next_token = match parser.get_token(1) {
                     Ok(n) => n,
                     Err(_) => break, // in the case of EOF
                 };
-                if next_token.in_(&vec![",", ".", "(", ")", ";"]) {
+                if next_token.in_(&vec![",", ".", "(", ")", ";"]) || parser.is_eof(1) {
                     break;
                 }
-                if !(curr_token.literal.chars().last().unwrap() == '.')
-                    && !(curr_token.line == next_token.line
-                        && curr_token.column + curr_token.literal.chars().count()
-                            == next_token.column)
+                if !curr_token.literal.ends_with('.')
+                    && (curr_token.line != next_token.line
+                        || curr_token.column + curr_token.literal.chars().count()
+                            != next_token.column)
                 {
                     break;
                 }
                 parser.next_token()?;
                 trailing_idents.push(parser.construct_node(NodeType::Identifier)?);
             }
-            if trailing_idents.len() > 0 {
+            if !trailing_idents.is_empty() {
                 root.node_type = NodeType::MultiTokenIdentifier;
                 root.push_node_vec("trailing_idents", trailing_idents);
             }
@@ -895,7 +1376,10 @@ impl Parser {
             node.push_node("right", unnest);
         } else {
             self.next_token()?; // IN -> (
-            if self.get_token(1)?.in_(&vec!["SELECT", "WITH"]) {
+            if self.is_placeholder(0)? {
+                // e.g. IN @list
+                node.push_node("right", self.construct_node(NodeType::Unknown)?);
+            } else if self.get_token(1)?.in_(&vec!["SELECT", "WITH"]) {
                 let mut lparen = self.construct_node(NodeType::GroupedStatement)?;
                 self.next_token()?; // -> SELECT | WITH
                 lparen.push_node("stmt", self.parse_select_statement(false, true)?);
@@ -930,11 +1414,54 @@ impl Parser {
         keyword.push_node("group", self.parse_grouped_exprs(alias)?);
         Ok(keyword)
     }
+    /// Parses `OPTIONS (key = value, ...)`.
+    /// The structure is the same as `parse_keyword_with_grouped_exprs`
+    /// but any token (e.g. reserved keywords, quoted strings) followed by `=` is a key.
+    fn parse_options(&mut self) -> BQ2CSTResult<Node> {
+        let mut options = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
+        self.next_token()?; // OPTIONS -> (
+        let mut group = self.construct_node(NodeType::GroupedExprs)?;
+        let mut exprs = Vec::new();
+        while !self.get_token(1)?.is(")") {
+            self.next_token()?; // -> key
+            let mut expr = if self.get_token(1)?.is("=") {
+                let key = if self.get_token(0)?.is_string() {
+                    self.construct_node(NodeType::StringLiteral)?
+                } else {
+                    self.construct_node(NodeType::Identifier)?
+                };
+                self.next_token()?; // key -> =
+                let mut operator = self.construct_node(NodeType::BinaryOperator)?;
+                operator.push_node("left", key);
+                self.next_token()?; // = -> value
+                operator.push_node("right", self.parse_expr(usize::MAX, false, false, false)?);
+                operator
+            } else {
+                self.parse_expr(usize::MAX, false, false, false)?
+            };
+            if self.get_token(1)?.is(",") {
+                self.next_token()?; // -> ,
+                expr.push_node("comma", self.construct_node(NodeType::Symbol)?);
+                exprs.push(expr);
+            } else {
+                exprs.push(expr);
+                break;
+            }
+        }
+        self.next_token()?; // -> )
+        group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+        if !exprs.is_empty() {
+            group.push_node_vec("exprs", exprs);
+        }
+        options.push_node("group", group);
+        Ok(options)
+    }
     fn parse_keyword_with_statements(&mut self, until: &Vec<&str>) -> BQ2CSTResult<Node> {
         let mut node = self.construct_node(NodeType::KeywordWithStatements)?;
         let mut stmts = Vec::new();
         while !self.get_token(1)?.in_(until) {
             self.next_token()?; // -> stmt
+            self.check_declare(&stmts, false)?;
             stmts.push(self.parse_statement(true)?);
         }
         node.push_node_vec("stmts", stmts);
@@ -950,54 +1477,59 @@ impl Parser {
         Ok(nodes)
     }
     fn parse_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+        if self.is_cancelled.as_ref().is_some_and(|f| f()) {
+            return Err(BQ2CSTError::from_token(
+                self.get_token(0)?,
+                ErrorCode::Cancelled,
+                "Parsing was cancelled.".to_string(),
+            ));
+        }
+        let mut stmt = self.traced("parse_statement", |p| {
+            p.parse_statement_by_keyword(semicolon)
+        })?;
+        stmt.annotations = annotations(&stmt);
+        Ok(stmt)
+    }
+    fn parse_statement_by_keyword(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+        if self.get_token(0)?.is_hint() {
+            // e.g. @{optimizer_version=1} SELECT 1
+            let mut hint = self.construct_node(NodeType::Hint)?;
+            self.next_token()?; // -> stmt
+            let mut stmt = self.traced("parse_statement", |p| {
+                p.parse_statement_by_keyword(semicolon)
+            })?;
+            move_leading_comments(&mut hint, &mut stmt);
+            stmt.push_node("hint", hint);
+            return Ok(stmt);
+        }
+        if self.get_token(0)?.is("OPTIONS") && self.get_token(1)?.is("(") {
+            // e.g. OPTIONS (query_label = 'team:analytics') SELECT 1
+            let mut options = self.parse_options()?;
+            self.next_token()?; // -> stmt
+            let mut stmt = self.traced("parse_statement", |p| {
+                p.parse_statement_by_keyword(semicolon)
+            })?;
+            move_leading_comments(&mut options, &mut stmt);
+            stmt.push_node("statement_options", options);
+            return Ok(stmt);
+        }
+        if self.get_token(0)?.is_template() || self.is_placeholder(0)? {
+            // e.g. {{ config(materialized='table') }}
+            return self.parse_single_token_statement(semicolon);
+        }
+        // NOTE `?` is applied once after `match` to keep this frame small,
+        // which matters for deeply nested blocks (`BEGIN BEGIN ...`)
         let node = match self.get_token(0)?.literal.to_uppercase().as_str() {
             // SELECT
-            "WITH" | "SELECT" | "(" => self.parse_select_statement(semicolon, true)?,
+            "WITH" | "SELECT" | "(" => self.parse_select_statement(semicolon, true),
             // DML
-            "INSERT" => self.parse_insert_statement(semicolon)?,
-            "DELETE" => self.parse_delete_statement(semicolon)?,
-            "TRUNCATE" => self.parse_truncate_statement(semicolon)?,
-            "UPDATE" => self.parse_update_statement(semicolon)?,
-            "MERGE" => self.parse_merge_statement(semicolon)?,
+            "INSERT" => self.parse_insert_statement(semicolon),
+            "DELETE" => self.parse_delete_statement(semicolon),
+            "TRUNCATE" => self.parse_truncate_statement(semicolon),
+            "UPDATE" => self.parse_update_statement(semicolon),
+            "MERGE" => self.parse_merge_statement(semicolon),
             // DDL
-            "CREATE" => {
-                let mut offset = 1;
-                loop {
-                    match self.get_token(offset)?.literal.to_uppercase().as_str() {
-                        "SCHEMA" => return self.parse_create_schema_statement(semicolon),
-                        "TABLE" => {
-                            if self.get_token(offset + 1)?.literal.to_uppercase().as_str()
-                                == "FUNCTION"
-                            {
-                                return self.parse_create_function_statement(semicolon);
-                            } else {
-                                return self.parse_create_table_statement(semicolon);
-                            }
-                        }
-                        "VIEW" => return self.parse_create_view_statement(semicolon),
-                        "FUNCTION" => return self.parse_create_function_statement(semicolon),
-                        "PROCEDURE" => return self.parse_create_procedure_statement(semicolon),
-                        "ROW" => return self.parse_create_row_access_policy_statement(semicolon),
-                        "CAPACITY" | "RESERVATION" | "ASSIGNMENT" => {
-                            return self.parse_create_reservation_statement(semicolon)
-                        }
-                        "SEARCH" | "VECTOR" => {
-                            return self.parse_create_search_index_statement(semicolon)
-                        }
-                        "MODEL" => return self.parse_create_model_statement(semicolon),
-                        _ => {
-                            offset += 1;
-                            if 5 < offset {
-                                break;
-                            }
-                        }
-                    }
-                }
-                return Err(BQ2CSTError::from_token(
-                    self.get_token(0)?,
-                    format!("Expected `SCHEMA`, `TABLE`, `VIEW`, `FUNCTION`, `PROCEDURE`, 'CAPACITY', 'RESERVATION' or 'ASSIGNMENT' but not found around here: {:?}", self.get_token(0)?)
-                ));
-            }
+            "CREATE" => self.parse_create_statement(semicolon),
             "ALTER" => {
                 let mut offset = 1;
                 loop {
@@ -1025,62 +1557,127 @@ impl Parser {
                 }
                 return Err(BQ2CSTError::from_token(
                     self.get_token(0)?,
+                    ErrorCode::UnknownStatement,
                     format!(
                         "Expected `SCHEMA`, `TABLE` or `VIEW` but not found around here: {:?}",
                         self.get_token(0)?
                     ),
-                ));
+                )
+                .with_expected(&[
+                    "SCHEMA",
+                    "TABLE",
+                    "COLUMN",
+                    "VIEW",
+                    "ORGANIZATION",
+                    "PROJECT",
+                    "BI_CAPACITY",
+                    "CAPACITY",
+                    "RESERVATION",
+                    "MODEL",
+                ]));
             }
             "DROP" => {
                 if self.get_token(1)?.in_(&vec!["ALL", "ROW"]) {
-                    self.parse_drop_row_access_policy_statement(semicolon)?
+                    self.parse_drop_row_access_policy_statement(semicolon)
                 } else {
-                    self.parse_drop_statement_general(semicolon)?
+                    self.parse_drop_statement_general(semicolon)
                 }
             }
-            "UNDROP" => self.parse_undrop_statement(semicolon)?,
+            "UNDROP" => self.parse_undrop_statement(semicolon),
             // DCL
-            "GRANT" => self.parse_grant_statement(semicolon)?,
-            "REVOKE" => self.parse_revoke_statement(semicolon)?,
+            "GRANT" => self.parse_grant_statement(semicolon),
+            "REVOKE" => self.parse_revoke_statement(semicolon),
             // script
-            "DECLARE" => self.parse_declare_statement(semicolon)?,
-            "SET" => self.parse_set_statement(semicolon)?,
-            "EXECUTE" => self.parse_execute_statement(semicolon)?,
-            "IF" => self.parse_if_statement(semicolon)?,
+            "DECLARE" => self.parse_declare_statement(semicolon),
+            "SET" => self.parse_set_statement(semicolon),
+            "EXECUTE" => self.parse_execute_statement(semicolon),
+            "IF" => self.parse_if_statement(semicolon),
             "BEGIN" => {
                 if self.get_token(1)?.in_(&vec!["TRANSACTION", ";"]) || self.is_eof(1) {
-                    return Ok(self.parse_transaction_statement(semicolon)?);
+                    return self.parse_transaction_statement(semicolon);
                 }
-                self.parse_begin_statement(semicolon)?
+                self.parse_begin_statement(semicolon)
             }
-            "CASE" => self.parse_case_statement(semicolon)?,
-            "LOAD" => self.parse_load_statement(semicolon)?,
-            "LOOP" => self.parse_loop_statement(semicolon)?,
-            "REPEAT" => self.parse_repeat_statement(semicolon)?,
-            "WHILE" => self.parse_while_statement(semicolon)?,
+            "CASE" => self.parse_case_statement(semicolon),
+            "LOAD" => self.parse_load_statement(semicolon),
+            "LOOP" => self.parse_loop_statement(semicolon),
+            "REPEAT" => self.parse_repeat_statement(semicolon),
+            "WHILE" => self.parse_while_statement(semicolon),
             "BREAK" | "LEAVE" | "CONTINUE" | "ITERATE" => {
-                self.parse_break_continue_statement(semicolon)?
+                self.parse_break_continue_statement(semicolon)
             }
-            "FOR" => self.parse_for_statement(semicolon)?,
-            "COMMIT" | "ROLLBACK" => self.parse_transaction_statement(semicolon)?,
-            "RAISE" => self.parse_raise_statement(semicolon)?,
-            "RETURN" => self.parse_single_token_statement(semicolon)?,
-            "CALL" => self.parse_call_statement(semicolon)?,
+            "FOR" => self.parse_for_statement(semicolon),
+            "COMMIT" | "ROLLBACK" => self.parse_transaction_statement(semicolon),
+            "RAISE" => self.parse_raise_statement(semicolon),
+            "RETURN" => self.parse_single_token_statement(semicolon),
+            "CALL" => self.parse_call_statement(semicolon),
             // DEBUG
-            "ASSERT" => self.parse_assert_satement(semicolon)?,
+            "ASSERT" => self.parse_assert_satement(semicolon),
             // other
             "EXPORT" => {
                 if self.get_token(1)?.is("DATA") {
-                    self.parse_export_data_statement(semicolon)?
+                    self.parse_export_data_statement(semicolon)
                 } else {
-                    self.parse_export_model_statement(semicolon)?
+                    self.parse_export_model_statement(semicolon)
                 }
             }
-            _ => self.parse_labeled_statement(semicolon)?,
-        };
+            _ => self.parse_labeled_statement(semicolon),
+        }?;
         Ok(node)
     }
+    fn parse_create_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+        let mut offset = 1;
+        loop {
+            match self.get_token(offset)?.literal.to_uppercase().as_str() {
+                "SCHEMA" => return self.parse_create_schema_statement(semicolon),
+                "TABLE" => {
+                    if self.get_token(offset + 1)?.literal.to_uppercase().as_str() == "FUNCTION" {
+                        return self.parse_create_function_statement(semicolon);
+                    } else {
+                        return self.parse_create_table_statement(semicolon);
+                    }
+                }
+                "VIEW" => return self.parse_create_view_statement(semicolon),
+                "FUNCTION" => return self.parse_create_function_statement(semicolon),
+                "PROCEDURE" => return self.parse_create_procedure_statement(semicolon),
+                "ROW" => return self.parse_create_row_access_policy_statement(semicolon),
+                "CAPACITY" | "RESERVATION" | "ASSIGNMENT" => {
+                    return self.parse_create_reservation_statement(semicolon)
+                }
+                "SEARCH" | "VECTOR" => return self.parse_create_search_index_statement(semicolon),
+                "MODEL" => return self.parse_create_model_statement(semicolon),
+                _ => {
+                    offset += 1;
+                    if 5 < offset {
+                        break;
+                    }
+                }
+            }
+        }
+        Err(BQ2CSTError::from_token(
+            self.get_token(0)?,
+            ErrorCode::UnknownStatement,
+            format!("Expected `SCHEMA`, `TABLE`, `VIEW`, `FUNCTION`, `PROCEDURE`, 'CAPACITY', 'RESERVATION' or 'ASSIGNMENT' but not found around here: {:?}", self.get_token(0)?)
+        )
+        .with_expected(&[
+            "SCHEMA",
+            "TABLE",
+            "VIEW",
+            "FUNCTION",
+            "PROCEDURE",
+            "ROW",
+            "CAPACITY",
+            "RESERVATION",
+            "ASSIGNMENT",
+            "SEARCH",
+            "VECTOR",
+            "MODEL",
+        ]))
+    }
     fn parse_table(&mut self, root: bool) -> BQ2CSTResult<Node> {
+        self.traced("parse_table", |p| p.parse_table_untraced(root))
+    }
+    fn parse_table_untraced(&mut self, root: bool) -> BQ2CSTResult<Node> {
         let mut left: Node;
         match self.get_token(0)?.literal.to_uppercase().as_str() {
             "(" => {
@@ -1132,6 +1729,7 @@ impl Parser {
                 left.node_type = NodeType::CallingUnnest;
             }
             _ => {
+                self.check_legacy_table()?;
                 // tvf or identifier
                 left = self.parse_expr(usize::MAX, false, true, false)?;
             }
@@ -1139,9 +1737,16 @@ impl Parser {
         if left.node_type == NodeType::CallingFunction {
             left.node_type = NodeType::CallingTableFunction; // EXTERNAL_QUERY() is included
         }
+        // e.g. t @{FORCE_INDEX=idx}
+        if self.get_token(1)?.is_hint() {
+            self.next_token()?; // -> hint
+            left.push_node("hint", self.construct_node(NodeType::Hint)?);
+        }
         // alias
-        // NOTE PIVOT and UNPIVOT are not reserved keywords
-        if !(self.get_token(1)?.in_(&vec!["PIVOT", "UNPIVOT"])
+        // NOTE PIVOT, UNPIVOT and MATCH_RECOGNIZE are not reserved keywords
+        if !(self
+            .get_token(1)?
+            .in_(&vec!["PIVOT", "UNPIVOT", "MATCH_RECOGNIZE"])
             && self.get_token(2)?.in_(&vec!["(", "INCLUDE", "EXCLUDE"]))
         {
             left = self.push_trailing_alias(left)?;
@@ -1181,6 +1786,7 @@ impl Parser {
         }
         // PIVOT, UNPIVOT
         if self.get_token(1)?.is("PIVOT") {
+            self.check_feature(SyntaxFeature::Pivot, 1)?;
             self.next_token()?; // -> PIVOT
             let mut pivot = self.construct_node(NodeType::PivotOperator)?;
             self.next_token()?; // -> (
@@ -1200,6 +1806,7 @@ impl Parser {
             pivot = self.push_trailing_alias(pivot)?;
             left.push_node("pivot", pivot);
         } else if self.get_token(1)?.is("UNPIVOT") {
+            self.check_feature(SyntaxFeature::Unpivot, 1)?;
             self.next_token()?; // -> UNPIVOT
             let mut unpivot = self.construct_node(NodeType::UnpivotOperator)?;
             if self.get_token(1)?.in_(&vec!["INCLUDE", "EXCLUDE"]) {
@@ -1265,9 +1872,18 @@ impl Parser {
             unpivot = self.push_trailing_alias(unpivot)?;
             left.push_node("unpivot", unpivot);
         }
+        // MATCH_RECOGNIZE
+        if self.get_token(1)?.is("MATCH_RECOGNIZE") && self.get_token(2)?.is("(") {
+            self.check_feature(SyntaxFeature::MatchRecognize, 1)?;
+            self.next_token()?; // -> MATCH_RECOGNIZE
+            let mut match_recognize = self.parse_match_recognize_clause()?;
+            match_recognize = self.push_trailing_alias(match_recognize)?;
+            left.push_node("match_recognize", match_recognize);
+        }
         // TABLESAMPLE
         if self.get_token(1)?.is("tablesample") {
             // TODO check when it becomes GA
+            self.check_feature(SyntaxFeature::Tablesample, 1)?;
             self.next_token()?; // -> TABLESAMPLE
             let mut tablesample = self.construct_node(NodeType::TableSampleClause)?;
             self.next_token()?; // -> SYSTEM
@@ -1284,14 +1900,130 @@ impl Parser {
             left.push_node("tablesample", tablesample);
         }
         // JOIN
-        while self.get_token(1)?.in_(&vec![
+        if root {
+            left = self.parse_joins(left)?;
+        }
+        Ok(left)
+    }
+    fn parse_match_recognize_clause(&mut self) -> BQ2CSTResult<Node> {
+        let mut match_recognize = self.construct_node(NodeType::MatchRecognizeClause)?;
+        self.next_token()?; // -> (
+        let mut config = self.construct_node(NodeType::MatchRecognizeConfig)?;
+        if self.get_token(1)?.is("PARTITION") {
+            self.next_token()?; // -> PARTITION
+            config.push_node("partitionby", self.parse_xxxby_exprs()?);
+        }
+        if self.get_token(1)?.is("ORDER") {
+            self.next_token()?; // -> ORDER
+            config.push_node("orderby", self.parse_xxxby_exprs()?);
+        }
+        if self.get_token(1)?.is("MEASURES") {
+            self.next_token()?; // -> MEASURES
+            let mut measures = self.construct_node(NodeType::KeywordWithExprs)?;
+            self.next_token()?; // -> expr
+            measures.push_node_vec("exprs", self.parse_exprs(&vec![], true)?);
+            config.push_node("measures", measures);
+        }
+        // AFTER MATCH SKIP {PAST LAST ROW | TO NEXT ROW}
+        if self.get_token(1)?.is("AFTER") {
+            self.next_token()?; // -> AFTER
+            config.push_node_vec("after_match_skip", self.parse_n_keywords(6)?);
+        }
+        self.next_token()?; // -> PATTERN
+        let mut pattern = self.construct_node(NodeType::KeywordWithGroupedXXX)?;
+        self.next_token()?; // -> (
+        pattern.push_node("group", self.parse_grouped_pattern()?);
+        config.push_node("pattern", pattern);
+        self.next_token()?; // -> DEFINE
+        let mut define = self.construct_node(NodeType::KeywordWithExprs)?;
+        let mut definitions = Vec::new();
+        loop {
+            self.next_token()?; // -> symbol
+            let mut definition = self.construct_node(NodeType::PatternDefinition)?;
+            self.next_token()?; // -> AS
+            definition.push_node("as", self.construct_node(NodeType::Keyword)?);
+            self.next_token()?; // -> expr
+            definition.push_node("expr", self.parse_expr(usize::MAX, false, false, false)?);
+            if self.get_token(1)?.is(",") {
+                self.next_token()?; // -> ,
+                definition.push_node("comma", self.construct_node(NodeType::Symbol)?);
+                definitions.push(definition);
+            } else {
+                definitions.push(definition);
+                break;
+            }
+        }
+        define.push_node_vec("exprs", definitions);
+        config.push_node("define", define);
+        if self.get_token(1)?.is("OPTIONS") {
+            self.next_token()?; // -> OPTIONS
+            config.push_node("options", self.parse_options()?);
+        }
+        self.next_token()?; // -> )
+        config.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+        match_recognize.push_node("config", config);
+        Ok(match_recognize)
+    }
+    /// Parses a row pattern (e.g. `(a b+ (c | d){2,} $)`) token by token.
+    /// Only parentheses are structured, quantifiers and operators are kept as `Symbol`.
+    fn parse_grouped_pattern(&mut self) -> BQ2CSTResult<Node> {
+        let mut group = self.construct_node(NodeType::GroupedPattern)?;
+        let mut patterns = Vec::new();
+        while !self.get_token(1)?.is(")") {
+            self.next_token()?; // -> pattern
+            if self.get_token(0)?.is("(") {
+                patterns.push(self.parse_grouped_pattern()?);
+            } else if self.get_token(0)?.is_identifier() {
+                patterns.push(self.construct_node(NodeType::Identifier)?);
+            } else {
+                patterns.push(self.construct_node(NodeType::Symbol)?);
+            }
+        }
+        self.next_token()?; // -> )
+        group.push_node_vec("patterns", patterns);
+        group.push_node("rparen", self.construct_node(NodeType::Symbol)?);
+        Ok(group)
+    }
+    fn is_join(&self, offset: usize) -> BQ2CSTResult<bool> {
+        Ok(self.get_token(offset)?.in_(&vec![
             "left", "right", "cross", "inner", "full", "join", ",",
-        ]) && root
-        {
+        ]))
+    }
+    /// Returns `true` if the following `ON` and `USING` outnumber the following conditional joins
+    /// (at the same depth of parentheses, up to the end of FROM clause),
+    /// which means the condition of the current join is placed after those of the next joins.
+    fn has_deferred_condition(&self) -> bool {
+        let mut depth = 0;
+        let mut joins = 0;
+        let mut conditions = 0;
+        let mut prev: Option<&Token> = None;
+        for token in self.tokens[self.position + 1..].iter() {
+            if token.is_comment() {
+                continue;
+            }
+            match token.literal.to_uppercase().as_str() {
+                "(" | "[" => depth += 1,
+                ")" | "]" if depth == 0 => break,
+                ")" | "]" => depth -= 1,
+                "JOIN" if depth == 0 && !prev.is_some_and(|t| t.is("CROSS")) => joins += 1,
+                "ON" | "USING" if depth == 0 => conditions += 1,
+                "" | ";" | "WHERE" | "GROUP" | "HAVING" | "QUALIFY" | "WINDOW" | "ORDER"
+                | "LIMIT" | "UNION" | "INTERSECT" | "EXCEPT" | "WHEN" | "SET"
+                    if depth == 0 =>
+                {
+                    break
+                }
+                _ => (),
+            }
+            prev = Some(token);
+        }
+        joins < conditions
+    }
+    fn parse_joins(&mut self, mut left: Node) -> BQ2CSTResult<Node> {
+        while self.is_join(1)? {
             self.next_token()?; // table -> LEFT, RIGHT, INNER, CROSS, FULL, JOIN, ","
             let mut join = if self.get_token(0)?.in_(&vec!["join", ","]) {
-                let join = self.construct_node(NodeType::JoinOperator)?;
-                join
+                self.construct_node(NodeType::JoinOperator)?
             } else {
                 let type_ = self.construct_node(NodeType::Keyword)?;
                 self.next_token()?; // join_type -> OUTER, JOIN
@@ -1308,8 +2040,23 @@ impl Parser {
                     join
                 }
             };
+            // e.g. JOIN @{JOIN_METHOD=HASH_JOIN} t
+            if self.get_token(1)?.is_hint() {
+                self.next_token()?; // JOIN -> hint
+                join.push_node("hint", self.construct_node(NodeType::Hint)?);
+            }
+            let conditional = !join.token.as_ref().is_some_and(|t| t.is(","))
+                && !matches!(
+                    join.children.get("join_type"),
+                    Some(ContentType::Node(n)) if n.token.as_ref().is_some_and(|t| t.is("CROSS"))
+                );
             self.next_token()?; // -> table
-            let right = self.parse_table(false)?;
+            let mut right = self.parse_table(false)?;
+            // the condition of this join may follow the ones of the next joins
+            // e.g. `a JOIN b JOIN c ON b.x = c.x ON a.x = b.x`
+            if conditional && self.is_join(1)? && self.has_deferred_condition() {
+                right = self.parse_joins(right)?;
+            }
             if self.get_token(1)?.is("on") {
                 self.next_token()?; // `table` -> ON
                 let mut on = self.construct_node(NodeType::KeywordWithExpr)?;
@@ -1318,7 +2065,7 @@ impl Parser {
                 join.push_node("on", on);
             } else if self.get_token(1)?.is("using") {
                 self.next_token()?; // -> USING
-                join.push_node("using", self.parse_expr(usize::MAX, false, false, false)?)
+                join.push_node("using", self.parse_keyword_with_grouped_exprs(false)?)
             }
             join.push_node("left", left);
             join.push_node("right", right);
@@ -1336,6 +2083,7 @@ impl Parser {
                     self.next_token()?; // < -> type
                     type_.push_node("type", self.parse_type(schema, false)?);
                     self.next_token()?; // type -> >
+                    self.split_closing_angle_bracket();
                     type_.push_node("rparen", self.construct_node(NodeType::Symbol)?);
                     res.push_node("type_declaration", type_);
                 }
@@ -1349,9 +2097,13 @@ impl Parser {
                         self.construct_node(NodeType::GroupedTypeDeclarationOrConstraints)?;
                     self.next_token()?; // < -> type or ident
                     let mut type_declarations = Vec::new();
-                    while !self.get_token(0)?.is(">") {
+                    loop {
+                        self.split_closing_angle_bracket();
+                        if self.get_token(0)?.is(">") {
+                            break;
+                        }
                         let mut type_declaration;
-                        if !self.get_token(1)?.in_(&vec![",", ">", "TYPE", "<"]) {
+                        if !self.get_token(1)?.in_(&vec![",", ">", ">>", "TYPE", "<"]) {
                             // `is_identifier` is not availabe here,
                             // because `int64` is valid identifier
                             type_declaration = self.construct_node(NodeType::TypeDeclaration)?;
@@ -1377,6 +2129,14 @@ impl Parser {
             "ANY" => {
                 let mut res = self.construct_node(NodeType::Type)?;
                 self.next_token()?; // ANY -> TYPE
+                if !self.get_token(0)?.is("TYPE") {
+                    return Err(BQ2CSTError::from_token(
+                        self.get_token(0)?,
+                        ErrorCode::UnexpectedToken,
+                        "Expected TYPE.".to_string(),
+                    )
+                    .with_expected(&["TYPE"]));
+                }
                 res.push_node("type", self.construct_node(NodeType::Keyword)?);
                 res
             }
@@ -1449,19 +2209,17 @@ impl Parser {
             }
             if self.get_token(1)?.is("OPTIONS") {
                 self.next_token()?; // -> OPTIONS
-                let options = self.parse_keyword_with_grouped_exprs(false)?;
+                let options = self.parse_options()?;
                 res.push_node("options", options);
             }
         }
-        if aggregate {
-            if self.get_token(1)?.is("NOT") {
-                self.next_token()?; // -> NOT
-                let mut not_ = self.construct_node(NodeType::KeywordSequence)?;
-                self.next_token()?; // -> AGGREGATE
-                let null = self.construct_node(NodeType::Keyword)?;
-                not_.push_node("next_keyword", null);
-                res.push_node("aggregate", not_);
-            }
+        if aggregate && self.get_token(1)?.is("NOT") {
+            self.next_token()?; // -> NOT
+            let mut not_ = self.construct_node(NodeType::KeywordSequence)?;
+            self.next_token()?; // -> AGGREGATE
+            let null = self.construct_node(NodeType::Keyword)?;
+            not_.push_node("next_keyword", null);
+            res.push_node("aggregate", not_);
         }
         Ok(res)
     }
@@ -1549,11 +2307,19 @@ impl Parser {
         let mut with = self.construct_node(NodeType::KeywordSequence)?;
         self.next_token()?; // -> CONNECTION
         let mut connection = self.construct_node(NodeType::KeywordWithExpr)?;
-        self.next_token()?; // -> ident
-        connection.push_node("expr", self.parse_identifier()?);
+        self.next_token()?; // -> ident | DEFAULT
+        connection.push_node("expr", self.parse_connection_name()?);
         with.push_node("next_keyword", connection);
         Ok(with)
     }
+    /// `DEFAULT` means the default connection of the project.
+    fn parse_connection_name(&mut self) -> BQ2CSTResult<Node> {
+        if self.get_token(0)?.is("DEFAULT") {
+            self.construct_node(NodeType::Keyword)
+        } else {
+            self.parse_identifier()
+        }
+    }
     fn parse_xxxby_exprs(&mut self) -> BQ2CSTResult<Node> {
         let mut xxxby = self.construct_node(NodeType::XXXByExprs)?;
         self.next_token()?; // xxx -> BY
@@ -1576,6 +2342,15 @@ impl Parser {
     }
     // ----- SELECT statement -----
     fn parse_select_statement(&mut self, semicolon: bool, root: bool) -> BQ2CSTResult<Node> {
+        self.traced("parse_select_statement", |p| {
+            p.parse_select_statement_untraced(semicolon, root)
+        })
+    }
+    fn parse_select_statement_untraced(
+        &mut self,
+        semicolon: bool,
+        root: bool,
+    ) -> BQ2CSTResult<Node> {
         if self.get_token(0)?.literal.to_uppercase() == "(" {
             let mut node = self.construct_node(NodeType::GroupedStatement)?;
             self.next_token()?; // ( -> SELECT
@@ -1584,48 +2359,18 @@ impl Parser {
             if !self.get_token(0)?.is(")") {
                 return Err(BQ2CSTError::from_token(
                     self.get_token(0)?,
+                    ErrorCode::UnexpectedToken,
                     "expected )".to_string(),
-                ));
+                )
+                .with_expected(&[")"]));
             }
             node.push_node("rparen", self.construct_node(NodeType::Symbol)?);
-            while self
-                .get_token(1)?
-                .in_(&vec!["UNION", "INTERSECT", "EXCEPT"])
-                && root
-            {
-                self.next_token()?; // stmt -> UNION
-                let mut operator = self.construct_node(NodeType::SetOperator)?;
-                self.next_token()?; // UNION -> DISTINCT
-                operator.push_node("distinct_or_all", self.construct_node(NodeType::Keyword)?);
-                operator.push_node("left", node);
-                self.next_token()?; // DISTINCT -> stmt
-                operator.push_node("right", self.parse_select_statement(false, false)?);
-                node = operator;
-            }
-            // ORDER BY
-            if self.get_token(1)?.is("ORDER") && root {
-                self.next_token()?; // -> ORDER
-                let mut order = self.construct_node(NodeType::XXXByExprs)?;
-                self.next_token()?; // -> BY
-                order.push_node("by", self.construct_node(NodeType::Keyword)?);
-                self.next_token()?; // BY -> expr
-                order.push_node_vec("exprs", self.parse_exprs(&vec![], false)?);
-                node.push_node("orderby", order);
+            if root {
+                node = self.parse_set_operators(node, usize::MAX)?;
             }
-            // LIMIT
-            if self.get_token(1)?.is("LIMIT") && root {
-                self.next_token()?; // -> LIMIT
-                let mut limit = self.construct_node(NodeType::LimitClause)?;
-                self.next_token()?; // -> expr
-                limit.push_node("expr", self.parse_expr(usize::MAX, false, false, false)?);
-                if self.get_token(1)?.literal.to_uppercase() == "OFFSET" {
-                    self.next_token()?; // expr -> OFFSET
-                    let mut offset = self.construct_node(NodeType::KeywordWithExpr)?;
-                    self.next_token()?; // OFFSET -> expr
-                    offset.push_node("expr", self.parse_expr(usize::MAX, false, false, false)?);
-                    limit.push_node("offset", offset);
-                }
-                node.push_node("limit", limit);
+            // ORDER BY, LIMIT of the whole set operation (or the grouped statement)
+            if root {
+                self.parse_orderby_and_limit(&mut node)?;
             }
             if self.get_token(1)?.is(";") && semicolon && root {
                 self.next_token()?; // expr -> ;
@@ -1633,6 +2378,15 @@ impl Parser {
             }
             return Ok(node);
         }
+        if self.is_placeholder(0)? {
+            // e.g. INSERT INTO t {query}
+            let mut node = self.construct_node(NodeType::Unknown)?;
+            if self.get_token(1)?.is(";") && semicolon && root {
+                self.next_token()?; // -> ;
+                node.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
+            }
+            return Ok(node);
+        }
         if self.get_token(0)?.literal.to_uppercase() == "WITH" {
             let mut with = self.construct_node(NodeType::WithClause)?;
             if self.get_token(1)?.is("RECURSIVE") {
@@ -1657,6 +2411,7 @@ impl Parser {
 
         // WITH DIFFERENTIAL_PRIVACY
         if self.get_token(1)?.is("WITH") {
+            self.check_feature(SyntaxFeature::DifferentialPrivacy, 1)?;
             self.next_token()?; // -> WITH
             let mut with = self.construct_node(NodeType::DifferentialPrivacyClause)?;
             self.next_token()?; // -> differential_privacy
@@ -1666,50 +2421,68 @@ impl Parser {
             );
             if self.get_token(1)?.is("OPTIONS") {
                 self.next_token()?; // -> OPTIONS
-                with.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+                with.push_node("options", self.parse_options()?);
             };
             node.push_node("differential_privacy", with);
         }
+        // DISTINCT
+        if self.get_token(1)?.in_(&vec!["ALL", "DISTINCT"]) {
+            self.next_token()?; // select -> all, distinct
+            node.push_node("distinct_or_all", self.construct_node(NodeType::Keyword)?);
+        }
+
         // AS STRUCT, VALUE
         if self.get_token(1)?.literal.to_uppercase() == "AS" {
             self.next_token()?; // SELECT -> AS
             let as_ = self.construct_node(NodeType::Keyword)?;
             self.next_token()?; // AS -> STRUCT, VALUE
+            if !self.get_token(0)?.in_(&vec!["STRUCT", "VALUE"]) {
+                return Err(BQ2CSTError::from_token(
+                    self.get_token(0)?,
+                    ErrorCode::UnexpectedToken,
+                    "Expected STRUCT or VALUE.".to_string(),
+                )
+                .with_expected(&["STRUCT", "VALUE"]));
+            }
             node.push_node_vec(
                 "as_struct_or_value",
                 vec![as_, self.construct_node(NodeType::Keyword)?],
             );
         }
 
-        // DISTINCT
-        if self.get_token(1)?.in_(&vec!["ALL", "DISTINCT"]) {
-            self.next_token()?; // select -> all, distinct
+        // NOTE `SELECT AS STRUCT DISTINCT` is not valid but accepted for compatibility
+        if !node.children.contains_key("distinct_or_all")
+            && self.get_token(1)?.in_(&vec!["ALL", "DISTINCT"])
+        {
+            self.next_token()?; // -> all, distinct
             node.push_node("distinct_or_all", self.construct_node(NodeType::Keyword)?);
         }
         self.next_token()?; // -> expr
 
         // exprs
-        node.push_node_vec(
-            "exprs",
-            self.parse_exprs(
-                &vec![
-                    "FROM",
-                    "WHERE",
-                    "GROUP",
-                    "HAVING",
-                    "QUALIFY",
-                    "WINDOW",
-                    "ORDER",
-                    "LIMIT",
-                    "UNION",
-                    "INTERSECT",
-                    "EXCEPT",
-                    ";",
-                    ")",
-                ],
-                true,
-            )?,
-        );
+        let exprs = self.parse_exprs(
+            &vec![
+                "FROM",
+                "WHERE",
+                "GROUP",
+                "HAVING",
+                "QUALIFY",
+                "WINDOW",
+                "ORDER",
+                "LIMIT",
+                "UNION",
+                "INTERSECT",
+                "EXCEPT",
+                ";",
+                ")",
+            ],
+            true,
+        )?;
+        node.push_node_vec("exprs", exprs);
+        // e.g. `SELECT a INTO x FROM t` (T-SQL)
+        if self.get_token(1)?.is("INTO") {
+            return Err(self.select_into_error(1)?);
+        }
         // FROM
         if self.get_token(1)?.is("FROM") {
             self.next_token()?; // expr -> FROM
@@ -1758,6 +2531,7 @@ impl Parser {
         }
         // QUALIFY
         if self.get_token(1)?.is("QUALIFY") {
+            self.check_feature(SyntaxFeature::Qualify, 1)?;
             self.next_token()?; // -> QUALIFY
             let mut qualify = self.construct_node(NodeType::KeywordWithExpr)?;
             self.next_token()?; // -> expr
@@ -1785,6 +2559,53 @@ impl Parser {
             window.push_node_vec("window_exprs", window_exprs);
             node.push_node("window", window);
         }
+        // ORDER BY, LIMIT
+        // NOTE those after the right side of a set operation belong to the whole set operation.
+        // those before a set operation are not valid but accepted for compatibility
+        if root {
+            self.parse_orderby_and_limit(&mut node)?;
+        }
+        // UNION
+        if root
+            && self
+                .get_token(1)?
+                .in_(&vec!["UNION", "INTERSECT", "EXCEPT"])
+        {
+            node = self.parse_set_operators(node, usize::MAX)?;
+            self.parse_orderby_and_limit(&mut node)?;
+        }
+        // ;
+        if self.get_token(1)?.is(";") && semicolon {
+            self.next_token()?; // expr -> ;
+            node.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
+        }
+        Ok(node)
+    }
+    /// Parses set operators following `left` whose precedence is `precedence` or tighter
+    /// (see `crate::precedence::set_operator_precedence`).
+    /// e.g. `A UNION ALL B INTERSECT DISTINCT C` is `A UNION ALL (B INTERSECT DISTINCT C)`
+    fn parse_set_operators(&mut self, mut left: Node, precedence: usize) -> BQ2CSTResult<Node> {
+        while let Some(curr_precedence) =
+            precedence::set_operator_precedence(&self.get_token(1)?.literal)
+        {
+            if precedence < curr_precedence {
+                break;
+            }
+            self.next_token()?; // stmt -> UNION
+            let mut operator = self.construct_node(NodeType::SetOperator)?;
+            self.next_token()?; // UNION -> DISTINCT
+            operator.push_node("distinct_or_all", self.construct_node(NodeType::Keyword)?);
+            operator.push_node("left", left);
+            self.next_token()?; // DISTINCT -> stmt
+            let right = self.parse_select_statement(false, false)?;
+            // operators which bind more tightly belong to the right side
+            let right = self.parse_set_operators(right, curr_precedence - 1)?;
+            operator.push_node("right", right);
+            left = operator;
+        }
+        Ok(left)
+    }
+    fn parse_orderby_and_limit(&mut self, node: &mut Node) -> BQ2CSTResult<()> {
         // ORDER BY
         if self.get_token(1)?.is("ORDER") {
             self.next_token()?; // expr -> ORDER
@@ -1810,27 +2631,7 @@ impl Parser {
             }
             node.push_node("limit", limit);
         }
-        // UNION
-        while self
-            .get_token(1)?
-            .in_(&vec!["UNION", "INTERSECT", "EXCEPT"])
-            && root
-        {
-            self.next_token()?; // stmt -> UNION
-            let mut operator = self.construct_node(NodeType::SetOperator)?;
-            self.next_token()?; // UNION -> DISTINCT
-            operator.push_node("distinct_or_all", self.construct_node(NodeType::Keyword)?);
-            operator.push_node("left", node);
-            self.next_token()?; // DISTINCT -> stmt
-            operator.push_node("right", self.parse_select_statement(false, false)?);
-            node = operator;
-        }
-        // ;
-        if self.get_token(1)?.is(";") && semicolon {
-            self.next_token()?; // expr -> ;
-            node.push_node("semicolon", self.construct_node(NodeType::Symbol)?)
-        }
-        Ok(node)
+        Ok(())
     }
     // ----- DML -----
     fn parse_insert_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
@@ -1884,7 +2685,7 @@ impl Parser {
         }
         self.next_token()?; // -> table_name
         let mut table_name = self.parse_identifier()?;
-        if !self.get_token(1)?.is("WHERE") {
+        if self.get_token(1)?.is("AS") || self.get_token(1)?.is_identifier() {
             self.next_token()?; // -> AS, ident
             if self.get_token(0)?.is("AS") {
                 table_name.push_node("as", self.construct_node(NodeType::Keyword)?);
@@ -1893,11 +2694,15 @@ impl Parser {
             table_name.push_node("alias", self.construct_node(NodeType::Identifier)?);
         }
         delete.push_node("table_name", table_name);
-        self.next_token()?; // -> WHERE
-        let mut where_ = self.construct_node(NodeType::KeywordWithExpr)?;
-        self.next_token()?; // WHERE -> expr
-        where_.push_node("expr", self.parse_expr(usize::MAX, false, false, false)?);
-        delete.push_node("where", where_);
+        // NOTE WHERE is required in BigQuery
+        // but accepted here so that `analysis::dangerous_statements` can find it
+        if self.get_token(1)?.is("WHERE") {
+            self.next_token()?; // -> WHERE
+            let mut where_ = self.construct_node(NodeType::KeywordWithExpr)?;
+            self.next_token()?; // WHERE -> expr
+            where_.push_node("expr", self.parse_expr(usize::MAX, false, false, false)?);
+            delete.push_node("where", where_);
+        }
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
             delete.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
@@ -2004,11 +2809,13 @@ impl Parser {
                 _ => {
                     return Err(BQ2CSTError::from_token(
                         self.get_token(0)?,
+                        ErrorCode::UnexpectedToken,
                         format!(
                             "Expected `DELETE`, `UPDATE` or `INSERT` but got: {:?}",
                             self.get_token(0)?
                         ),
-                    ))
+                    )
+                    .with_expected(&["DELETE", "UPDATE", "INSERT"]))
                 }
             };
             then.push_node("stmt", stmt);
@@ -2025,6 +2832,12 @@ impl Parser {
     // ----- DDL -----
     fn parse_create_schema_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
         let mut create = self.construct_node(NodeType::CreateSchemaStatement)?;
+        // NOTE actually, OR REPLACE is not allowed in CREATE SCHEMA statement
+        // but it is allowed here for consistency with other CREATE statements
+        if self.get_token(1)?.is("OR") {
+            self.next_token()?; // -> OR
+            create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
+        }
         if self.get_token(1)?.is("EXTERNAL") {
             self.next_token()?; // -> EXTERNAL
             create.push_node("external", self.construct_node(NodeType::Keyword)?);
@@ -2057,7 +2870,7 @@ impl Parser {
         }
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // OPTIONS
-            create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            create.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
@@ -2107,7 +2920,7 @@ impl Parser {
         }
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            create.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
@@ -2184,7 +2997,8 @@ impl Parser {
             self.next_token()?; // -> CLUSTER
             create.push_node("clusterby", self.parse_xxxby_exprs()?);
         }
-        if self.get_token(1)?.is("WITH") && self.get_token(2)?.is("CONNECTION") && external {
+        // e.g. BigLake tables for Apache Iceberg
+        if self.get_token(1)?.is("WITH") && self.get_token(2)?.is("CONNECTION") {
             self.next_token()?; // -> WITH
             create.push_node("with_connection", self.parse_with_connection_clause()?);
         }
@@ -2211,7 +3025,7 @@ impl Parser {
         }
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            create.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is("AS") {
             self.next_token()?; // -> AS
@@ -2263,7 +3077,7 @@ impl Parser {
                 ident.node_type = NodeType::IdentWithOptions;
                 if self.get_token(1)?.is("OPTIONS") {
                     self.next_token()?; // -> OPTIONS
-                    ident.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+                    ident.push_node("options", self.parse_options()?);
                 }
                 if self.get_token(1)?.is(",") {
                     self.next_token()?; // -> ,
@@ -2284,7 +3098,7 @@ impl Parser {
         }
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            create.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is("AS") {
             self.next_token()?; // -> AS
@@ -2360,14 +3174,14 @@ impl Parser {
             let mut with = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> CONNECTION
             let mut connection = self.construct_node(NodeType::KeywordWithExpr)?;
-            self.next_token()?; // -> ident
-            connection.push_node("expr", self.parse_identifier()?);
+            self.next_token()?; // -> ident | DEFAULT
+            connection.push_node("expr", self.parse_connection_name()?);
             with.push_node("next_keyword", connection);
             remote.push_node("next_keyword", with);
             node.push_node("remote", remote);
             if self.get_token(1)?.is("OPTIONS") {
                 self.next_token()?; // -> OPTIONS
-                node.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+                node.push_node("options", self.parse_options()?);
             }
         } else if self.get_token(1)?.is("AS") {
             // sql function definition
@@ -2403,9 +3217,14 @@ impl Parser {
             self.next_token()?; // -> js
             language.push_node("expr", self.construct_node(NodeType::Identifier)?);
             node.push_node("language", language);
+            // python function definition
+            if self.get_token(1)?.is("WITH") {
+                self.next_token()?; // -> WITH
+                node.push_node("with_connection", self.parse_with_connection_clause()?);
+            }
             if self.get_token(1)?.is("OPTIONS") {
                 self.next_token()?; // -> OPTIONS
-                node.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+                node.push_node("options", self.parse_options()?);
             }
             self.next_token()?; // -> AS
             let mut as_ = self.construct_node(NodeType::KeywordWithExpr)?;
@@ -2455,7 +3274,7 @@ impl Parser {
         }
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            create.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is("LANGUAGE") {
             self.next_token()?; // -> LANGUAGE
@@ -2567,15 +3386,15 @@ impl Parser {
             let mut with = self.construct_node(NodeType::KeywordSequence)?;
             self.next_token()?; // -> CONNECTION
             let mut connection = self.construct_node(NodeType::KeywordWithExpr)?;
-            self.next_token()?; // -> ident
-            connection.push_node("expr", self.parse_identifier()?);
+            self.next_token()?; // -> ident | DEFAULT
+            connection.push_node("expr", self.parse_connection_name()?);
             with.push_node("next_keyword", connection);
             remote.push_node("next_keyword", with);
             create.push_node("remote", remote);
         }
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            create.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is("AS") {
             self.next_token()?; // -> AS
@@ -2632,7 +3451,7 @@ impl Parser {
         }
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            alter.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
@@ -2656,7 +3475,7 @@ impl Parser {
                 alter.push_node("set", self.construct_node(NodeType::Keyword)?);
                 if self.get_token(1)?.is("OPTIONS") {
                     self.next_token()?; // -> OPTIONS
-                    alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+                    alter.push_node("options", self.parse_options()?);
                 } else if self.get_token(1)?.is("DEFAULT") {
                     self.next_token()?; // DEFAULT
                     let mut default = self.construct_node(NodeType::KeywordSequence)?;
@@ -2797,11 +3616,13 @@ impl Parser {
             _ => {
                 return Err(BQ2CSTError::from_token(
                     self.get_token(1)?,
+                    ErrorCode::UnexpectedToken,
                     format!(
                         "Expected `SET`, `ADD` `RENAME` or `DROP` but got: {:?}",
                         self.get_token(1)?
                     ),
-                ))
+                )
+                .with_expected(&["SET", "ADD", "RENAME", "DROP", "ALTER"]))
             }
         }
         if self.get_token(1)?.is(";") && semicolon {
@@ -2826,7 +3647,7 @@ impl Parser {
                 alter.push_node("set", self.construct_node(NodeType::Keyword)?);
                 if self.get_token(1)?.is("OPTIONS") {
                     self.next_token()?; // -> OPTIONS
-                    alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+                    alter.push_node("options", self.parse_options()?);
                 } else if self.get_token(1)?.is("DATA") {
                     self.next_token()?; // -> DATA
                     alter.push_node_vec("data_type", self.parse_n_keywords(2)?);
@@ -2850,11 +3671,13 @@ impl Parser {
             _ => {
                 return Err(BQ2CSTError::from_token(
                     self.get_token(0)?,
+                    ErrorCode::UnexpectedToken,
                     format!(
                         "Expected `SET` or `DROP` but got : {:?}",
                         self.get_token(0)?
                     ),
-                ))
+                )
+                .with_expected(&["SET", "DROP"]))
             }
         }
         if self.get_token(1)?.is(";") && semicolon {
@@ -2885,7 +3708,7 @@ impl Parser {
             self.next_token()?; // -> SET
             alter.push_node("set", self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // -> OPTIONS
-            alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            alter.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
@@ -2900,7 +3723,7 @@ impl Parser {
         self.next_token()?; // -> SET
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
-        alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+        alter.push_node("options", self.parse_options()?);
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
@@ -2918,7 +3741,7 @@ impl Parser {
         self.next_token()?; // -> SET
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
-        alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+        alter.push_node("options", self.parse_options()?);
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
@@ -2934,7 +3757,7 @@ impl Parser {
         self.next_token()?; // -> SET
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
-        alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+        alter.push_node("options", self.parse_options()?);
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
@@ -2950,7 +3773,7 @@ impl Parser {
         self.next_token()?; // -> SET
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
-        alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+        alter.push_node("options", self.parse_options()?);
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
@@ -2970,7 +3793,7 @@ impl Parser {
         self.next_token()?; // -> SET
         alter.push_node("set", self.construct_node(NodeType::Keyword)?);
         self.next_token()?; // -> OPTIONS
-        alter.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+        alter.push_node("options", self.parse_options()?);
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
             alter.push_node("semicolon", self.construct_node(NodeType::Symbol)?);
@@ -3012,6 +3835,9 @@ impl Parser {
         } else if self.get_token(1)?.is("MATERIALIZED") {
             self.next_token()?; // -> MATERIALIZED
             drop.push_node("materialized", self.construct_node(NodeType::Keyword)?);
+        } else if self.get_token(1)?.is("SNAPSHOT") {
+            self.next_token()?; // -> SNAPSHOT
+            drop.push_node("snapshot", self.construct_node(NodeType::Keyword)?);
         } else if self.get_token(1)?.is("TABLE") && self.get_token(2)?.is("FUNCTION") {
             self.next_token()?; // -> TABLE
             drop.push_node("table", self.construct_node(NodeType::Keyword)?)
@@ -3112,8 +3938,17 @@ impl Parser {
     }
     fn parse_create_reservation_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
         let mut create = self.construct_node(NodeType::CreateReservationStatement)?;
+        // NOTE OR REPLACE and IF NOT EXISTS are allowed for consistency with other CREATE statements
+        if self.get_token(1)?.is("OR") {
+            self.next_token()?; // -> OR
+            create.push_node_vec("or_replace", self.parse_n_keywords(2)?);
+        }
         self.next_token()?; // -> CAPACITY | RESERVATION | ASSIGNMENT
         create.push_node("what", self.construct_node(NodeType::Keyword)?);
+        if self.get_token(1)?.is("IF") {
+            self.next_token()?; // -> IF
+            create.push_node_vec("if_not_exists", self.parse_n_keywords(3)?);
+        }
         self.next_token()?; // -> ident
         create.push_node("ident", self.parse_identifier()?);
         if self.get_token(1)?.is("AS") {
@@ -3129,7 +3964,7 @@ impl Parser {
             );
         } else if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            create.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            create.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // ;
@@ -3138,6 +3973,27 @@ impl Parser {
         Ok(create)
     }
     // ----- script -----
+    /// Returns an error if the current token is `DECLARE`
+    /// but it does not come at the beginning of a script or `BEGIN ... END` block.
+    /// `stmts` are the preceding statements in the same script or block
+    /// and `block` is `false` in the body of e.g. `IF`, `LOOP`, `WHILE`.
+    fn check_declare(&self, stmts: &[Node], block: bool) -> BQ2CSTResult<()> {
+        let token = self.get_token(0)?;
+        if !self.options.strict_declare
+            || !token.is("DECLARE")
+            || block
+                && stmts
+                    .iter()
+                    .all(|s| s.node_type == NodeType::DeclareStatement)
+        {
+            return Ok(());
+        }
+        Err(BQ2CSTError::from_token(
+            token,
+            ErrorCode::MisplacedDeclare,
+            misplaced_declare_message(block).to_string(),
+        ))
+    }
     fn parse_declare_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
         let mut declare = self.construct_node(NodeType::DeclareStatement)?;
         let mut idents = Vec::new();
@@ -3172,7 +4028,13 @@ impl Parser {
         Ok(declare)
     }
     fn parse_set_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
-        let mut set = self.construct_node(NodeType::SetStatement)?;
+        // e.g. SET @@time_zone = 'Asia/Tokyo';
+        let node_type = if self.get_token(1)?.literal.starts_with("@@") {
+            NodeType::SetSystemVariableStatement
+        } else {
+            NodeType::SetStatement
+        };
+        let mut set = self.construct_node(node_type)?;
         self.next_token()?; // set -> expr
         set.push_node("expr", self.parse_expr(usize::MAX, false, false, false)?);
         if self.get_token(1)?.is(";") && semicolon {
@@ -3227,9 +4089,10 @@ impl Parser {
         let mut stmts = Vec::new();
         while !self.get_token(1)?.in_(&vec!["END", "EXCEPTION"]) {
             self.next_token()?; // -> stmt
+            self.check_declare(&stmts, true)?;
             stmts.push(self.parse_statement(true)?);
         }
-        if 0 < stmts.len() {
+        if !stmts.is_empty() {
             begin.push_node_vec("stmts", stmts);
         }
         if self.get_token(1)?.is("exception") {
@@ -3281,7 +4144,7 @@ impl Parser {
             );
             elseifs.push(elseif);
         }
-        if 0 < elseifs.len() {
+        if !elseifs.is_empty() {
             if_.push_node_vec("elseifs", elseifs);
         }
 
@@ -3300,6 +4163,23 @@ impl Parser {
         Ok(if_)
     }
     fn parse_labeled_statement(&mut self, semicolon: bool) -> BQ2CSTResult<Node> {
+        // e.g. `SELECT a FROM t INTO x` (MySQL)
+        let prev_token = self.tokens[..self.position]
+            .iter()
+            .rev()
+            .find(|t| !t.is_comment());
+        if self.get_token(0)?.is("INTO") && prev_token.is_some_and(|t| !t.is(";")) {
+            return Err(self.select_into_error(0)?);
+        }
+        if !self.get_token(1)?.is(":") {
+            let token = self.get_token(0)?;
+            return Err(BQ2CSTError::from_token(
+                token,
+                ErrorCode::UnknownStatement,
+                format!("Expected statement but got `{}`.", token.literal),
+            )
+            .with_expected(&constants::STATEMENT_KEYWORDS));
+        }
         let label = self.construct_node(NodeType::Identifier)?;
         self.next_token()?; // -> :
         let colon = self.construct_node(NodeType::Symbol)?;
@@ -3312,6 +4192,7 @@ impl Parser {
         {
             return Err(BQ2CSTError::from_token(
                 self.get_token(0)?,
+                ErrorCode::InvalidLabel,
                 format!(
                     "The statement is not properly labeled: {:?}",
                     self.get_token(0)?
@@ -3467,6 +4348,7 @@ impl Parser {
             let mut stmts = Vec::new();
             while !self.get_token(1)?.in_(&vec!["WHEN", "ELSE", "END"]) {
                 self.next_token()?; // -> stmt
+                self.check_declare(&stmts, false)?;
                 stmts.push(self.parse_statement(true)?);
             }
             when.push_node_vec("stmts", stmts);
@@ -3478,6 +4360,7 @@ impl Parser {
             let mut stmts = Vec::new();
             while !self.get_token(1)?.is("END") {
                 self.next_token()?; // -> stmt
+                self.check_declare(&stmts, false)?;
                 stmts.push(self.parse_statement(true)?);
             }
             else_.push_node_vec("stmts", stmts);
@@ -3533,7 +4416,7 @@ impl Parser {
             export.push_node("with_connection", self.parse_with_connection_clause()?);
         }
         self.next_token()?; // -> OPTIONS
-        export.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+        export.push_node("options", self.parse_options()?);
         self.next_token()?; // -> AS
         let mut as_ = self.construct_node(NodeType::KeywordWithStatement)?;
         self.next_token()?; // -> stmt
@@ -3553,7 +4436,7 @@ impl Parser {
         export.push_node("ident", self.parse_identifier()?);
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            export.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            export.push_node("options", self.parse_options()?);
         }
         if self.get_token(1)?.is(";") && semicolon {
             self.next_token()?; // -> ;
@@ -3583,7 +4466,10 @@ impl Parser {
 
             // if precedence is usize::MAX
             // column_group is parsed as function arguments
-            op.push_node("grouped_expr", self.parse_expr(101, false, false, false)?);
+            op.push_node(
+                "grouped_expr",
+                self.parse_expr(precedence::ACCESS, false, false, false)?,
+            );
             load.push_node("overwrite_partitions", op);
         }
         if self.get_token(1)?.is("(") {
@@ -3603,7 +4489,7 @@ impl Parser {
         }
         if self.get_token(1)?.is("OPTIONS") {
             self.next_token()?; // -> OPTIONS
-            load.push_node("options", self.parse_keyword_with_grouped_exprs(false)?);
+            load.push_node("options", self.parse_options()?);
         }
         self.next_token()?; // -> FROM
         load.push_node("from", self.construct_node(NodeType::Keyword)?);
@@ -3630,8 +4516,8 @@ impl Parser {
             load.push_node("with", self.construct_node(NodeType::Keyword)?);
             self.next_token()?; // -> CONNECTION
             load.push_node("connection", self.construct_node(NodeType::Keyword)?);
-            self.next_token()?; // -> connection_name
-            load.push_node("connection_name", self.parse_identifier()?);
+            self.next_token()?; // -> connection_name | DEFAULT
+            load.push_node("connection_name", self.parse_connection_name()?);
         }
 
         if self.get_token(1)?.is(";") && semicolon {
@@ -3641,3 +4527,208 @@ impl Parser {
         Ok(load)
     }
 }
+
+fn comment_node(token: &Token) -> Node {
+    if token.is_template_tag() {
+        Node::new(token.clone(), NodeType::Template)
+    } else {
+        Node::new(token.clone(), NodeType::Comment)
+    }
+}
+
+/// Collects `key: value` lines from the comments placed immediately before `stmt`
+/// (no blank line is allowed between them).
+/// The key is written in lower case (e.g. `depends_on`) and followed by `:` and a space,
+/// so that notes such as `TODO: ...` and URLs are not annotations.
+/// `bq2cst: off` and `bq2cst: on` are not annotations either.
+fn annotations(stmt: &Node) -> BTreeMap<String, Vec<String>> {
+    let mut res: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    // the statement may start with a prefix (e.g. `@{...} SELECT 1`, `OPTIONS (...) SELECT 1`)
+    let first = STATEMENT_PREFIXES
+        .iter()
+        .filter_map(|key| match stmt.children.get(*key) {
+            Some(ContentType::Node(prefix)) => prefix.token.as_ref(),
+            _ => None,
+        })
+        .chain(&stmt.token)
+        .map(|t| (t.line, t.column))
+        .min();
+    let (leading_comments, first) = match (stmt.children.get("leading_comments"), first) {
+        (Some(ContentType::NodeVec(comments)), Some(first)) => (comments, first),
+        _ => return res,
+    };
+    let mut next_line = first.0;
+    let mut texts = Vec::new();
+    for comment in leading_comments.iter().rev() {
+        let token = match &comment.token {
+            Some(t) => t,
+            None => break,
+        };
+        // e.g. `-- comment` between `OPTIONS (...)` and `SELECT`
+        if first <= (token.line, token.column) {
+            continue;
+        }
+        let last_line = token.line + token.literal.matches('\n').count();
+        if last_line + 1 != next_line && last_line != next_line {
+            break;
+        }
+        match token.comment_text() {
+            Some(text) => texts.push(text),
+            None => break,
+        }
+        next_line = token.line;
+    }
+    for text in texts.into_iter().rev() {
+        for line in text.lines() {
+            let line = line.trim().trim_start_matches('*').trim();
+            let (key, value) = match line.split_once(':') {
+                Some((key, value)) if value.starts_with(char::is_whitespace) => (key, value.trim()),
+                _ => continue,
+            };
+            let is_key = key.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "_-.".contains(c));
+            if !is_key || value.is_empty() || key == "bq2cst" {
+                continue;
+            }
+            res.entry(key.to_string())
+                .or_default()
+                .push(value.to_string());
+        }
+    }
+    res
+}
+
+/// Functions whose arguments are parsed by `parse_ai_function_args`.
+const AI_FUNCTIONS: [&str; 3] = ["AI.GENERATE", "AI.GENERATE_TABLE", "ML.GENERATE_EMBEDDING"];
+
+/// Keys of the nodes which are placed before the keyword of the statement.
+const STATEMENT_PREFIXES: [&str; 2] = ["hint", "statement_options"];
+
+/// Moves `leading_comments` of the prefix (e.g. `@{...}`, `OPTIONS (...)`) to the statement,
+/// where `annotations` and `Script` look for them.
+fn move_leading_comments(prefix: &mut Node, stmt: &mut Node) {
+    if let Some(ContentType::NodeVec(mut comments)) = prefix.children.remove("leading_comments") {
+        if let Some(ContentType::NodeVec(rest)) = stmt.children.remove("leading_comments") {
+            comments.extend(rest);
+        }
+        stmt.push_node_vec("leading_comments", comments);
+    }
+}
+
+/// Returns `true` if the last element of the list is followed by a comma.
+fn has_trailing_comma(exprs: &[Node]) -> bool {
+    exprs
+        .last()
+        .is_some_and(|expr| expr.children.contains_key("comma"))
+}
+
+type Position = (usize, usize);
+
+/// Marks the nodes placed between `-- bq2cst: off` and `-- bq2cst: on`
+/// (or the end of the code) as `format_exempt`.
+/// A node which is only partially in the region is not marked but its children may be.
+fn mark_format_exempt(tokens: &[Token], stmts: &mut [Node]) {
+    let mut regions: Vec<(Position, Position)> = Vec::new();
+    let mut off = None;
+    for token in tokens {
+        match (token.format_directive(), off) {
+            (Some(FormatDirective::Off), None) => off = Some((token.line, token.column)),
+            (Some(FormatDirective::On), Some(start)) => {
+                regions.push((start, (token.line, token.column)));
+                off = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(start) = off {
+        regions.push((start, (usize::MAX, usize::MAX)));
+    }
+    if regions.is_empty() {
+        return;
+    }
+    for stmt in stmts {
+        mark_nodes(stmt, &regions);
+    }
+}
+
+fn mark_nodes(node: &mut Node, regions: &[(Position, Position)]) {
+    let (first, last) = match token_range(node) {
+        Some(range) => range,
+        None => return, // comments and EOF
+    };
+    if regions.iter().any(|(off, on)| off < &first && &last < on) {
+        node.format_exempt = true;
+        return;
+    }
+    if regions.iter().any(|(off, on)| off < &last && &first < on) {
+        for content in node.children.values_mut() {
+            match content {
+                ContentType::Node(n) => mark_nodes(n, regions),
+                ContentType::NodeVec(ns) => {
+                    for n in ns {
+                        mark_nodes(n, regions);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Normalizes the literals of `Identifier` and `Unknown` nodes to NFC.
+/// `Unknown` is included because a single identifier (e.g. a column in `SELECT`) is parsed as it.
+#[cfg(feature = "unicode-normalization")]
+fn normalize_identifiers(nodes: &mut [Node]) {
+    for node in nodes {
+        if matches!(node.node_type, NodeType::Identifier | NodeType::Unknown) {
+            if let Some(token) = &mut node.token {
+                if !is_nfc(&token.literal) {
+                    token.literal = token.literal.nfc().collect();
+                }
+            }
+        }
+        for content in node.children.values_mut() {
+            match content {
+                ContentType::Node(n) => normalize_identifiers(core::slice::from_mut(n)),
+                ContentType::NodeVec(ns) => normalize_identifiers(ns),
+            }
+        }
+    }
+}
+
+/// The message of `ErrorCode::MisplacedDeclare`.
+/// `block` is `true` if DECLARE is placed in a script or `BEGIN ... END` block.
+pub(crate) fn misplaced_declare_message(block: bool) -> &'static str {
+    if block {
+        "DECLARE must come before any other statement in a script or `BEGIN ... END` block."
+    } else {
+        "DECLARE is only allowed at the beginning of a script or `BEGIN ... END` block. \
+        Wrap the statements in `BEGIN ... END` to declare variables here."
+    }
+}
+
+/// Returns the positions of the first and the last token in `node` except comments.
+pub(crate) fn token_range(node: &Node) -> Option<(Position, Position)> {
+    let mut res = match &node.token {
+        Some(t) if !t.is_comment() && t.line != usize::MAX => {
+            Some(((t.line, t.column), (t.line, t.column)))
+        }
+        _ => None,
+    };
+    for content in node.children.values() {
+        let nodes = match content {
+            ContentType::Node(n) => core::slice::from_ref(n),
+            ContentType::NodeVec(ns) => ns.as_slice(),
+        };
+        for n in nodes {
+            if let Some((first, last)) = token_range(n) {
+                res = match res {
+                    Some((f, l)) => Some((f.min(first), l.max(last))),
+                    None => Some((first, last)),
+                };
+            }
+        }
+    }
+    res
+}
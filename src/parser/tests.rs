@@ -47,7 +47,7 @@ impl TestCase for SuccessTestCase {
         );
         let result = stmts[self.target_idx].to_string();
         let changeset = Changeset::new(self.expected_output.as_str(), result.as_str(), "\n");
-        println!("{}\n", changeset.to_string());
+        println!("{}\n", changeset);
         assert_eq!(self.expected_output, result);
     }
 }
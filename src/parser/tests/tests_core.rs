@@ -24,9 +24,7 @@ leading_comments:
         Box::new(ErrorTestCase::new(
             "\
 HOGE
-",
-            usize::MAX,
-            usize::MAX,
+", 1, 1,
         )),
         // ----- eof -----
         Box::new(SuccessTestCase::new(
@@ -126,6 +124,63 @@ exprs:
 - self: MODEL (UnaryOperator)
   right:
     self: `ident.ident` (Identifier)
+",
+            0,
+        )),
+        // sign is not fused into numeric literals
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT
+  +.5,
+  ~x,
+  - -1,
+  -x.y,
+  -a * b,
+  a -1
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: + (UnaryOperator)
+  comma:
+    self: , (Symbol)
+  right:
+    self: .5 (NumericLiteral)
+- self: ~ (UnaryOperator)
+  comma:
+    self: , (Symbol)
+  right:
+    self: x (Identifier)
+- self: - (UnaryOperator)
+  comma:
+    self: , (Symbol)
+  right:
+    self: - (UnaryOperator)
+    right:
+      self: 1 (NumericLiteral)
+- self: - (UnaryOperator)
+  comma:
+    self: , (Symbol)
+  right:
+    self: . (DotOperator)
+    left:
+      self: x (Identifier)
+    right:
+      self: y (Identifier)
+- self: * (BinaryOperator)
+  comma:
+    self: , (Symbol)
+  left:
+    self: - (UnaryOperator)
+    right:
+      self: a (Identifier)
+  right:
+    self: b (Identifier)
+- self: - (BinaryOperator)
+  left:
+    self: a (Identifier)
+  right:
+    self: 1 (NumericLiteral)
 ",
             0,
         )),
@@ -619,6 +674,57 @@ exprs:
         self: 0 (NumericLiteral)
   right:
     self: TRUE (BooleanLiteral)
+",
+            0,
+        )),
+        // bitwise operators
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT
+  x >> 1 & 2 | 3 ^ 4 << 5,
+  ARRAY_LENGTH(a) > b >> c
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: | (BinaryOperator)
+  comma:
+    self: , (Symbol)
+  left:
+    self: & (BinaryOperator)
+    left:
+      self: >> (BinaryOperator)
+      left:
+        self: x (Identifier)
+      right:
+        self: 1 (NumericLiteral)
+    right:
+      self: 2 (NumericLiteral)
+  right:
+    self: ^ (BinaryOperator)
+    left:
+      self: 3 (NumericLiteral)
+    right:
+      self: << (BinaryOperator)
+      left:
+        self: 4 (NumericLiteral)
+      right:
+        self: 5 (NumericLiteral)
+- self: > (BinaryOperator)
+  left:
+    self: ( (CallingFunction)
+    args:
+    - self: a (Identifier)
+    func:
+      self: ARRAY_LENGTH (Identifier)
+    rparen:
+      self: ) (Symbol)
+  right:
+    self: >> (BinaryOperator)
+    left:
+      self: b (Identifier)
+    right:
+      self: c (Identifier)
 ",
             0,
         )),
@@ -774,6 +880,65 @@ exprs:
       self: ) (Symbol)
   rparen:
     self: ] (Symbol)
+",
+            0,
+        )),
+        // `>>` closing nested type declarations
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT ARRAY<ARRAY<INT64>>[], ARRAY<STRUCT<a ARRAY<INT64>>>[] -- comment
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: [ (ArrayLiteral)
+  comma:
+    self: , (Symbol)
+  exprs: []
+  rparen:
+    self: ] (Symbol)
+  type:
+    self: ARRAY (Type)
+    type_declaration:
+      self: < (GroupedType)
+      rparen:
+        self: > (Symbol)
+      type:
+        self: ARRAY (Type)
+        type_declaration:
+          self: < (GroupedType)
+          rparen:
+            self: > (Symbol)
+          type:
+            self: INT64 (Type)
+- self: [ (ArrayLiteral)
+  exprs: []
+  rparen:
+    self: ] (Symbol)
+    trailing_comments:
+    - self: -- comment (Comment)
+  type:
+    self: ARRAY (Type)
+    type_declaration:
+      self: < (GroupedType)
+      rparen:
+        self: > (Symbol)
+      type:
+        self: STRUCT (Type)
+        type_declaration:
+          self: < (GroupedTypeDeclarationOrConstraints)
+          declarations:
+          - self: a (TypeDeclaration)
+            type:
+              self: ARRAY (Type)
+              type_declaration:
+                self: < (GroupedType)
+                rparen:
+                  self: > (Symbol)
+                type:
+                  self: INT64 (Type)
+          rparen:
+            self: > (Symbol)
 ",
             0,
         )),
@@ -1446,6 +1611,45 @@ exprs:
     self: ST_GEOGFROMTEXT (Identifier)
   rparen:
     self: ) (Symbol)
+",
+            0,
+        )),
+        // JSONPath modes and RETURNING
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT
+  JSON_QUERY(data, 'lax recursive $.a'),
+  JSON_VALUE(data, '$.b' RETURNING INT64)
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: ( (CallingFunction)
+  args:
+  - self: data (Identifier)
+    comma:
+      self: , (Symbol)
+  - self: 'lax recursive $.a' (StringLiteral)
+  comma:
+    self: , (Symbol)
+  func:
+    self: JSON_QUERY (Identifier)
+  rparen:
+    self: ) (Symbol)
+- self: ( (CallingFunction)
+  args:
+  - self: data (Identifier)
+    comma:
+      self: , (Symbol)
+  - self: '$.b' (StringLiteral)
+  func:
+    self: JSON_VALUE (Identifier)
+  returning:
+    self: RETURNING (KeywordWithType)
+    type:
+      self: INT64 (Type)
+  rparen:
+    self: ) (Symbol)
 ",
             0,
         )),
@@ -1749,6 +1953,109 @@ exprs:
         self: ) (Symbol)
   rparen:
     self: ) (Symbol)
+",
+            0,
+        )),
+        // ----- statement options -----
+        Box::new(SuccessTestCase::new(
+            "\
+-- comment
+OPTIONS (a = 1) SELECT 1;
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 1 (NumericLiteral)
+leading_comments:
+- self: -- comment (Comment)
+semicolon:
+  self: ; (Symbol)
+statement_options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      left:
+        self: a (Identifier)
+      right:
+        self: 1 (NumericLiteral)
+    rparen:
+      self: ) (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+OPTIONS (query_label = 'team:analytics') SELECT 1;
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 1 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+statement_options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      left:
+        self: query_label (Identifier)
+      right:
+        self: 'team:analytics' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+OPTIONS (labels = [('k', 'v')])
+label: BEGIN
+  SELECT 1;
+END;
+",
+            "\
+self: BEGIN (BeginStatement)
+colon:
+  self: : (Symbol)
+end:
+  self: END (Keyword)
+leading_label:
+  self: label (Identifier)
+semicolon:
+  self: ; (Symbol)
+statement_options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      left:
+        self: labels (Identifier)
+      right:
+        self: [ (ArrayLiteral)
+        exprs:
+        - self: ( (StructLiteral)
+          exprs:
+          - self: 'k' (StringLiteral)
+            comma:
+              self: , (Symbol)
+          - self: 'v' (StringLiteral)
+          rparen:
+            self: ) (Symbol)
+        rparen:
+          self: ] (Symbol)
+    rparen:
+      self: ) (Symbol)
+stmts:
+- self: SELECT (SelectStatement)
+  exprs:
+  - self: 1 (NumericLiteral)
+  semicolon:
+    self: ; (Symbol)
 ",
             0,
         )),
@@ -1776,3 +2083,759 @@ exprs:
         t.test();
     }
 }
+
+#[test]
+fn test_error_expected() {
+    let test_cases = vec![
+        ("SELECT 1 NOT x", vec!["IN", "LIKE", "BETWEEN"]),
+        (
+            "ALTER TABLE t DO SOMETHING;",
+            vec!["SET", "ADD", "RENAME", "DROP", "ALTER"],
+        ),
+    ];
+    for (code, expected) in test_cases {
+        let l = Lexer::new(code.to_string());
+        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+        let error = p
+            .parse_code()
+            .expect_err("Unexpectedly succeeded to parse code.");
+        assert_eq!(ErrorCode::UnexpectedToken, error.code, "{}", code);
+        assert_eq!(expected, error.expected, "{}", code);
+    }
+}
+
+#[test]
+fn test_error_unknown_statement() {
+    let tokens = Lexer::new("SELEC 1;".to_string()).tokenize_code().unwrap();
+    let error = Parser::new(tokens).parse_code().unwrap_err();
+    assert_eq!(ErrorCode::UnknownStatement, error.code);
+    assert_eq!("Expected statement but got `SELEC`.", error.message());
+}
+
+#[test]
+fn test_error_suggestion() {
+    let test_cases = vec![
+        ("SELEC 1;", Some((1, 1, "SELECT"))),
+        ("SELECT a FROM t GROPU BY a;", Some((1, 17, "GROUP"))),
+        ("SELECT 1 NOT LIKR 'a';", Some((1, 14, "LIKE"))),
+        ("SELECT id FROM t WHERE", None),
+    ];
+    for (code, expected) in test_cases {
+        let l = Lexer::new(code.to_string());
+        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+        let error = p
+            .parse_code()
+            .expect_err("Unexpectedly succeeded to parse code.");
+        let actual = error.suggestion.map(|s| (s.line, s.column, s.replacement));
+        let expected = expected.map(|(l, c, r)| (l, c, r.to_string()));
+        assert_eq!(expected, actual, "{}", code);
+    }
+}
+
+#[test]
+fn test_parse_code_with_recovery() {
+    let test_cases = vec![
+        (
+            "\
+SELECT 1 NOT x FROM t WHERE a NOT b ORDER BY c;
+SELECT 2;
+",
+            vec![(1, 14), (1, 35)],
+            3,
+        ),
+        (
+            "\
+SELECT (1 NOT x) FROM t;
+SELEC 2;
+SELECT 3
+",
+            vec![(1, 15), (2, 1)],
+            4,
+        ),
+        ("SELECT 1; SELECT 2;", vec![], 3),
+        // the block is skipped as a whole
+        (
+            "\
+BEGIN
+  SELEC 1;
+  SELECT 1;
+END;
+SELECT 2;
+",
+            vec![(2, 3)],
+            3,
+        ),
+    ];
+    for (code, expected_errors, expected_stmts) in test_cases {
+        let l = Lexer::new(code.to_string());
+        let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+        let (stmts, errors) = p.parse_code_with_recovery();
+        let actual: Vec<(usize, usize)> = errors.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(expected_errors, actual, "{}", code);
+        assert_eq!(expected_stmts, stmts.len(), "{}", code);
+    }
+
+    // the clauses are checked with the same options
+    let code = "SELECT 1 NOT x FROM t WHERE a IN @list ORDER BY c;";
+    let options = ParseOptions {
+        placeholders: true,
+        ..Default::default()
+    };
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let (_, errors) = Parser::with_options(tokens, options).parse_code_with_recovery();
+    let actual: Vec<(usize, usize)> = errors.iter().map(|e| (e.line, e.column)).collect();
+    assert_eq!(vec![(1, 14)], actual);
+}
+
+#[test]
+fn test_placeholders() {
+    let test_cases = vec![
+        (
+            "SELECT 1 IN @list;",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: IN (InOperator)
+  left:
+    self: 1 (NumericLiteral)
+  right:
+    self: @list (Parameter)
+semicolon:
+  self: ; (Symbol)
+",
+        ),
+        (
+            "INSERT INTO t {query};",
+            "\
+self: INSERT (InsertStatement)
+input:
+  self: {query} (Template)
+into:
+  self: INTO (Keyword)
+semicolon:
+  self: ; (Symbol)
+target_name:
+  self: t (Identifier)
+",
+        ),
+        (
+            "@stmt;",
+            "\
+self: @stmt (SingleTokenStatement)
+semicolon:
+  self: ; (Symbol)
+",
+        ),
+    ];
+    let options = ParseOptions {
+        placeholders: true,
+        ..Default::default()
+    };
+    for (code, expected) in test_cases {
+        let l = Lexer::new(code.to_string());
+        let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+        let mut p = Parser::with_options(tokens, options.clone());
+        let stmts = p.parse_code().expect("Failed to parse code.");
+        assert_eq!(expected, stmts[0].to_string(), "{}", code);
+    }
+}
+
+#[test]
+fn test_disabled_features() {
+    let test_cases = vec![
+        (
+            "SELECT WITH DIFFERENTIAL_PRIVACY OPTIONS(epsilon = 1) SUM(x) FROM t;",
+            SyntaxFeature::DifferentialPrivacy,
+            (1, 8),
+        ),
+        (
+            "SELECT * FROM t PIVOT (SUM(x) FOR y IN ('a'));",
+            SyntaxFeature::Pivot,
+            (1, 17),
+        ),
+        (
+            "SELECT * FROM t UNPIVOT (x FOR y IN (a, b));",
+            SyntaxFeature::Unpivot,
+            (1, 17),
+        ),
+        (
+            "SELECT * FROM t TABLESAMPLE SYSTEM (10 PERCENT);",
+            SyntaxFeature::Tablesample,
+            (1, 17),
+        ),
+        (
+            "SELECT x FROM t QUALIFY ROW_NUMBER() OVER () = 1;",
+            SyntaxFeature::Qualify,
+            (1, 17),
+        ),
+        (
+            "SELECT * FROM t MATCH_RECOGNIZE (PATTERN (a) DEFINE a AS true);",
+            SyntaxFeature::MatchRecognize,
+            (1, 17),
+        ),
+    ];
+    for (code, feature, (line, column)) in test_cases {
+        let tokens = Lexer::new(code.to_string())
+            .tokenize_code()
+            .expect("Failed to tokenize code.");
+        assert!(Parser::new(tokens.clone()).parse_code().is_ok(), "{}", code);
+        let options = ParseOptions {
+            disabled_features: vec![feature],
+            ..Default::default()
+        };
+        let err = Parser::with_options(tokens, options)
+            .parse_code()
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::DisabledFeature, "{}", code);
+        assert_eq!((err.line, err.column), (line, column), "{}", code);
+    }
+}
+
+#[test]
+fn test_hints() {
+    let code = "@{optimizer_version=1} SELECT * FROM a @{FORCE_INDEX=idx} AS x JOIN @{JOIN_METHOD=HASH_JOIN} b USING (id);";
+    let expected = "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: JOIN (JoinOperator)
+    hint:
+      self: @{JOIN_METHOD=HASH_JOIN} (Hint)
+    left:
+      self: a (Identifier)
+      alias:
+        self: x (Identifier)
+      as:
+        self: AS (Keyword)
+      hint:
+        self: @{FORCE_INDEX=idx} (Hint)
+    right:
+      self: b (Identifier)
+    using:
+      self: USING (KeywordWithGroupedXXX)
+      group:
+        self: ( (GroupedExprs)
+        exprs:
+        - self: id (Identifier)
+        rparen:
+          self: ) (Symbol)
+hint:
+  self: @{optimizer_version=1} (Hint)
+semicolon:
+  self: ; (Symbol)
+";
+    let options = ParseOptions {
+        hints: true,
+        ..Default::default()
+    };
+    let l = Lexer::with_options(code.to_string(), options.clone());
+    let tokens = l.tokenize_code().expect("Failed to tokenize code.");
+    let mut p = Parser::with_options(tokens, options.clone());
+    let stmts = p.parse_code().expect("Failed to parse code.");
+    assert_eq!(expected, stmts[0].to_string());
+
+    // comments before the hint belong to the statement
+    let code = "-- header\n\n-- owner: x\n@{optimizer_version=1} SELECT 1;";
+    let tokens = Lexer::with_options(code.to_string(), options.clone())
+        .tokenize_code()
+        .unwrap();
+    let script = Parser::with_options(tokens, options)
+        .parse_script()
+        .unwrap();
+    assert_eq!(script.leading_comments.len(), 1);
+    assert_eq!(script.stmts[0].annotations["owner"], vec!["x".to_string()]);
+}
+
+#[test]
+fn test_cancellation() {
+    let code = "\
+SELECT 1;
+BEGIN
+  SELECT 2;
+END;
+SELECT 3;
+";
+    // cancelled before the n-th statement
+    for (n, position) in [(1, (1, 1)), (2, (2, 1)), (3, (3, 3)), (4, (5, 1))] {
+        let count = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = count.clone();
+        let tokens = Lexer::new(code.to_string())
+            .tokenize_code()
+            .expect("Failed to tokenize code.");
+        let mut p = Parser::new(tokens).with_cancellation(move || {
+            counter.set(counter.get() + 1);
+            n <= counter.get()
+        });
+        let err = p.parse_code().unwrap_err();
+        assert_eq!(err.code, ErrorCode::Cancelled);
+        assert_eq!((err.line, err.column), position);
+        assert!(err.suggestion.is_none());
+        assert_eq!(count.get(), n);
+    }
+
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let (stmts, errors) = Parser::new(tokens)
+        .with_cancellation(|| true)
+        .parse_code_with_recovery();
+    assert_eq!(stmts.len(), 1); // EOF
+    assert_eq!(errors.len(), 1);
+
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).with_cancellation(|| false).parse_code();
+    assert_eq!(stmts.unwrap().len(), 4);
+}
+
+#[test]
+fn test_format_exempt() {
+    let code = "\
+SELECT 1;
+-- bq2cst: off
+SELECT   2;
+SELECT   3; -- bq2cst: on
+SELECT 4;
+SELECT
+  5,
+  -- bq2cst: off
+  6;
+";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let exempt: Vec<bool> = stmts.iter().map(|s| s.format_exempt).collect();
+    assert_eq!(exempt, vec![false, true, true, false, false, false]);
+    // only a part of the last statement
+    let exprs = match stmts[4].children.get("exprs") {
+        Some(ContentType::NodeVec(ns)) => ns,
+        other => panic!("{:?}", other),
+    };
+    assert!(!exprs[0].format_exempt);
+    assert!(exprs[1].format_exempt);
+}
+
+#[test]
+fn test_annotations() {
+    let code = "\
+-- depends_on: dataset.a
+-- not an annotation
+-- depends_on: dataset.b
+
+-- owner: data-team
+-- owner: someone@example.com
+/*
+ * schedule: daily
+ * url: https://example.com
+ * TODO: fix
+ * Note: not an annotation
+ * https://example.com
+ * key : value
+ * two words: value
+ */
+SELECT 1;
+-- bq2cst: off
+SELECT 2;
+BEGIN
+  # retry: 3
+  SELECT 3;
+END;
+";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let annotations: Vec<(&str, Vec<&str>)> = stmts[0]
+        .annotations
+        .iter()
+        .map(|(k, vs)| (k.as_str(), vs.iter().map(|v| v.as_str()).collect()))
+        .collect();
+    // comments before the blank line are not immediately before the statement
+    assert_eq!(
+        annotations,
+        vec![
+            ("owner", vec!["data-team", "someone@example.com"]),
+            ("schedule", vec!["daily"]),
+            ("url", vec!["https://example.com"]),
+        ]
+    );
+    assert!(stmts[1].annotations.is_empty());
+    let inner = match stmts[2].children.get("stmts") {
+        Some(ContentType::NodeVec(ns)) => &ns[0],
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(inner.annotations["retry"], vec!["3".to_string()]);
+
+    // comments before the prefix of the statement
+    let code = "-- owner: x\nOPTIONS (a = 1)\n-- not: annotation\nSELECT 1;";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    assert_eq!(
+        stmts[0].annotations.keys().collect::<Vec<_>>(),
+        vec![&"owner".to_string()]
+    );
+}
+
+#[test]
+fn test_select_into() {
+    let test_cases = vec![
+        ("SELECT a INTO x FROM t;", Some((1, 10))),
+        ("SELECT a, b INTO x, y;", Some((1, 13))),
+        ("SELECT a FROM t INTO x;", Some((1, 17))),
+        ("SELECT 1; INTO x;", None),
+        ("INSERT INTO t SELECT 1;", None),
+    ];
+    for (code, expected) in test_cases {
+        let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+        let actual = match Parser::new(tokens).parse_code() {
+            Err(e) if e.code == ErrorCode::SelectInto => {
+                assert!(e.message().contains("SET"));
+                assert!(e.suggestion.is_none());
+                Some((e.line, e.column))
+            }
+            _ => None,
+        };
+        assert_eq!(expected, actual, "{}", code);
+    }
+}
+
+#[test]
+fn test_legacy_sql() {
+    let test_cases = vec![
+        (
+            "SELECT a FROM [project:dataset.table] WHERE b = 1;",
+            Some((1, 15, 1, 38)),
+        ),
+        (
+            "SELECT a FROM t JOIN [dataset.t2] ON t.id = t2.id;",
+            Some((1, 22, 1, 34)),
+        ),
+        (
+            "SELECT a FROM TABLE_DATE_RANGE([dataset.t_], TIMESTAMP('2020-01-01'), CURRENT_TIMESTAMP());",
+            Some((1, 15, 1, 31)),
+        ),
+        ("SELECT a FROM t1, t2;", None),
+        ("SELECT [1, 2];", None),
+    ];
+    for (code, expected) in test_cases {
+        let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+        let actual = match Parser::new(tokens).parse_code() {
+            Err(e) if e.code == ErrorCode::LegacySql => {
+                let span = e.span.expect("span");
+                Some((
+                    span.start_line,
+                    span.start_column,
+                    span.end_line,
+                    span.end_column,
+                ))
+            }
+            Err(e) => panic!("{:?}", e),
+            Ok(_) => None,
+        };
+        assert_eq!(expected, actual, "{}", code);
+    }
+
+    // no cascade of errors
+    let code = "SELECT a FROM [dataset.t] WHERE b = [x]; SELECT 1;";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let (stmts, errors) = Parser::new(tokens).parse_code_with_recovery();
+    assert_eq!(stmts.len(), 3);
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn test_trace() {
+    let events = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let collected = events.clone();
+    let tokens = Lexer::new("SELECT a FROM t;".to_string())
+        .tokenize_code()
+        .unwrap();
+    Parser::new(tokens)
+        .with_trace(move |event| {
+            collected.borrow_mut().push(match event {
+                TraceEvent::Enter { rule, token } => format!("> {} {}", rule, token.literal),
+                TraceEvent::Exit { rule, token, ok } => {
+                    format!("< {} {} {}", rule, token.literal, ok)
+                }
+            })
+        })
+        .parse_code()
+        .unwrap();
+    assert_eq!(
+        *events.borrow(),
+        vec![
+            "> parse_statement SELECT",
+            "> parse_select_statement SELECT",
+            "> parse_expr a",
+            "< parse_expr a true",
+            "> parse_table t",
+            "> parse_expr t",
+            "< parse_expr t true",
+            "< parse_table t true",
+            "< parse_select_statement ; true",
+            "< parse_statement ; true",
+        ]
+    );
+}
+
+#[test]
+fn test_unparsed_statement() {
+    let code = "\
+-- leading
+SELEC 1, /* c */ 2;
+SELECT 3;
+";
+    let l = Lexer::new(code.to_string());
+    let mut p = Parser::new(l.tokenize_code().expect("Failed to tokenize code."));
+    let (stmts, errors) = p.parse_code_with_recovery();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        stmts[0].to_string(),
+        "\
+self: SELEC (UnparsedStatement)
+leading_comments:
+- self: -- leading (Comment)
+semicolon:
+  self: ; (Symbol)
+unknown:
+- self: 1 (Unknown)
+- self: , (Unknown)
+- self: /* c */ (Comment)
+- self: 2 (Unknown)
+"
+    );
+    assert_eq!(stmts[1].node_type, NodeType::SelectStatement);
+}
+
+#[test]
+fn test_trailing_comma() {
+    let code = "\
+SELECT
+  [1, 2,],
+  ARRAY<INT64>[3],
+  (4, 5,),
+  STRUCT(6 AS x,),
+FROM t;
+SELECT 1;
+";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    assert!(stmts[0].has_trailing_comma());
+    assert!(!stmts[1].has_trailing_comma());
+    let exprs = match stmts[0].children.get("exprs") {
+        Some(ContentType::NodeVec(ns)) => ns,
+        other => panic!("{:?}", other),
+    };
+    let flags: Vec<(NodeType, bool)> = exprs
+        .iter()
+        .map(|e| (e.node_type.clone(), e.has_trailing_comma()))
+        .collect();
+    assert_eq!(
+        flags,
+        vec![
+            (NodeType::ArrayLiteral, true),
+            (NodeType::ArrayLiteral, false),
+            (NodeType::StructLiteral, true),
+            (NodeType::StructLiteral, true),
+        ]
+    );
+}
+
+#[test]
+fn test_misplaced_declare() {
+    let test_cases = vec![
+        ("DECLARE x INT64; DECLARE y INT64; SELECT 1;", None),
+        ("BEGIN END;", None),
+        ("BEGIN BEGIN BEGIN DECLARE x INT64; END; END; END;", None),
+        ("SELECT 1; DECLARE x INT64;", Some((1, 11))),
+        (
+            "BEGIN DECLARE x INT64; SET x = 1; DECLARE y INT64; END;",
+            Some((1, 35)),
+        ),
+        ("IF TRUE THEN DECLARE x INT64; END IF;", Some((1, 14))),
+        ("LOOP BEGIN DECLARE x INT64; END; END LOOP;", None),
+    ];
+    let options = ParseOptions {
+        strict_declare: true,
+        ..Default::default()
+    };
+    for (code, expected) in test_cases {
+        let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+        // accepted unless `strict_declare` is set
+        assert!(Parser::new(tokens.clone()).parse_code().is_ok(), "{}", code);
+        let actual = match Parser::with_options(tokens, options.clone()).parse_code() {
+            Err(e) if e.code == ErrorCode::MisplacedDeclare => Some((e.line, e.column)),
+            Err(e) => panic!("{:?}", e),
+            Ok(_) => None,
+        };
+        assert_eq!(expected, actual, "{}", code);
+    }
+
+    // deeply nested blocks
+    let code = format!(
+        "{}SELECT 1;{}",
+        "BEGIN DECLARE x INT64; ".repeat(50),
+        " END;".repeat(50)
+    );
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    assert!(Parser::with_options(tokens, options.clone())
+        .parse_code()
+        .is_ok());
+
+    // the rest of the script is still parsed
+    let code = "SELECT 1; DECLARE x INT64; SELECT 2;";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let (stmts, errors) = Parser::with_options(tokens, options).parse_code_with_recovery();
+    assert_eq!(stmts.len(), 4);
+    assert_eq!(stmts[1].node_type, NodeType::UnparsedStatement);
+    assert_eq!(
+        errors.iter().map(|e| e.code).collect::<Vec<_>>(),
+        vec![ErrorCode::MisplacedDeclare]
+    );
+}
+
+#[test]
+fn test_max_depth() {
+    // the default is deep enough
+    let code = format!("{}SELECT 1;{}", "BEGIN ".repeat(50), " END;".repeat(50));
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    assert!(Parser::new(tokens).parse_code().is_ok());
+
+    let options = ParseOptions {
+        max_depth: Some(5),
+        ..Default::default()
+    };
+    let test_cases = vec![
+        format!("{}SELECT 1;{}", "BEGIN ".repeat(2000), " END;".repeat(2000)),
+        format!("SELECT {}1{};", "(".repeat(2000), ")".repeat(2000)),
+        format!("SELECT {}1{};", "(SELECT ".repeat(2000), ")".repeat(2000)),
+    ];
+    for code in test_cases {
+        let tokens = Lexer::new(code).tokenize_code().unwrap();
+        let err = Parser::with_options(tokens.clone(), options.clone())
+            .parse_code()
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::ResourceLimitExceeded);
+
+        let (_, errors) = Parser::with_options(tokens, options.clone()).parse_code_with_recovery();
+        assert_eq!(
+            errors.iter().map(|e| e.code).collect::<Vec<_>>(),
+            vec![ErrorCode::ResourceLimitExceeded]
+        );
+    }
+}
+
+#[test]
+fn test_max_nodes() {
+    let code = "SELECT 1; SELECT a, b, c FROM t; SELECT 2;";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let options = ParseOptions {
+        max_nodes: Some(10),
+        ..Default::default()
+    };
+    let err = Parser::with_options(tokens.clone(), options.clone())
+        .parse_code()
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::ResourceLimitExceeded);
+    assert_eq!(err.category, crate::error::ErrorCategory::Resource);
+
+    // the rest of the code is not parsed
+    let (stmts, errors) = Parser::with_options(tokens.clone(), options).parse_code_with_recovery();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].code, ErrorCode::ResourceLimitExceeded);
+    assert_eq!(stmts.len(), 2);
+
+    let options = ParseOptions {
+        max_nodes: Some(100),
+        ..Default::default()
+    };
+    assert!(Parser::with_options(tokens, options).parse_code().is_ok());
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn test_normalize_identifiers() {
+    let code = "SELECT cafe\u{301}, `cafe\u{301}`, 'cafe\u{301}' FROM t.cafe\u{301};";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens.clone()).parse_code().unwrap();
+    assert!(stmts[0].to_string().contains("cafe\u{301}"));
+
+    let options = ParseOptions {
+        normalize_identifiers: true,
+        ..Default::default()
+    };
+    let stmts = Parser::with_options(tokens.clone(), options)
+        .parse_code()
+        .unwrap();
+    let tree = stmts[0].to_string();
+    assert!(tree.contains("self: caf\u{e9} (Unknown)"));
+    assert!(tree.contains("self: `caf\u{e9}` (Identifier)"));
+    assert!(tree.contains("right:\n      self: caf\u{e9} (Identifier)"));
+    // string literals are not normalized
+    assert!(tree.contains("'cafe\u{301}'"));
+    // the tokens are kept as written
+    assert_eq!(tokens[1].literal, "cafe\u{301}");
+}
+
+#[test]
+fn test_chained_joins() {
+    // each join is parsed once (this used to take exponential time)
+    let code = format!(
+        "SELECT * FROM t{};",
+        " LEFT JOIN UNNEST(t.a) AS x".repeat(30)
+    );
+    let tokens = Lexer::new(code).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let mut depth = 0;
+    let mut node = match stmts[0].children.get("from") {
+        Some(ContentType::Node(from)) => from,
+        _ => panic!(),
+    };
+    while let Some(ContentType::Node(expr)) =
+        node.children.get("expr").or(node.children.get("left"))
+    {
+        if expr.node_type == NodeType::JoinOperator {
+            depth += 1;
+            assert!(!expr.children.contains_key("on"));
+        }
+        node = expr;
+    }
+    assert_eq!(depth, 30);
+
+    // conditions placed after those of the next joins
+    let code = "SELECT * FROM a JOIN b JOIN c JOIN d ON c.x = d.x ON b.x = c.x ON a.x = b.x JOIN e ON TRUE";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let tree = stmts[0].to_string();
+    assert_eq!(tree.matches("(JoinOperator)").count(), 4);
+    assert_eq!(tree.matches("self: ON (KeywordWithExpr)").count(), 4);
+}
+
+#[test]
+fn test_split_closing_angle_bracket_trivia() {
+    fn collect_tokens<'a>(node: &'a Node, tokens: &mut Vec<&'a Token>) {
+        if let Some(t) = &node.token {
+            tokens.push(t);
+        }
+        for (_, child) in crate::analysis::child_nodes(node) {
+            collect_tokens(child, tokens);
+        }
+    }
+    let code = "SELECT CAST(x AS ARRAY<STRUCT<a INT64>>  ) -- comment\nFROM t;\n\
+        DECLARE y ARRAY<STRUCT<a INT64\n>> ;";
+    let options = ParseOptions {
+        keep_trivia: true,
+        ..Default::default()
+    };
+    let tokens = Lexer::with_options(code.to_string(), options)
+        .tokenize_code()
+        .unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let mut tokens = Vec::new();
+    for stmt in &stmts {
+        collect_tokens(stmt, &mut tokens);
+    }
+    tokens.sort_by_key(|t| (t.line, t.column));
+    let actual: String = tokens
+        .iter()
+        .map(|t| format!("{}{}{}", t.leading_trivia(), t.literal, t.trailing_trivia()))
+        .collect();
+    assert_eq!(actual, code);
+}
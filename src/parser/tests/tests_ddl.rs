@@ -430,6 +430,85 @@ ident:
   self: tablename (Identifier)
 what:
   self: TABLE (Keyword)
+",
+            0,
+        )),
+        // both schema and query
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE TABLE example (x INT64 OPTIONS(description = 'x'), y STRING)
+PARTITION BY x
+OPTIONS(description = 'example')
+AS SELECT 1 AS x, 'y' AS y;
+",
+            "\
+self: CREATE (CreateTableStatement)
+as:
+  self: AS (KeywordWithStatement)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 1 (NumericLiteral)
+      alias:
+        self: x (Identifier)
+      as:
+        self: AS (Keyword)
+      comma:
+        self: , (Symbol)
+    - self: 'y' (StringLiteral)
+      alias:
+        self: y (Identifier)
+      as:
+        self: AS (Keyword)
+column_schema_group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: x (TypeDeclaration)
+    comma:
+      self: , (Symbol)
+    type:
+      self: INT64 (Type)
+      options:
+        self: OPTIONS (KeywordWithGroupedXXX)
+        group:
+          self: ( (GroupedExprs)
+          exprs:
+          - self: = (BinaryOperator)
+            left:
+              self: description (Identifier)
+            right:
+              self: 'x' (StringLiteral)
+          rparen:
+            self: ) (Symbol)
+  - self: y (TypeDeclaration)
+    type:
+      self: STRING (Type)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: example (Identifier)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      left:
+        self: description (Identifier)
+      right:
+        self: 'example' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+partitionby:
+  self: PARTITION (XXXByExprs)
+  by:
+    self: BY (Keyword)
+  exprs:
+  - self: x (Identifier)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: TABLE (Keyword)
 ",
             0,
         )),
@@ -707,6 +786,252 @@ ident:
   self: example (Identifier)
 what:
   self: TABLE (Keyword)
+",
+            0,
+        )),
+        // INTERVAL and nested arrays of struct literals in OPTIONS
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE TABLE example (x INT64)
+OPTIONS(
+  max_staleness = INTERVAL '0:30:0' HOUR TO SECOND,
+  labels = [('a', 'b'), ('c', 'd')],
+  nested = [STRUCT('k' AS key, [STRUCT(1 AS v)] AS vs)]
+);
+",
+            "\
+self: CREATE (CreateTableStatement)
+column_schema_group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: x (TypeDeclaration)
+    type:
+      self: INT64 (Type)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: example (Identifier)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: max_staleness (Identifier)
+      right:
+        self: INTERVAL (IntervalLiteral)
+        date_part:
+          self: HOUR (Keyword)
+        expr:
+          self: '0:30:0' (StringLiteral)
+        to:
+          self: TO (Keyword)
+        to_date_part:
+          self: SECOND (Keyword)
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: labels (Identifier)
+      right:
+        self: [ (ArrayLiteral)
+        exprs:
+        - self: ( (StructLiteral)
+          comma:
+            self: , (Symbol)
+          exprs:
+          - self: 'a' (StringLiteral)
+            comma:
+              self: , (Symbol)
+          - self: 'b' (StringLiteral)
+          rparen:
+            self: ) (Symbol)
+        - self: ( (StructLiteral)
+          exprs:
+          - self: 'c' (StringLiteral)
+            comma:
+              self: , (Symbol)
+          - self: 'd' (StringLiteral)
+          rparen:
+            self: ) (Symbol)
+        rparen:
+          self: ] (Symbol)
+    - self: = (BinaryOperator)
+      left:
+        self: nested (Identifier)
+      right:
+        self: [ (ArrayLiteral)
+        exprs:
+        - self: ( (StructLiteral)
+          exprs:
+          - self: 'k' (StringLiteral)
+            alias:
+              self: key (Identifier)
+            as:
+              self: AS (Keyword)
+            comma:
+              self: , (Symbol)
+          - self: [ (ArrayLiteral)
+            alias:
+              self: vs (Identifier)
+            as:
+              self: AS (Keyword)
+            exprs:
+            - self: ( (StructLiteral)
+              exprs:
+              - self: 1 (NumericLiteral)
+                alias:
+                  self: v (Identifier)
+                as:
+                  self: AS (Keyword)
+              rparen:
+                self: ) (Symbol)
+              type:
+                self: STRUCT (Type)
+            rparen:
+              self: ] (Symbol)
+          rparen:
+            self: ) (Symbol)
+          type:
+            self: STRUCT (Type)
+        rparen:
+          self: ] (Symbol)
+    rparen:
+      self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: TABLE (Keyword)
+",
+            0,
+        )),
+        // BigLake tables for Apache Iceberg
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE TABLE dataset.example (x INT64)
+CLUSTER BY x
+WITH CONNECTION `project.region.connection`
+OPTIONS (
+  file_format = 'PARQUET',
+  table_format = 'ICEBERG',
+  storage_uri = 'gs://bucket/path'
+);
+",
+            "\
+self: CREATE (CreateTableStatement)
+clusterby:
+  self: CLUSTER (XXXByExprs)
+  by:
+    self: BY (Keyword)
+  exprs:
+  - self: x (Identifier)
+column_schema_group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: x (TypeDeclaration)
+    type:
+      self: INT64 (Type)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: example (Identifier)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: file_format (Identifier)
+      right:
+        self: 'PARQUET' (StringLiteral)
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: table_format (Identifier)
+      right:
+        self: 'ICEBERG' (StringLiteral)
+    - self: = (BinaryOperator)
+      left:
+        self: storage_uri (Identifier)
+      right:
+        self: 'gs://bucket/path' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: TABLE (Keyword)
+with_connection:
+  self: WITH (KeywordSequence)
+  next_keyword:
+    self: CONNECTION (KeywordWithExpr)
+    expr:
+      self: `project.region.connection` (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE TABLE dataset.example (x INT64)
+WITH CONNECTION DEFAULT
+OPTIONS (table_format = 'ICEBERG', storage_uri = 'gs://bucket/path');
+",
+            "\
+self: CREATE (CreateTableStatement)
+column_schema_group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: x (TypeDeclaration)
+    type:
+      self: INT64 (Type)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: example (Identifier)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: table_format (Identifier)
+      right:
+        self: 'ICEBERG' (StringLiteral)
+    - self: = (BinaryOperator)
+      left:
+        self: storage_uri (Identifier)
+      right:
+        self: 'gs://bucket/path' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: TABLE (Keyword)
+with_connection:
+  self: WITH (KeywordSequence)
+  next_keyword:
+    self: CONNECTION (KeywordWithExpr)
+    expr:
+      self: DEFAULT (Keyword)
 ",
             0,
         )),
@@ -1269,6 +1594,90 @@ materialized:
   self: MATERIALIZED (Keyword)
 what:
   self: VIEW (Keyword)
+",
+            0,
+        )),
+        // quoted and reserved option keys
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE MATERIALIZED VIEW dataset.mv
+OPTIONS (
+  allow_non_incremental_definition = true,
+  max_staleness = INTERVAL '4:0:0' HOUR TO SECOND,
+  \"friendly_name\" = 'mv',
+  default = 1,
+  range = 'x'
+)
+AS SELECT 1;
+",
+            "\
+self: CREATE (CreateViewStatement)
+as:
+  self: AS (KeywordWithStatement)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 1 (NumericLiteral)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: mv (Identifier)
+materialized:
+  self: MATERIALIZED (Keyword)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: allow_non_incremental_definition (Identifier)
+      right:
+        self: true (BooleanLiteral)
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: max_staleness (Identifier)
+      right:
+        self: INTERVAL (IntervalLiteral)
+        date_part:
+          self: HOUR (Keyword)
+        expr:
+          self: '4:0:0' (StringLiteral)
+        to:
+          self: TO (Keyword)
+        to_date_part:
+          self: SECOND (Keyword)
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: \"friendly_name\" (StringLiteral)
+      right:
+        self: 'mv' (StringLiteral)
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: default (Identifier)
+      right:
+        self: 1 (NumericLiteral)
+    - self: = (BinaryOperator)
+      left:
+        self: range (Identifier)
+      right:
+        self: 'x' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: VIEW (Keyword)
 ",
             0,
         )),
@@ -1450,6 +1859,75 @@ what:
 ",
             0,
         )),
+        // templated parameters without RETURNS
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE TEMP FUNCTION f(arr ANY TYPE) AS ((SELECT SUM(x) FROM UNNEST(arr) AS x));
+",
+            "\
+self: CREATE (CreateFunctionStatement)
+as:
+  self: AS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExpr)
+    expr:
+      self: ( (GroupedStatement)
+      rparen:
+        self: ) (Symbol)
+      stmt:
+        self: SELECT (SelectStatement)
+        exprs:
+        - self: ( (CallingFunction)
+          args:
+          - self: x (Identifier)
+          func:
+            self: SUM (Identifier)
+          rparen:
+            self: ) (Symbol)
+        from:
+          self: FROM (KeywordWithExpr)
+          expr:
+            self: ( (CallingUnnest)
+            alias:
+              self: x (Identifier)
+            args:
+            - self: arr (Identifier)
+            as:
+              self: AS (Keyword)
+            func:
+              self: UNNEST (Identifier)
+            rparen:
+              self: ) (Symbol)
+    rparen:
+      self: ) (Symbol)
+group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: arr (TypeDeclaration)
+    type:
+      self: ANY (Type)
+      type:
+        self: TYPE (Keyword)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: f (Identifier)
+semicolon:
+  self: ; (Symbol)
+temp:
+  self: TEMP (Keyword)
+what:
+  self: FUNCTION (Keyword)
+",
+            0,
+        )),
+        Box::new(ErrorTestCase::new(
+            "\
+CREATE FUNCTION f(x ANY INT64) AS (x);
+",
+            1,
+            25,
+        )),
         // javascript function definition
         Box::new(SuccessTestCase::new(
             "\
@@ -1576,108 +2054,279 @@ what:
 ",
             0,
         )),
-        // remote function
+        // remote function
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE FUNCTION dataset.abc()
+RETURNS INT64
+REMOTE WITH CONNECTION `project.us.connection`
+OPTIONS (endpoint = 'https://region-project.cloudfunctions.net/function')
+",
+            "\
+self: CREATE (CreateFunctionStatement)
+group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: abc (Identifier)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      left:
+        self: endpoint (Identifier)
+      right:
+        self: 'https://region-project.cloudfunctions.net/function' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+remote:
+  self: REMOTE (KeywordSequence)
+  next_keyword:
+    self: WITH (KeywordSequence)
+    next_keyword:
+      self: CONNECTION (KeywordWithExpr)
+      expr:
+        self: `project.us.connection` (Identifier)
+returns:
+  self: RETURNS (KeywordWithType)
+  type:
+    self: INT64 (Type)
+what:
+  self: FUNCTION (Keyword)
+",
+            0,
+        )),
+        // TVF
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE TABLE FUNCTION one(x INT64)
+RETURNS TABLE<one INT64>
+AS SELECT 1 AS one
+",
+            "\
+self: CREATE (CreateFunctionStatement)
+as:
+  self: AS (KeywordWithStatement)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 1 (NumericLiteral)
+      alias:
+        self: one (Identifier)
+      as:
+        self: AS (Keyword)
+group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: x (TypeDeclaration)
+    type:
+      self: INT64 (Type)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: one (Identifier)
+returns:
+  self: RETURNS (KeywordWithType)
+  type:
+    self: TABLE (Type)
+    type_declaration:
+      self: < (GroupedTypeDeclarationOrConstraints)
+      declarations:
+      - self: one (TypeDeclaration)
+        type:
+          self: INT64 (Type)
+      rparen:
+        self: > (Symbol)
+table:
+  self: TABLE (Keyword)
+what:
+  self: FUNCTION (Keyword)
+",
+            0,
+        )),
+        // python function definition
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE FUNCTION f(x INT64) RETURNS INT64 LANGUAGE python
+OPTIONS (runtime_version = 'python-3.11', entry_point = 'f')
+AS r'''
+def f(x):
+  return x + 1
+''';
+",
+            "\
+self: CREATE (CreateFunctionStatement)
+as:
+  self: AS (KeywordWithExpr)
+  expr:
+    self: r (UnaryOperator)
+    right:
+      self: '''
+def f(x):
+  return x + 1
+''' (StringLiteral)
+group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: x (TypeDeclaration)
+    type:
+      self: INT64 (Type)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: f (Identifier)
+language:
+  self: LANGUAGE (KeywordWithExpr)
+  expr:
+    self: python (Identifier)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: runtime_version (Identifier)
+      right:
+        self: 'python-3.11' (StringLiteral)
+    - self: = (BinaryOperator)
+      left:
+        self: entry_point (Identifier)
+      right:
+        self: 'f' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+returns:
+  self: RETURNS (KeywordWithType)
+  type:
+    self: INT64 (Type)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: FUNCTION (Keyword)
+",
+            0,
+        )),
         Box::new(SuccessTestCase::new(
             "\
-CREATE FUNCTION dataset.abc()
-RETURNS INT64
-REMOTE WITH CONNECTION `project.us.connection`
-OPTIONS (endpoint = 'https://region-project.cloudfunctions.net/function')
+CREATE FUNCTION f(x INT64) RETURNS INT64 LANGUAGE python
+WITH CONNECTION `proj.us.conn`
+OPTIONS (runtime_version = 'python-3.11', entry_point = 'f')
+AS r'''def f(x): return x''';
 ",
             "\
 self: CREATE (CreateFunctionStatement)
+as:
+  self: AS (KeywordWithExpr)
+  expr:
+    self: r (UnaryOperator)
+    right:
+      self: '''def f(x): return x''' (StringLiteral)
 group:
   self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: x (TypeDeclaration)
+    type:
+      self: INT64 (Type)
   rparen:
     self: ) (Symbol)
 ident:
-  self: . (DotOperator)
-  left:
-    self: dataset (Identifier)
-  right:
-    self: abc (Identifier)
+  self: f (Identifier)
+language:
+  self: LANGUAGE (KeywordWithExpr)
+  expr:
+    self: python (Identifier)
 options:
   self: OPTIONS (KeywordWithGroupedXXX)
   group:
     self: ( (GroupedExprs)
     exprs:
     - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
       left:
-        self: endpoint (Identifier)
+        self: runtime_version (Identifier)
       right:
-        self: 'https://region-project.cloudfunctions.net/function' (StringLiteral)
+        self: 'python-3.11' (StringLiteral)
+    - self: = (BinaryOperator)
+      left:
+        self: entry_point (Identifier)
+      right:
+        self: 'f' (StringLiteral)
     rparen:
       self: ) (Symbol)
-remote:
-  self: REMOTE (KeywordSequence)
-  next_keyword:
-    self: WITH (KeywordSequence)
-    next_keyword:
-      self: CONNECTION (KeywordWithExpr)
-      expr:
-        self: `project.us.connection` (Identifier)
 returns:
   self: RETURNS (KeywordWithType)
   type:
     self: INT64 (Type)
+semicolon:
+  self: ; (Symbol)
 what:
   self: FUNCTION (Keyword)
+with_connection:
+  self: WITH (KeywordSequence)
+  next_keyword:
+    self: CONNECTION (KeywordWithExpr)
+    expr:
+      self: `proj.us.conn` (Identifier)
 ",
             0,
         )),
-        // TVF
+        // AGGREGATE function
         Box::new(SuccessTestCase::new(
             "\
-CREATE TABLE FUNCTION one(x INT64)
-RETURNS TABLE<one INT64>
-AS SELECT 1 AS one
+CREATE AGGREGATE FUNCTION plus_one(n int64 not aggregate)
+AS (n + 1)
 ",
             "\
 self: CREATE (CreateFunctionStatement)
+aggregate:
+  self: AGGREGATE (Keyword)
 as:
-  self: AS (KeywordWithStatement)
-  stmt:
-    self: SELECT (SelectStatement)
-    exprs:
-    - self: 1 (NumericLiteral)
-      alias:
-        self: one (Identifier)
-      as:
-        self: AS (Keyword)
+  self: AS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExpr)
+    expr:
+      self: + (BinaryOperator)
+      left:
+        self: n (Identifier)
+      right:
+        self: 1 (NumericLiteral)
+    rparen:
+      self: ) (Symbol)
 group:
   self: ( (GroupedTypeDeclarationOrConstraints)
   declarations:
-  - self: x (TypeDeclaration)
+  - self: n (TypeDeclaration)
     type:
-      self: INT64 (Type)
+      self: int64 (Type)
+      aggregate:
+        self: not (KeywordSequence)
+        next_keyword:
+          self: aggregate (Keyword)
   rparen:
     self: ) (Symbol)
 ident:
-  self: one (Identifier)
-returns:
-  self: RETURNS (KeywordWithType)
-  type:
-    self: TABLE (Type)
-    type_declaration:
-      self: < (GroupedTypeDeclarationOrConstraints)
-      declarations:
-      - self: one (TypeDeclaration)
-        type:
-          self: INT64 (Type)
-      rparen:
-        self: > (Symbol)
-table:
-  self: TABLE (Keyword)
+  self: plus_one (Identifier)
 what:
   self: FUNCTION (Keyword)
 ",
             0,
         )),
-        // AGGREGATE function
         Box::new(SuccessTestCase::new(
             "\
-CREATE AGGREGATE FUNCTION plus_one(n int64 not aggregate)
-AS (n + 1)
+CREATE TEMP AGGREGATE FUNCTION f(x FLOAT64, w INT64 NOT AGGREGATE)
+RETURNS FLOAT64
+AS (SUM(x) * w)
 ",
             "\
 self: CREATE (CreateFunctionStatement)
@@ -1688,27 +2337,44 @@ as:
   group:
     self: ( (GroupedExpr)
     expr:
-      self: + (BinaryOperator)
+      self: * (BinaryOperator)
       left:
-        self: n (Identifier)
+        self: ( (CallingFunction)
+        args:
+        - self: x (Identifier)
+        func:
+          self: SUM (Identifier)
+        rparen:
+          self: ) (Symbol)
       right:
-        self: 1 (NumericLiteral)
+        self: w (Identifier)
     rparen:
       self: ) (Symbol)
 group:
   self: ( (GroupedTypeDeclarationOrConstraints)
   declarations:
-  - self: n (TypeDeclaration)
+  - self: x (TypeDeclaration)
+    comma:
+      self: , (Symbol)
     type:
-      self: int64 (Type)
+      self: FLOAT64 (Type)
+  - self: w (TypeDeclaration)
+    type:
+      self: INT64 (Type)
       aggregate:
-        self: not (KeywordSequence)
+        self: NOT (KeywordSequence)
         next_keyword:
-          self: aggregate (Keyword)
+          self: AGGREGATE (Keyword)
   rparen:
     self: ) (Symbol)
 ident:
-  self: plus_one (Identifier)
+  self: f (Identifier)
+returns:
+  self: RETURNS (KeywordWithType)
+  type:
+    self: FLOAT64 (Type)
+temp:
+  self: TEMP (Keyword)
 what:
   self: FUNCTION (Keyword)
 ",
@@ -2021,6 +2687,58 @@ what:
         )),
         // ----- CREATE RESERVATIONS statement -----
         // CREATE
+        // numeric id
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE ASSIGNMENT admin-project.region-us.prod.123456
+OPTIONS (assignee = 'projects/my-project', job_type = 'QUERY')
+",
+            "\
+self: CREATE (CreateReservationStatement)
+ident:
+  self: . (DotOperator)
+  left:
+    self: . (DotOperator)
+    left:
+      self: . (DotOperator)
+      left:
+        self: admin (MultiTokenIdentifier)
+        trailing_idents:
+        - self: - (Identifier)
+        - self: project (Identifier)
+      right:
+        self: region (MultiTokenIdentifier)
+        trailing_idents:
+        - self: - (Identifier)
+        - self: us (Identifier)
+    right:
+      self: prod (Identifier)
+  right:
+    self: 123456 (Identifier)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: assignee (Identifier)
+      right:
+        self: 'projects/my-project' (StringLiteral)
+    - self: = (BinaryOperator)
+      left:
+        self: job_type (Identifier)
+      right:
+        self: 'QUERY' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+what:
+  self: ASSIGNMENT (Keyword)
+",
+            0,
+        )),
         Box::new(SuccessTestCase::new(
             // may be deprecated
             "\
@@ -3154,6 +3872,30 @@ what:
         )),
         Box::new(SuccessTestCase::new(
             "\
+DROP SNAPSHOT TABLE IF EXISTS dataset.snap;
+",
+            "\
+self: DROP (DropStatement)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: snap (Identifier)
+if_exists:
+- self: IF (Keyword)
+- self: EXISTS (Keyword)
+semicolon:
+  self: ; (Symbol)
+snapshot:
+  self: SNAPSHOT (Keyword)
+what:
+  self: TABLE (Keyword)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
 DROP MATERIALIZED VIEW example;
 ",
             "\
@@ -3351,6 +4093,90 @@ if_not_exists:
 - self: EXISTS (Keyword)
 what:
   self: SCHEMA (Keyword)
+",
+            0,
+        )),
+        // ----- comments before CREATE -----
+        Box::new(SuccessTestCase::new(
+            "\
+-- not doc
+
+-- doc 1
+-- doc 2
+CREATE TABLE t (x INT64);
+",
+            "\
+self: CREATE (CreateTableStatement)
+column_schema_group:
+  self: ( (GroupedTypeDeclarationOrConstraints)
+  declarations:
+  - self: x (TypeDeclaration)
+    type:
+      self: INT64 (Type)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: t (Identifier)
+leading_comments:
+- self: -- not doc (Comment)
+- self: -- doc 1 (Comment)
+- self: -- doc 2 (Comment)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: TABLE (Keyword)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+/**
+ * doc
+ */
+CREATE VIEW v AS SELECT 1;
+",
+            "\
+self: CREATE (CreateViewStatement)
+as:
+  self: AS (KeywordWithStatement)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 1 (NumericLiteral)
+ident:
+  self: v (Identifier)
+leading_comments:
+- self: /**
+ * doc
+ */ (Comment)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: VIEW (Keyword)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+/* not doc */
+CREATE VIEW v AS SELECT 1;
+",
+            "\
+self: CREATE (CreateViewStatement)
+as:
+  self: AS (KeywordWithStatement)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 1 (NumericLiteral)
+ident:
+  self: v (Identifier)
+leading_comments:
+- self: /* not doc */ (Comment)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: VIEW (Keyword)
 ",
             0,
         )),
@@ -3359,3 +4185,82 @@ what:
         t.test();
     }
 }
+
+#[test]
+fn test_create_modifiers() {
+    // (keywords before the name, the rest of the statement, whether TEMP is allowed)
+    let variants = [
+        ("SCHEMA", "", false),
+        (
+            "EXTERNAL SCHEMA",
+            "WITH CONNECTION conn OPTIONS (external_source = 'x')",
+            false,
+        ),
+        ("TABLE", "(x INT64)", true),
+        ("EXTERNAL TABLE", "OPTIONS (format = 'CSV')", true),
+        ("SNAPSHOT TABLE", "CLONE t", false),
+        ("VIEW", "AS SELECT 1", false),
+        ("MATERIALIZED VIEW", "AS SELECT 1", false),
+        ("FUNCTION", "() AS (1)", true),
+        ("TABLE FUNCTION", "() AS SELECT 1", false),
+        ("AGGREGATE FUNCTION", "(x INT64) AS (SUM(x))", true),
+        ("PROCEDURE", "() BEGIN SELECT 1; END", false),
+        ("ROW ACCESS POLICY", "ON t FILTER USING (TRUE)", false),
+        ("SEARCH INDEX", "ON t (ALL COLUMNS)", false),
+        (
+            "VECTOR INDEX",
+            "ON t (x) OPTIONS (index_type = 'IVF')",
+            false,
+        ),
+        (
+            "MODEL",
+            "OPTIONS (model_type = 'linear_reg') AS SELECT 1",
+            false,
+        ),
+        ("RESERVATION", "OPTIONS (slot_capacity = 100)", false),
+        ("ASSIGNMENT", "OPTIONS (assignee = 'projects/p')", false),
+    ];
+    for (what, rest, temp_allowed) in variants {
+        for or_replace in [false, true] {
+            for temp in [false, true] {
+                if temp && !temp_allowed {
+                    continue;
+                }
+                for if_not_exists in [false, true] {
+                    let mut code = "CREATE ".to_string();
+                    if or_replace {
+                        code.push_str("OR REPLACE ");
+                    }
+                    if temp {
+                        code.push_str("TEMP ");
+                    }
+                    code.push_str(what);
+                    if if_not_exists {
+                        code.push_str(" IF NOT EXISTS");
+                    }
+                    code.push_str(" name ");
+                    code.push_str(rest);
+                    let tokens = Lexer::new(code.clone()).tokenize_code().unwrap();
+                    let stmts = Parser::new(tokens)
+                        .parse_code()
+                        .unwrap_or_else(|e| panic!("{}: {:?}", code, e));
+                    let children = &stmts[0].children;
+                    assert_eq!(children.contains_key("or_replace"), or_replace, "{}", code);
+                    assert_eq!(children.contains_key("temp"), temp, "{}", code);
+                    assert_eq!(
+                        children.contains_key("if_not_exists"),
+                        if_not_exists,
+                        "{}",
+                        code
+                    );
+                    match children.get("ident") {
+                        Some(ContentType::Node(n)) => {
+                            assert_eq!(n.token.as_ref().unwrap().literal, "name", "{}", code)
+                        }
+                        _ => panic!("{}: ident not found", code),
+                    }
+                }
+            }
+        }
+    }
+}
@@ -90,6 +90,67 @@ semicolon:
   self: ; (Symbol)
 target_name:
   self: table_name (Identifier)
+",
+            0,
+        )),
+        // DEFAULT
+        Box::new(SuccessTestCase::new(
+            "\
+INSERT INTO t (a, b) VALUES (DEFAULT, 1);
+",
+            "\
+self: INSERT (InsertStatement)
+columns:
+  self: ( (GroupedExprs)
+  exprs:
+  - self: a (Identifier)
+    comma:
+      self: , (Symbol)
+  - self: b (Identifier)
+  rparen:
+    self: ) (Symbol)
+input:
+  self: VALUES (KeywordWithExprs)
+  exprs:
+  - self: ( (GroupedExprs)
+    exprs:
+    - self: DEFAULT (Keyword)
+      comma:
+        self: , (Symbol)
+    - self: 1 (NumericLiteral)
+    rparen:
+      self: ) (Symbol)
+into:
+  self: INTO (Keyword)
+semicolon:
+  self: ; (Symbol)
+target_name:
+  self: t (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+UPDATE t SET a = DEFAULT WHERE TRUE;
+",
+            "\
+self: UPDATE (UpdateStatement)
+semicolon:
+  self: ; (Symbol)
+set:
+  self: SET (KeywordWithExprs)
+  exprs:
+  - self: = (BinaryOperator)
+    left:
+      self: a (Identifier)
+    right:
+      self: DEFAULT (Keyword)
+table_name:
+  self: t (Identifier)
+where:
+  self: WHERE (KeywordWithExpr)
+  expr:
+    self: TRUE (BooleanLiteral)
 ",
             0,
         )),
@@ -127,6 +188,22 @@ where:
   self: WHERE (KeywordWithExpr)
   expr:
     self: TRUE (BooleanLiteral)
+",
+            0,
+        )),
+        // without WHERE (see analysis::dangerous_statements)
+        Box::new(SuccessTestCase::new(
+            "\
+DELETE table_name t;
+",
+            "\
+self: DELETE (DeleteStatement)
+semicolon:
+  self: ; (Symbol)
+table_name:
+  self: table_name (Identifier)
+  alias:
+    self: t (Identifier)
 ",
             0,
         )),
@@ -111,6 +111,48 @@ output:
       self: ) (Symbol)
 what:
   self: MODEL (Keyword)
+",
+            0,
+        )),
+        // default connection
+        Box::new(SuccessTestCase::new(
+            "\
+CREATE MODEL dataset.example
+REMOTE WITH CONNECTION DEFAULT
+OPTIONS (endpoint = 'gemini-2.0-flash');
+",
+            "\
+self: CREATE (CreateModelStatement)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: example (Identifier)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      left:
+        self: endpoint (Identifier)
+      right:
+        self: 'gemini-2.0-flash' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+remote:
+  self: REMOTE (KeywordSequence)
+  next_keyword:
+    self: WITH (KeywordSequence)
+    next_keyword:
+      self: CONNECTION (KeywordWithExpr)
+      expr:
+        self: DEFAULT (Keyword)
+semicolon:
+  self: ; (Symbol)
+what:
+  self: MODEL (Keyword)
 ",
             0,
         )),
@@ -221,6 +263,195 @@ training_data_custom_holiday:
           - self: 1 (NumericLiteral)
 what:
   self: MODEL (Keyword)
+",
+            0,
+        )),
+        // AI and ML functions
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT AI.GENERATE(PROMPT ('Summarize: ', body), CONNECTION DEFAULT).result FROM t;
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: . (DotOperator)
+  left:
+    self: ( (CallingFunction)
+    args:
+    - self: PROMPT (UnaryOperator)
+      comma:
+        self: , (Symbol)
+      right:
+        self: ( (StructLiteral)
+        exprs:
+        - self: 'Summarize: ' (StringLiteral)
+          comma:
+            self: , (Symbol)
+        - self: body (Identifier)
+        rparen:
+          self: ) (Symbol)
+    - self: CONNECTION (UnaryOperator)
+      right:
+        self: DEFAULT (Keyword)
+    func:
+      self: . (DotOperator)
+      left:
+        self: AI (Identifier)
+      right:
+        self: GENERATE (Identifier)
+    rparen:
+      self: ) (Symbol)
+  right:
+    self: result (Identifier)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        // other functions take `prompt` as a usual expression
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT AI.SCORE(prompt (x));
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: ( (CallingFunction)
+  args:
+  - self: ( (CallingFunction)
+    args:
+    - self: x (Identifier)
+    func:
+      self: prompt (Identifier)
+    rparen:
+      self: ) (Symbol)
+  func:
+    self: . (DotOperator)
+    left:
+      self: AI (Identifier)
+    right:
+      self: SCORE (Identifier)
+  rparen:
+    self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT *
+FROM AI.GENERATE_TABLE(
+  MODEL dataset.model,
+  PROMPT (SELECT 'x' AS prompt),
+  STRUCT('a STRING' AS output_schema)
+);
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: ( (CallingTableFunction)
+    args:
+    - self: MODEL (UnaryOperator)
+      comma:
+        self: , (Symbol)
+      right:
+        self: . (DotOperator)
+        left:
+          self: dataset (Identifier)
+        right:
+          self: model (Identifier)
+    - self: PROMPT (UnaryOperator)
+      comma:
+        self: , (Symbol)
+      right:
+        self: ( (GroupedStatement)
+        rparen:
+          self: ) (Symbol)
+        stmt:
+          self: SELECT (SelectStatement)
+          exprs:
+          - self: 'x' (StringLiteral)
+            alias:
+              self: prompt (Identifier)
+            as:
+              self: AS (Keyword)
+    - self: ( (StructLiteral)
+      exprs:
+      - self: 'a STRING' (StringLiteral)
+        alias:
+          self: output_schema (Identifier)
+        as:
+          self: AS (Keyword)
+      rparen:
+        self: ) (Symbol)
+      type:
+        self: STRUCT (Type)
+    func:
+      self: . (DotOperator)
+      left:
+        self: AI (Identifier)
+      right:
+        self: GENERATE_TABLE (Identifier)
+    rparen:
+      self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * FROM ML.GENERATE_EMBEDDING(MODEL m, TABLE t, STRUCT(TRUE AS flatten_json_output));
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: ( (CallingTableFunction)
+    args:
+    - self: MODEL (UnaryOperator)
+      comma:
+        self: , (Symbol)
+      right:
+        self: m (Identifier)
+    - self: TABLE (UnaryOperator)
+      comma:
+        self: , (Symbol)
+      right:
+        self: t (Identifier)
+    - self: ( (StructLiteral)
+      exprs:
+      - self: TRUE (BooleanLiteral)
+        alias:
+          self: flatten_json_output (Identifier)
+        as:
+          self: AS (Keyword)
+      rparen:
+        self: ) (Symbol)
+      type:
+        self: STRUCT (Type)
+    func:
+      self: . (DotOperator)
+      left:
+        self: ML (Identifier)
+      right:
+        self: GENERATE_EMBEDDING (Identifier)
+    rparen:
+      self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
 ",
             0,
         )),
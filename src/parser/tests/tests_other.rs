@@ -81,6 +81,194 @@ with_connection:
     self: CONNECTION (KeywordWithExpr)
     expr:
       self: conn (Identifier)
+",
+            0,
+        )),
+        // default connection
+        Box::new(SuccessTestCase::new(
+            "\
+EXPORT DATA WITH CONNECTION DEFAULT
+OPTIONS (uri = 'gs://bucket/*.csv', format = 'CSV')
+AS SELECT 1;
+",
+            "\
+self: EXPORT (ExportDataStatement)
+as:
+  self: AS (KeywordWithStatement)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 1 (NumericLiteral)
+data:
+  self: DATA (Keyword)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: uri (Identifier)
+      right:
+        self: 'gs://bucket/*.csv' (StringLiteral)
+    - self: = (BinaryOperator)
+      left:
+        self: format (Identifier)
+      right:
+        self: 'CSV' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+with_connection:
+  self: WITH (KeywordSequence)
+  next_keyword:
+    self: CONNECTION (KeywordWithExpr)
+    expr:
+      self: DEFAULT (Keyword)
+",
+            0,
+        )),
+        // continuous query
+        Box::new(SuccessTestCase::new(
+            "\
+EXPORT DATA OPTIONS (format = 'CLOUD_PUBSUB', uri = 'https://pubsub.googleapis.com/projects/p/topics/t')
+AS (
+  SELECT TO_JSON_STRING(STRUCT(a)) AS message
+  FROM APPENDS(TABLE dataset.t, CURRENT_TIMESTAMP() - INTERVAL 10 MINUTE)
+);
+",
+            "\
+self: EXPORT (ExportDataStatement)
+as:
+  self: AS (KeywordWithStatement)
+  stmt:
+    self: ( (GroupedStatement)
+    rparen:
+      self: ) (Symbol)
+    stmt:
+      self: SELECT (SelectStatement)
+      exprs:
+      - self: ( (CallingFunction)
+        alias:
+          self: message (Identifier)
+        args:
+        - self: ( (StructLiteral)
+          exprs:
+          - self: a (Identifier)
+          rparen:
+            self: ) (Symbol)
+          type:
+            self: STRUCT (Type)
+        as:
+          self: AS (Keyword)
+        func:
+          self: TO_JSON_STRING (Identifier)
+        rparen:
+          self: ) (Symbol)
+      from:
+        self: FROM (KeywordWithExpr)
+        expr:
+          self: ( (CallingTableFunction)
+          args:
+          - self: TABLE (UnaryOperator)
+            comma:
+              self: , (Symbol)
+            right:
+              self: . (DotOperator)
+              left:
+                self: dataset (Identifier)
+              right:
+                self: t (Identifier)
+          - self: - (BinaryOperator)
+            left:
+              self: ( (CallingFunction)
+              func:
+                self: CURRENT_TIMESTAMP (Identifier)
+              rparen:
+                self: ) (Symbol)
+            right:
+              self: INTERVAL (IntervalLiteral)
+              date_part:
+                self: MINUTE (Keyword)
+              expr:
+                self: 10 (NumericLiteral)
+          func:
+            self: APPENDS (Identifier)
+          rparen:
+            self: ) (Symbol)
+data:
+  self: DATA (Keyword)
+options:
+  self: OPTIONS (KeywordWithGroupedXXX)
+  group:
+    self: ( (GroupedExprs)
+    exprs:
+    - self: = (BinaryOperator)
+      comma:
+        self: , (Symbol)
+      left:
+        self: format (Identifier)
+      right:
+        self: 'CLOUD_PUBSUB' (StringLiteral)
+    - self: = (BinaryOperator)
+      left:
+        self: uri (Identifier)
+      right:
+        self: 'https://pubsub.googleapis.com/projects/p/topics/t' (StringLiteral)
+    rparen:
+      self: ) (Symbol)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+BEGIN
+  EXPORT DATA OPTIONS (uri = 'gs://bucket/*.csv') AS SELECT 1 UNION ALL SELECT 2;
+END;
+",
+            "\
+self: BEGIN (BeginStatement)
+end:
+  self: END (Keyword)
+semicolon:
+  self: ; (Symbol)
+stmts:
+- self: EXPORT (ExportDataStatement)
+  as:
+    self: AS (KeywordWithStatement)
+    stmt:
+      self: UNION (SetOperator)
+      distinct_or_all:
+        self: ALL (Keyword)
+      left:
+        self: SELECT (SelectStatement)
+        exprs:
+        - self: 1 (NumericLiteral)
+      right:
+        self: SELECT (SelectStatement)
+        exprs:
+        - self: 2 (NumericLiteral)
+  data:
+    self: DATA (Keyword)
+  options:
+    self: OPTIONS (KeywordWithGroupedXXX)
+    group:
+      self: ( (GroupedExprs)
+      exprs:
+      - self: = (BinaryOperator)
+        left:
+          self: uri (Identifier)
+        right:
+          self: 'gs://bucket/*.csv' (StringLiteral)
+      rparen:
+        self: ) (Symbol)
+  semicolon:
+    self: ; (Symbol)
 ",
             0,
         )),
@@ -254,6 +442,54 @@ with_partition_columns:
   partition_columns:
   - self: PARTITION (Keyword)
   - self: COLUMNS (Keyword)
+",
+            0,
+        )),
+        // default connection
+        Box::new(SuccessTestCase::new(
+            "\
+LOAD DATA INTO dataset.example
+FROM FILES (uris = ['gs://bucket/*.csv'])
+WITH CONNECTION DEFAULT;
+",
+            "\
+self: LOAD (LoadStatement)
+connection:
+  self: CONNECTION (Keyword)
+connection_name:
+  self: DEFAULT (Keyword)
+data:
+  self: DATA (Keyword)
+files:
+  self: FILES (Keyword)
+from:
+  self: FROM (Keyword)
+from_files:
+  self: ( (GroupedExprs)
+  exprs:
+  - self: = (BinaryOperator)
+    left:
+      self: uris (Identifier)
+    right:
+      self: [ (ArrayLiteral)
+      exprs:
+      - self: 'gs://bucket/*.csv' (StringLiteral)
+      rparen:
+        self: ] (Symbol)
+  rparen:
+    self: ) (Symbol)
+ident:
+  self: . (DotOperator)
+  left:
+    self: dataset (Identifier)
+  right:
+    self: example (Identifier)
+into:
+  self: INTO (Keyword)
+semicolon:
+  self: ; (Symbol)
+with:
+  self: WITH (Keyword)
 ",
             0,
         )),
@@ -82,6 +82,89 @@ variable_type:
     - self: 2 (NumericLiteral)
     rparen:
       self: ) (Symbol)
+",
+            0,
+        )),
+        // type inference from a subquery
+        Box::new(SuccessTestCase::new(
+            "\
+DECLARE x DEFAULT (SELECT MAX(col) FROM t);
+",
+            "\
+self: DECLARE (DeclareStatement)
+default:
+  self: DEFAULT (KeywordWithExpr)
+  expr:
+    self: ( (GroupedStatement)
+    rparen:
+      self: ) (Symbol)
+    stmt:
+      self: SELECT (SelectStatement)
+      exprs:
+      - self: ( (CallingFunction)
+        args:
+        - self: col (Identifier)
+        func:
+          self: MAX (Identifier)
+        rparen:
+          self: ) (Symbol)
+      from:
+        self: FROM (KeywordWithExpr)
+        expr:
+          self: t (Identifier)
+idents:
+- self: x (Identifier)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+DECLARE x ARRAY<STRING> DEFAULT (SELECT ARRAY_AGG(col) FROM t) || ['a'];
+",
+            "\
+self: DECLARE (DeclareStatement)
+default:
+  self: DEFAULT (KeywordWithExpr)
+  expr:
+    self: || (BinaryOperator)
+    left:
+      self: ( (GroupedStatement)
+      rparen:
+        self: ) (Symbol)
+      stmt:
+        self: SELECT (SelectStatement)
+        exprs:
+        - self: ( (CallingFunction)
+          args:
+          - self: col (Identifier)
+          func:
+            self: ARRAY_AGG (Identifier)
+          rparen:
+            self: ) (Symbol)
+        from:
+          self: FROM (KeywordWithExpr)
+          expr:
+            self: t (Identifier)
+    right:
+      self: [ (ArrayLiteral)
+      exprs:
+      - self: 'a' (StringLiteral)
+      rparen:
+        self: ] (Symbol)
+idents:
+- self: x (Identifier)
+semicolon:
+  self: ; (Symbol)
+variable_type:
+  self: ARRAY (Type)
+  type_declaration:
+    self: < (GroupedType)
+    rparen:
+      self: > (Symbol)
+    type:
+      self: STRING (Type)
 ",
             0,
         )),
@@ -162,6 +245,41 @@ expr:
         comma:
           self: , (Symbol)
       - self: 2 (NumericLiteral)
+",
+            0,
+        )),
+        // system variables
+        Box::new(SuccessTestCase::new(
+            "\
+SET @@dataset_id = 'mydataset';
+",
+            "\
+self: SET (SetSystemVariableStatement)
+expr:
+  self: = (BinaryOperator)
+  left:
+    self: @@dataset_id (Parameter)
+  right:
+    self: 'mydataset' (StringLiteral)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SET @@time_zone = 'Asia/Tokyo';
+",
+            "\
+self: SET (SetSystemVariableStatement)
+expr:
+  self: = (BinaryOperator)
+  left:
+    self: @@time_zone (Parameter)
+  right:
+    self: 'Asia/Tokyo' (StringLiteral)
+semicolon:
+  self: ; (Symbol)
 ",
             0,
         )),
@@ -334,6 +452,77 @@ semicolon:
 then:
   self: THEN (KeywordWithStatements)
   stmts: []
+",
+            0,
+        )),
+        // empty block
+        Box::new(SuccessTestCase::new(
+            "\
+BEGIN END;
+",
+            "\
+self: BEGIN (BeginStatement)
+end:
+  self: END (Keyword)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        // nested blocks
+        Box::new(SuccessTestCase::new(
+            "\
+BEGIN
+  DECLARE x INT64;
+  BEGIN
+    DECLARE y INT64;
+    BEGIN
+      SELECT x, y;
+    END;
+  END;
+END;
+",
+            "\
+self: BEGIN (BeginStatement)
+end:
+  self: END (Keyword)
+semicolon:
+  self: ; (Symbol)
+stmts:
+- self: DECLARE (DeclareStatement)
+  idents:
+  - self: x (Identifier)
+  semicolon:
+    self: ; (Symbol)
+  variable_type:
+    self: INT64 (Type)
+- self: BEGIN (BeginStatement)
+  end:
+    self: END (Keyword)
+  semicolon:
+    self: ; (Symbol)
+  stmts:
+  - self: DECLARE (DeclareStatement)
+    idents:
+    - self: y (Identifier)
+    semicolon:
+      self: ; (Symbol)
+    variable_type:
+      self: INT64 (Type)
+  - self: BEGIN (BeginStatement)
+    end:
+      self: END (Keyword)
+    semicolon:
+      self: ; (Symbol)
+    stmts:
+    - self: SELECT (SelectStatement)
+      exprs:
+      - self: x (Identifier)
+        comma:
+          self: , (Symbol)
+      - self: y (Identifier)
+      semicolon:
+        self: ; (Symbol)
 ",
             0,
         )),
@@ -359,6 +548,88 @@ stmts:
   - self: 1 (NumericLiteral)
   semicolon:
     self: ; (Symbol)
+",
+            0,
+        )),
+        // session-scoped objects
+        Box::new(SuccessTestCase::new(
+            "\
+BEGIN
+  CREATE TEMP TABLE t AS SELECT 1 AS x;
+  CREATE TEMPORARY FUNCTION f(x INT64) AS (x + 1);
+  SELECT f(x) FROM t;
+END;
+",
+            "\
+self: BEGIN (BeginStatement)
+end:
+  self: END (Keyword)
+semicolon:
+  self: ; (Symbol)
+stmts:
+- self: CREATE (CreateTableStatement)
+  as:
+    self: AS (KeywordWithStatement)
+    stmt:
+      self: SELECT (SelectStatement)
+      exprs:
+      - self: 1 (NumericLiteral)
+        alias:
+          self: x (Identifier)
+        as:
+          self: AS (Keyword)
+  ident:
+    self: t (Identifier)
+  semicolon:
+    self: ; (Symbol)
+  temp:
+    self: TEMP (Keyword)
+  what:
+    self: TABLE (Keyword)
+- self: CREATE (CreateFunctionStatement)
+  as:
+    self: AS (KeywordWithGroupedXXX)
+    group:
+      self: ( (GroupedExpr)
+      expr:
+        self: + (BinaryOperator)
+        left:
+          self: x (Identifier)
+        right:
+          self: 1 (NumericLiteral)
+      rparen:
+        self: ) (Symbol)
+  group:
+    self: ( (GroupedTypeDeclarationOrConstraints)
+    declarations:
+    - self: x (TypeDeclaration)
+      type:
+        self: INT64 (Type)
+    rparen:
+      self: ) (Symbol)
+  ident:
+    self: f (Identifier)
+  semicolon:
+    self: ; (Symbol)
+  temp:
+    self: TEMPORARY (Keyword)
+  what:
+    self: FUNCTION (Keyword)
+- self: SELECT (SelectStatement)
+  exprs:
+  - self: ( (CallingFunction)
+    args:
+    - self: x (Identifier)
+    func:
+      self: f (Identifier)
+    rparen:
+      self: ) (Symbol)
+  from:
+    self: FROM (KeywordWithExpr)
+    expr:
+      self: t (Identifier)
+  semicolon:
+    self: ; (Symbol)
 ",
             0,
         )),
@@ -2,7 +2,7 @@ use super::*;
 
 #[test]
 fn test_parse_code_select() {
-    let test_cases = vec![
+    let test_cases: Vec<Box<dyn TestCase>> = vec![
         Box::new(SuccessTestCase::new(
             "\
 SELECT 1;
@@ -212,6 +212,174 @@ right:
       - self: 3 (NumericLiteral)
 semicolon:
   self: ; (Symbol)
+",
+            0,
+        )),
+        // INTERSECT binds more tightly than UNION and EXCEPT
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT 1 UNION ALL SELECT 2 INTERSECT DISTINCT SELECT 3 ORDER BY 1;
+",
+            "\
+self: UNION (SetOperator)
+distinct_or_all:
+  self: ALL (Keyword)
+left:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: 1 (NumericLiteral)
+orderby:
+  self: ORDER (XXXByExprs)
+  by:
+    self: BY (Keyword)
+  exprs:
+  - self: 1 (NumericLiteral)
+right:
+  self: INTERSECT (SetOperator)
+  distinct_or_all:
+    self: DISTINCT (Keyword)
+  left:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 2 (NumericLiteral)
+  right:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 3 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        // parentheses take precedence
+        Box::new(SuccessTestCase::new(
+            "\
+(SELECT 1 UNION ALL SELECT 2) INTERSECT DISTINCT SELECT 3;
+",
+            "\
+self: INTERSECT (SetOperator)
+distinct_or_all:
+  self: DISTINCT (Keyword)
+left:
+  self: ( (GroupedStatement)
+  rparen:
+    self: ) (Symbol)
+  stmt:
+    self: UNION (SetOperator)
+    distinct_or_all:
+      self: ALL (Keyword)
+    left:
+      self: SELECT (SelectStatement)
+      exprs:
+      - self: 1 (NumericLiteral)
+    right:
+      self: SELECT (SelectStatement)
+      exprs:
+      - self: 2 (NumericLiteral)
+right:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: 3 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        // ORDER BY and LIMIT belong to the whole set operation
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT 1 UNION ALL SELECT 2 ORDER BY 1 LIMIT 1;
+",
+            "\
+self: UNION (SetOperator)
+distinct_or_all:
+  self: ALL (Keyword)
+left:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: 1 (NumericLiteral)
+limit:
+  self: LIMIT (LimitClause)
+  expr:
+    self: 1 (NumericLiteral)
+orderby:
+  self: ORDER (XXXByExprs)
+  by:
+    self: BY (Keyword)
+  exprs:
+  - self: 1 (NumericLiteral)
+right:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: 2 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+(SELECT 1 ORDER BY x LIMIT 1)
+UNION ALL SELECT 2
+UNION ALL (SELECT 3 ORDER BY x LIMIT 1)
+ORDER BY y;
+",
+            "\
+self: UNION (SetOperator)
+distinct_or_all:
+  self: ALL (Keyword)
+left:
+  self: UNION (SetOperator)
+  distinct_or_all:
+    self: ALL (Keyword)
+  left:
+    self: ( (GroupedStatement)
+    rparen:
+      self: ) (Symbol)
+    stmt:
+      self: SELECT (SelectStatement)
+      exprs:
+      - self: 1 (NumericLiteral)
+      limit:
+        self: LIMIT (LimitClause)
+        expr:
+          self: 1 (NumericLiteral)
+      orderby:
+        self: ORDER (XXXByExprs)
+        by:
+          self: BY (Keyword)
+        exprs:
+        - self: x (Identifier)
+  right:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 2 (NumericLiteral)
+orderby:
+  self: ORDER (XXXByExprs)
+  by:
+    self: BY (Keyword)
+  exprs:
+  - self: y (Identifier)
+right:
+  self: ( (GroupedStatement)
+  rparen:
+    self: ) (Symbol)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: 3 (NumericLiteral)
+    limit:
+      self: LIMIT (LimitClause)
+      expr:
+        self: 1 (NumericLiteral)
+    orderby:
+      self: ORDER (XXXByExprs)
+      by:
+        self: BY (Keyword)
+      exprs:
+      - self: x (Identifier)
+semicolon:
+  self: ; (Symbol)
 ",
             0,
         )),
@@ -345,39 +513,240 @@ UNION ALL
 SELECT 3
 ",
             "\
-self: UNION (SetOperator)
-distinct_or_all:
-  self: ALL (Keyword)
-left:
-  self: SELECT (SelectStatement)
-  exprs:
-  - self: 1 (NumericLiteral)
-  from:
-    self: FROM (KeywordWithExpr)
-    expr:
+self: UNION (SetOperator)
+distinct_or_all:
+  self: ALL (Keyword)
+left:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: 1 (NumericLiteral)
+  from:
+    self: FROM (KeywordWithExpr)
+    expr:
+      self: ( (GroupedStatement)
+      rparen:
+        self: ) (Symbol)
+      stmt:
+        self: SELECT (SelectStatement)
+        exprs:
+        - self: 2 (NumericLiteral)
+right:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: 3 (NumericLiteral)
+",
+            0,
+        )),
+        // EXCEPT as both a modifier of `*` and a set operator
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * EXCEPT(a) FROM x EXCEPT DISTINCT SELECT * EXCEPT (b) FROM y
+",
+            "\
+self: EXCEPT (SetOperator)
+distinct_or_all:
+  self: DISTINCT (Keyword)
+left:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: * (Asterisk)
+    except:
+      self: EXCEPT (KeywordWithGroupedXXX)
+      group:
+        self: ( (GroupedExprs)
+        exprs:
+        - self: a (Identifier)
+        rparen:
+          self: ) (Symbol)
+  from:
+    self: FROM (KeywordWithExpr)
+    expr:
+      self: x (Identifier)
+right:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: * (Asterisk)
+    except:
+      self: EXCEPT (KeywordWithGroupedXXX)
+      group:
+        self: ( (GroupedExprs)
+        exprs:
+        - self: b (Identifier)
+        rparen:
+          self: ) (Symbol)
+  from:
+    self: FROM (KeywordWithExpr)
+    expr:
+      self: y (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * EXCEPT DISTINCT SELECT t.* EXCEPT(a) FROM t
+",
+            "\
+self: EXCEPT (SetOperator)
+distinct_or_all:
+  self: DISTINCT (Keyword)
+left:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: * (Asterisk)
+right:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: . (DotOperator)
+    left:
+      self: t (Identifier)
+    right:
+      self: * (Asterisk)
+      except:
+        self: EXCEPT (KeywordWithGroupedXXX)
+        group:
+          self: ( (GroupedExprs)
+          exprs:
+          - self: a (Identifier)
+          rparen:
+            self: ) (Symbol)
+  from:
+    self: FROM (KeywordWithExpr)
+    expr:
+      self: t (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * EXCEPT(a) REPLACE (1 AS b) EXCEPT DISTINCT SELECT 2
+",
+            "\
+self: EXCEPT (SetOperator)
+distinct_or_all:
+  self: DISTINCT (Keyword)
+left:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: * (Asterisk)
+    except:
+      self: EXCEPT (KeywordWithGroupedXXX)
+      group:
+        self: ( (GroupedExprs)
+        exprs:
+        - self: a (Identifier)
+        rparen:
+          self: ) (Symbol)
+    replace:
+      self: REPLACE (KeywordWithGroupedXXX)
+      group:
+        self: ( (GroupedExprs)
+        exprs:
+        - self: 1 (NumericLiteral)
+          alias:
+            self: b (Identifier)
+          as:
+            self: AS (Keyword)
+        rparen:
+          self: ) (Symbol)
+right:
+  self: SELECT (SelectStatement)
+  exprs:
+  - self: 2 (NumericLiteral)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+(SELECT * EXCEPT(a) FROM t) EXCEPT DISTINCT (SELECT * EXCEPT(a) FROM u)
+",
+            "\
+self: EXCEPT (SetOperator)
+distinct_or_all:
+  self: DISTINCT (Keyword)
+left:
+  self: ( (GroupedStatement)
+  rparen:
+    self: ) (Symbol)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: * (Asterisk)
+      except:
+        self: EXCEPT (KeywordWithGroupedXXX)
+        group:
+          self: ( (GroupedExprs)
+          exprs:
+          - self: a (Identifier)
+          rparen:
+            self: ) (Symbol)
+    from:
+      self: FROM (KeywordWithExpr)
+      expr:
+        self: t (Identifier)
+right:
+  self: ( (GroupedStatement)
+  rparen:
+    self: ) (Symbol)
+  stmt:
+    self: SELECT (SelectStatement)
+    exprs:
+    - self: * (Asterisk)
+      except:
+        self: EXCEPT (KeywordWithGroupedXXX)
+        group:
+          self: ( (GroupedExprs)
+          exprs:
+          - self: a (Identifier)
+          rparen:
+            self: ) (Symbol)
+    from:
+      self: FROM (KeywordWithExpr)
+      expr:
+        self: u (Identifier)
+",
+            0,
+        )),
+        // ----- WITH clause -----
+        Box::new(SuccessTestCase::new(
+            "\
+WITH a AS (SELECT 1) SELECT 2;
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: 2 (NumericLiteral)
+semicolon:
+  self: ; (Symbol)
+with:
+  self: WITH (WithClause)
+  queries:
+  - self: a (WithQuery)
+    as:
+      self: AS (Keyword)
+    stmt:
       self: ( (GroupedStatement)
       rparen:
         self: ) (Symbol)
       stmt:
         self: SELECT (SelectStatement)
         exprs:
-        - self: 2 (NumericLiteral)
-right:
-  self: SELECT (SelectStatement)
-  exprs:
-  - self: 3 (NumericLiteral)
+        - self: 1 (NumericLiteral)
 ",
             0,
         )),
-        // ----- WITH clause -----
+        // column list
         Box::new(SuccessTestCase::new(
             "\
-WITH a AS (SELECT 1) SELECT 2;
+WITH a(x, y) AS (SELECT 1, 2) SELECT x FROM a;
 ",
             "\
 self: SELECT (SelectStatement)
 exprs:
-- self: 2 (NumericLiteral)
+- self: x (Identifier)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: a (Identifier)
 semicolon:
   self: ; (Symbol)
 with:
@@ -386,6 +755,15 @@ with:
   - self: a (WithQuery)
     as:
       self: AS (Keyword)
+    columns:
+      self: ( (GroupedExprs)
+      exprs:
+      - self: x (Identifier)
+        comma:
+          self: , (Symbol)
+      - self: y (Identifier)
+      rparen:
+        self: ) (Symbol)
     stmt:
       self: ( (GroupedStatement)
       rparen:
@@ -394,6 +772,9 @@ with:
         self: SELECT (SelectStatement)
         exprs:
         - self: 1 (NumericLiteral)
+          comma:
+            self: , (Symbol)
+        - self: 2 (NumericLiteral)
 ",
             0,
         )),
@@ -782,6 +1163,86 @@ exprs:
     self: ) (Symbol)
   type:
     self: STRUCT (Type)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT ARRAY(SELECT DISTINCT AS STRUCT a, b FROM t)
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: ( (CallingFunction)
+  args:
+  - self: SELECT (SelectStatement)
+    as_struct_or_value:
+    - self: AS (Keyword)
+    - self: STRUCT (Keyword)
+    distinct_or_all:
+      self: DISTINCT (Keyword)
+    exprs:
+    - self: a (Identifier)
+      comma:
+        self: , (Symbol)
+    - self: b (Identifier)
+    from:
+      self: FROM (KeywordWithExpr)
+      expr:
+        self: t (Identifier)
+  func:
+    self: ARRAY (Identifier)
+  rparen:
+    self: ) (Symbol)
+",
+            0,
+        )),
+        Box::new(ErrorTestCase::new(
+            "\
+SELECT AS foo 1
+",
+            1,
+            11,
+        )),
+        // DISTINCT, ALL
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT DISTINCT(a), b FROM t
+",
+            "\
+self: SELECT (SelectStatement)
+distinct_or_all:
+  self: DISTINCT (Keyword)
+exprs:
+- self: ( (GroupedExpr)
+  comma:
+    self: , (Symbol)
+  expr:
+    self: a (Identifier)
+  rparen:
+    self: ) (Symbol)
+- self: b (Identifier)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT ALL * FROM t
+",
+            "\
+self: SELECT (SelectStatement)
+distinct_or_all:
+  self: ALL (Keyword)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
 ",
             0,
         )),
@@ -1481,18 +1942,219 @@ from:
         in:
           self: IN (KeywordWithGroupedXXX)
           group:
-            self: ( (GroupedExprs)
-            exprs:
-            - self: v1 (Identifier)
-              comma:
-                self: , (Symbol)
-              row_value_alias:
-                self: 1 (NumericLiteral)
-            - self: v2 (Identifier)
-              as:
-                self: AS (Keyword)
-              row_value_alias:
-                self: 2 (NumericLiteral)
+            self: ( (GroupedExprs)
+            exprs:
+            - self: v1 (Identifier)
+              comma:
+                self: , (Symbol)
+              row_value_alias:
+                self: 1 (NumericLiteral)
+            - self: v2 (Identifier)
+              as:
+                self: AS (Keyword)
+              row_value_alias:
+                self: 2 (NumericLiteral)
+            rparen:
+              self: ) (Symbol)
+        rparen:
+          self: ) (Symbol)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * FROM t UNPIVOT INCLUDE NULLS (
+  (c1, c2)
+  FOR v
+  IN ((v1, v2) AS 'A', (v3, v4) 'B')
+) AS unpivot
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
+    unpivot:
+      self: UNPIVOT (UnpivotOperator)
+      alias:
+        self: unpivot (Identifier)
+      as:
+        self: AS (Keyword)
+      config:
+        self: ( (UnpivotConfig)
+        expr:
+          self: ( (GroupedExprs)
+          exprs:
+          - self: c1 (Identifier)
+            comma:
+              self: , (Symbol)
+          - self: c2 (Identifier)
+          rparen:
+            self: ) (Symbol)
+        for:
+          self: FOR (KeywordWithExpr)
+          expr:
+            self: v (Identifier)
+        in:
+          self: IN (KeywordWithGroupedXXX)
+          group:
+            self: ( (GroupedExprs)
+            exprs:
+            - self: ( (GroupedExprs)
+              as:
+                self: AS (Keyword)
+              comma:
+                self: , (Symbol)
+              exprs:
+              - self: v1 (Identifier)
+                comma:
+                  self: , (Symbol)
+              - self: v2 (Identifier)
+              row_value_alias:
+                self: 'A' (StringLiteral)
+              rparen:
+                self: ) (Symbol)
+            - self: ( (GroupedExprs)
+              exprs:
+              - self: v3 (Identifier)
+                comma:
+                  self: , (Symbol)
+              - self: v4 (Identifier)
+              row_value_alias:
+                self: 'B' (StringLiteral)
+              rparen:
+                self: ) (Symbol)
+            rparen:
+              self: ) (Symbol)
+        rparen:
+          self: ) (Symbol)
+      include_or_exclude_nulls:
+      - self: INCLUDE (Keyword)
+      - self: NULLS (Keyword)
+",
+            0,
+        )),
+        // MATCH_RECOGNIZE
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT *
+FROM t MATCH_RECOGNIZE (
+  PARTITION BY a
+  ORDER BY b
+  MEASURES FIRST(x.b) AS first_b, MATCH_NUMBER() AS n
+  AFTER MATCH SKIP PAST LAST ROW
+  PATTERN (^ x{2,} (y | z)+? $)
+  DEFINE x AS b > 0, y AS b < 0
+) AS m
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
+    match_recognize:
+      self: MATCH_RECOGNIZE (MatchRecognizeClause)
+      alias:
+        self: m (Identifier)
+      as:
+        self: AS (Keyword)
+      config:
+        self: ( (MatchRecognizeConfig)
+        after_match_skip:
+        - self: AFTER (Keyword)
+        - self: MATCH (Keyword)
+        - self: SKIP (Keyword)
+        - self: PAST (Keyword)
+        - self: LAST (Keyword)
+        - self: ROW (Keyword)
+        define:
+          self: DEFINE (KeywordWithExprs)
+          exprs:
+          - self: x (PatternDefinition)
+            as:
+              self: AS (Keyword)
+            comma:
+              self: , (Symbol)
+            expr:
+              self: > (BinaryOperator)
+              left:
+                self: b (Identifier)
+              right:
+                self: 0 (NumericLiteral)
+          - self: y (PatternDefinition)
+            as:
+              self: AS (Keyword)
+            expr:
+              self: < (BinaryOperator)
+              left:
+                self: b (Identifier)
+              right:
+                self: 0 (NumericLiteral)
+        measures:
+          self: MEASURES (KeywordWithExprs)
+          exprs:
+          - self: ( (CallingFunction)
+            alias:
+              self: first_b (Identifier)
+            args:
+            - self: . (DotOperator)
+              left:
+                self: x (Identifier)
+              right:
+                self: b (Identifier)
+            as:
+              self: AS (Keyword)
+            comma:
+              self: , (Symbol)
+            func:
+              self: FIRST (Identifier)
+            rparen:
+              self: ) (Symbol)
+          - self: ( (CallingFunction)
+            alias:
+              self: n (Identifier)
+            as:
+              self: AS (Keyword)
+            func:
+              self: MATCH_NUMBER (Identifier)
+            rparen:
+              self: ) (Symbol)
+        orderby:
+          self: ORDER (XXXByExprs)
+          by:
+            self: BY (Keyword)
+          exprs:
+          - self: b (Identifier)
+        partitionby:
+          self: PARTITION (XXXByExprs)
+          by:
+            self: BY (Keyword)
+          exprs:
+          - self: a (Identifier)
+        pattern:
+          self: PATTERN (KeywordWithGroupedXXX)
+          group:
+            self: ( (GroupedPattern)
+            patterns:
+            - self: ^ (Symbol)
+            - self: x (Identifier)
+            - self: {2,} (Symbol)
+            - self: ( (GroupedPattern)
+              patterns:
+              - self: y (Identifier)
+              - self: | (Symbol)
+              - self: z (Identifier)
+              rparen:
+                self: ) (Symbol)
+            - self: + (Symbol)
+            - self: ? (Symbol)
+            - self: $ (Symbol)
             rparen:
               self: ) (Symbol)
         rparen:
@@ -1502,11 +2164,7 @@ from:
         )),
         Box::new(SuccessTestCase::new(
             "\
-SELECT * FROM t UNPIVOT INCLUDE NULLS (
-  (c1, c2)
-  FOR v
-  IN ((v1, v2) AS 'A', (v3, v4) 'B')
-) AS unpivot
+SELECT * FROM t MATCH_RECOGNIZE (PATTERN (a) DEFINE a AS true)
 ",
             "\
 self: SELECT (SelectStatement)
@@ -1516,63 +2174,28 @@ from:
   self: FROM (KeywordWithExpr)
   expr:
     self: t (Identifier)
-    unpivot:
-      self: UNPIVOT (UnpivotOperator)
-      alias:
-        self: unpivot (Identifier)
-      as:
-        self: AS (Keyword)
+    match_recognize:
+      self: MATCH_RECOGNIZE (MatchRecognizeClause)
       config:
-        self: ( (UnpivotConfig)
-        expr:
-          self: ( (GroupedExprs)
+        self: ( (MatchRecognizeConfig)
+        define:
+          self: DEFINE (KeywordWithExprs)
           exprs:
-          - self: c1 (Identifier)
-            comma:
-              self: , (Symbol)
-          - self: c2 (Identifier)
-          rparen:
-            self: ) (Symbol)
-        for:
-          self: FOR (KeywordWithExpr)
-          expr:
-            self: v (Identifier)
-        in:
-          self: IN (KeywordWithGroupedXXX)
+          - self: a (PatternDefinition)
+            as:
+              self: AS (Keyword)
+            expr:
+              self: true (BooleanLiteral)
+        pattern:
+          self: PATTERN (KeywordWithGroupedXXX)
           group:
-            self: ( (GroupedExprs)
-            exprs:
-            - self: ( (GroupedExprs)
-              as:
-                self: AS (Keyword)
-              comma:
-                self: , (Symbol)
-              exprs:
-              - self: v1 (Identifier)
-                comma:
-                  self: , (Symbol)
-              - self: v2 (Identifier)
-              row_value_alias:
-                self: 'A' (StringLiteral)
-              rparen:
-                self: ) (Symbol)
-            - self: ( (GroupedExprs)
-              exprs:
-              - self: v3 (Identifier)
-                comma:
-                  self: , (Symbol)
-              - self: v4 (Identifier)
-              row_value_alias:
-                self: 'B' (StringLiteral)
-              rparen:
-                self: ) (Symbol)
+            self: ( (GroupedPattern)
+            patterns:
+            - self: a (Identifier)
             rparen:
               self: ) (Symbol)
         rparen:
           self: ) (Symbol)
-      include_or_exclude_nulls:
-      - self: INCLUDE (Keyword)
-      - self: NULLS (Keyword)
 ",
             0,
         )),
@@ -1722,6 +2345,90 @@ from:
         self: WITH (WithOffsetClause)
         offset:
           self: OFFSET (Keyword)
+",
+            0,
+        )),
+        // correlated UNNESTs joined by commas, each with its own WITH OFFSET
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * FROM t, UNNEST(t.a) AS x WITH OFFSET AS i, UNNEST(x.b) y WITH OFFSET, UNNEST(y.c) WITH OFFSET j;
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: , (JoinOperator)
+    left:
+      self: , (JoinOperator)
+      left:
+        self: , (JoinOperator)
+        left:
+          self: t (Identifier)
+        right:
+          self: ( (CallingUnnest)
+          alias:
+            self: x (Identifier)
+          args:
+          - self: . (DotOperator)
+            left:
+              self: t (Identifier)
+            right:
+              self: a (Identifier)
+          as:
+            self: AS (Keyword)
+          func:
+            self: UNNEST (Identifier)
+          rparen:
+            self: ) (Symbol)
+          with_offset:
+            self: WITH (WithOffsetClause)
+            alias:
+              self: i (Identifier)
+            as:
+              self: AS (Keyword)
+            offset:
+              self: OFFSET (Keyword)
+      right:
+        self: ( (CallingUnnest)
+        alias:
+          self: y (Identifier)
+        args:
+        - self: . (DotOperator)
+          left:
+            self: x (Identifier)
+          right:
+            self: b (Identifier)
+        func:
+          self: UNNEST (Identifier)
+        rparen:
+          self: ) (Symbol)
+        with_offset:
+          self: WITH (WithOffsetClause)
+          offset:
+            self: OFFSET (Keyword)
+    right:
+      self: ( (CallingUnnest)
+      args:
+      - self: . (DotOperator)
+        left:
+          self: y (Identifier)
+        right:
+          self: c (Identifier)
+      func:
+        self: UNNEST (Identifier)
+      rparen:
+        self: ) (Symbol)
+      with_offset:
+        self: WITH (WithOffsetClause)
+        alias:
+          self: j (Identifier)
+        offset:
+          self: OFFSET (Keyword)
+semicolon:
+  self: ; (Symbol)
 ",
             0,
         )),
@@ -1821,13 +2528,13 @@ from:
         alias:
           self: two (Identifier)
       using:
-        self: ( (CallingFunction)
-        args:
-        - self: col (Identifier)
-        func:
-          self: USING (Identifier)
-        rparen:
-          self: ) (Symbol)
+        self: USING (KeywordWithGroupedXXX)
+        group:
+          self: ( (GroupedExprs)
+          exprs:
+          - self: col (Identifier)
+          rparen:
+            self: ) (Symbol)
     on:
       self: ON (KeywordWithExpr)
       expr:
@@ -1948,6 +2655,128 @@ from:
           self: data3 (Identifier)
       rparen:
         self: ) (Symbol)
+",
+            0,
+        )),
+        // deferred join conditions
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * FROM a JOIN b JOIN c ON b.x = c.x ON a.x = b.x
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: JOIN (JoinOperator)
+    left:
+      self: a (Identifier)
+    on:
+      self: ON (KeywordWithExpr)
+      expr:
+        self: = (BinaryOperator)
+        left:
+          self: . (DotOperator)
+          left:
+            self: a (Identifier)
+          right:
+            self: x (Identifier)
+        right:
+          self: . (DotOperator)
+          left:
+            self: b (Identifier)
+          right:
+            self: x (Identifier)
+    right:
+      self: JOIN (JoinOperator)
+      left:
+        self: b (Identifier)
+      on:
+        self: ON (KeywordWithExpr)
+        expr:
+          self: = (BinaryOperator)
+          left:
+            self: . (DotOperator)
+            left:
+              self: b (Identifier)
+            right:
+              self: x (Identifier)
+          right:
+            self: . (DotOperator)
+            left:
+              self: c (Identifier)
+            right:
+              self: x (Identifier)
+      right:
+        self: c (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * FROM a JOIN b JOIN c ON TRUE
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: JOIN (JoinOperator)
+    left:
+      self: JOIN (JoinOperator)
+      left:
+        self: a (Identifier)
+      right:
+        self: b (Identifier)
+    on:
+      self: ON (KeywordWithExpr)
+      expr:
+        self: TRUE (BooleanLiteral)
+    right:
+      self: c (Identifier)
+",
+            0,
+        )),
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT * FROM (a JOIN b USING (x)) AS ab, c
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: * (Asterisk)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: , (JoinOperator)
+    left:
+      self: ( (GroupedExpr)
+      alias:
+        self: ab (Identifier)
+      as:
+        self: AS (Keyword)
+      expr:
+        self: JOIN (JoinOperator)
+        left:
+          self: a (Identifier)
+        right:
+          self: b (Identifier)
+        using:
+          self: USING (KeywordWithGroupedXXX)
+          group:
+            self: ( (GroupedExprs)
+            exprs:
+            - self: x (Identifier)
+            rparen:
+              self: ) (Symbol)
+      rparen:
+        self: ) (Symbol)
+    right:
+      self: c (Identifier)
 ",
             0,
         )),
@@ -2582,6 +3411,28 @@ limit:
     self: OFFSET (KeywordWithExpr)
     expr:
       self: 10 (NumericLiteral)
+",
+            0,
+        )),
+        // rejected by BigQuery but the sign is kept as UnaryOperator
+        Box::new(SuccessTestCase::new(
+            "\
+SELECT c1 FROM t LIMIT -1
+",
+            "\
+self: SELECT (SelectStatement)
+exprs:
+- self: c1 (Identifier)
+from:
+  self: FROM (KeywordWithExpr)
+  expr:
+    self: t (Identifier)
+limit:
+  self: LIMIT (LimitClause)
+  expr:
+    self: - (UnaryOperator)
+    right:
+      self: 1 (NumericLiteral)
 ",
             0,
         )),
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{Node, NodeType};
+
+// https://cloud.google.com/bigquery/docs/reference/standard-sql/operators
+// The smaller the value is, the more tightly the operator binds.
+
+/// `r''` and `b''`
+pub const PREFIX: usize = 1;
+/// `DATE ''`, `TIMESTAMP ''`, `TABLE t`
+pub const TYPED_LITERAL: usize = 2;
+/// `[]`, `.` and `(` (calling function, which is not mentioned in the documentation)
+pub const ACCESS: usize = 101;
+/// unary `+`, `-` and `~`
+pub const UNARY: usize = 102;
+/// `*`, `/` and `||`
+pub const MULTIPLICATIVE: usize = 103;
+/// binary `+` and `-`
+pub const ADDITIVE: usize = 104;
+/// `<<` and `>>`
+pub const SHIFT: usize = 105;
+pub const BIT_AND: usize = 106;
+pub const BIT_XOR: usize = 107;
+pub const BIT_OR: usize = 108;
+/// `=`, `<`, `LIKE`, `BETWEEN`, `IN`, `IS` and so on
+pub const COMPARISON: usize = 109;
+/// unary `NOT`
+pub const NOT: usize = 110;
+pub const AND: usize = 111;
+pub const OR: usize = 112;
+/// `=>` of named arguments (e.g. `ST_GEOGFROMGEOJSON(x, make_valid => TRUE)`)
+pub const NAMED_ARGUMENT: usize = 200;
+/// `INTERSECT`
+pub const INTERSECT: usize = 301;
+/// `UNION` and `EXCEPT`
+pub const UNION: usize = 302;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Associativity {
+    Left,
+    /// e.g. `a = b = c` is a syntax error in BigQuery
+    /// (it is parsed as `(a = b) = c` like left associative operators though)
+    NonAssociative,
+}
+
+/// Returns the precedence of the binary operator `literal` (case-insensitive).
+/// `NOT` in `NOT LIKE`, `NOT IN` and `NOT BETWEEN` is not an operator by itself.
+pub fn binary_precedence(literal: &str) -> Option<usize> {
+    let precedence = match literal.to_uppercase().as_str() {
+        "(" | "." | "[" => ACCESS,
+        "*" | "/" | "||" => MULTIPLICATIVE,
+        "+" | "-" => ADDITIVE,
+        "<<" | ">>" => SHIFT,
+        "&" => BIT_AND,
+        "^" => BIT_XOR,
+        "|" => BIT_OR,
+        "=" | "<" | ">" | "<=" | ">=" | "!=" | "<>" | "LIKE" | "BETWEEN" | "IN" | "IS" => {
+            COMPARISON
+        }
+        "AND" => AND,
+        "OR" => OR,
+        "=>" => NAMED_ARGUMENT,
+        _ => return None,
+    };
+    Some(precedence)
+}
+
+/// Returns the precedence of the set operator `literal` (case-insensitive).
+/// As in the SQL standard, `INTERSECT` binds more tightly than `UNION` and `EXCEPT`
+/// (BigQuery itself requires parentheses to mix different set operators).
+pub fn set_operator_precedence(literal: &str) -> Option<usize> {
+    match literal.to_uppercase().as_str() {
+        "INTERSECT" => Some(INTERSECT),
+        "UNION" | "EXCEPT" => Some(UNION),
+        _ => None,
+    }
+}
+
+pub fn associativity(precedence: usize) -> Associativity {
+    if precedence == COMPARISON {
+        Associativity::NonAssociative
+    } else {
+        Associativity::Left
+    }
+}
+
+/// Returns the precedence of the operator represented by `node`
+/// (e.g. `BinaryOperator`, `UnaryOperator`, `BetweenOperator`)
+/// or `None` if `node` is not an operator.
+pub fn node_precedence(node: &Node) -> Option<usize> {
+    let literal = node.token.as_ref()?.literal.to_uppercase();
+    match node.node_type {
+        NodeType::BinaryOperator => binary_precedence(&literal),
+        NodeType::BetweenOperator | NodeType::InOperator | NodeType::IsDistinctFromOperator => {
+            Some(COMPARISON)
+        }
+        NodeType::SetOperator => set_operator_precedence(&literal),
+        NodeType::DotOperator | NodeType::AccessOperator | NodeType::CallingFunction => {
+            Some(ACCESS)
+        }
+        NodeType::UnaryOperator => match literal.as_str() {
+            "+" | "-" | "~" => Some(UNARY),
+            "NOT" => Some(NOT),
+            "B" | "R" | "BR" | "RB" => Some(PREFIX),
+            _ => Some(TYPED_LITERAL),
+        },
+        _ => None,
+    }
+}
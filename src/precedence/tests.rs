@@ -0,0 +1,80 @@
+use super::*;
+use crate::cst::ContentType;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[test]
+fn test_binary_precedence() {
+    assert_eq!(binary_precedence("*"), Some(MULTIPLICATIVE));
+    assert_eq!(binary_precedence("like"), Some(COMPARISON));
+    assert_eq!(binary_precedence("OR"), Some(OR));
+    assert_eq!(binary_precedence("NOT"), None);
+    assert_eq!(binary_precedence("=>"), Some(NAMED_ARGUMENT));
+    assert!(binary_precedence("*") < binary_precedence("+"));
+    assert!(binary_precedence("AND") < binary_precedence("OR"));
+    assert_eq!(associativity(COMPARISON), Associativity::NonAssociative);
+    assert_eq!(associativity(ADDITIVE), Associativity::Left);
+}
+
+#[test]
+fn test_node_precedence() {
+    let code = "SELECT a + b * c, NOT x AND y, -1, a BETWEEN 1 AND 2, DATE '2000-01-01', f(x), 1";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let exprs = match stmts[0].children.get("exprs") {
+        Some(ContentType::NodeVec(ns)) => ns,
+        _ => panic!(),
+    };
+    let precedences: Vec<Option<usize>> = exprs.iter().map(node_precedence).collect();
+    assert_eq!(
+        precedences,
+        vec![
+            Some(ADDITIVE),
+            Some(AND),
+            Some(UNARY),
+            Some(COMPARISON),
+            Some(TYPED_LITERAL),
+            Some(ACCESS),
+            None,
+        ]
+    );
+    // `b * c` binds more tightly than `a + ...`
+    let right = match exprs[0].children.get("right") {
+        Some(ContentType::Node(n)) => n,
+        _ => panic!(),
+    };
+    assert_eq!(node_precedence(right), Some(MULTIPLICATIVE));
+    // named arguments
+    let code = "SELECT ST_GEOGFROMGEOJSON(x, make_valid => TRUE)";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let args = match stmts[0].children.get("exprs") {
+        Some(ContentType::NodeVec(ns)) => match ns[0].children.get("args") {
+            Some(ContentType::NodeVec(args)) => args,
+            _ => panic!(),
+        },
+        _ => panic!(),
+    };
+    assert_eq!(node_precedence(&args[1]), Some(NAMED_ARGUMENT));
+}
+
+#[test]
+fn test_set_operator_precedence() {
+    assert_eq!(set_operator_precedence("intersect"), Some(INTERSECT));
+    assert_eq!(
+        set_operator_precedence("EXCEPT"),
+        set_operator_precedence("UNION")
+    );
+    assert!(set_operator_precedence("INTERSECT") < set_operator_precedence("UNION"));
+    assert_eq!(set_operator_precedence("SELECT"), None);
+
+    let code = "SELECT 1 EXCEPT DISTINCT SELECT 2 INTERSECT DISTINCT SELECT 3";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    assert_eq!(node_precedence(&stmts[0]), Some(UNION));
+    let right = match stmts[0].children.get("right") {
+        Some(ContentType::Node(n)) => n,
+        _ => panic!(),
+    };
+    assert_eq!(node_precedence(right), Some(INTERSECT));
+}
@@ -0,0 +1,901 @@
+#[cfg(test)]
+mod tests;
+
+use crate::analysis::{child_nodes, identifier_path};
+use crate::cst::{ContentType, Node, NodeType};
+use crate::diagnostic::Span;
+use crate::literal;
+use crate::parser::token_range;
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+/// Identifies a node by the position (line, column) of its token,
+/// which is unique within a parse result.
+/// `crate::index::NodeIndex::node_at` gives the node back.
+pub type NodeId = (usize, usize);
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum BindingKind {
+    /// `WITH name AS (...)`
+    Cte,
+    /// A table, a subquery or a path in FROM clause (e.g. `t AS x`, `t.arr`).
+    /// The last part of the path is used if there is no alias.
+    TableAlias,
+    /// `UNNEST(...) AS x`
+    Unnest,
+    /// `WITH OFFSET AS x` (`offset` if the alias is omitted)
+    Offset,
+    /// An alias in the select list (e.g. `SELECT a AS x`).
+    ColumnAlias,
+}
+
+/// A name defined in the statement.
+/// `id` points to the alias (or the CTE name), or to the first token of the table path
+/// if the alias is omitted.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Binding {
+    pub id: NodeId,
+    pub kind: BindingKind,
+    pub name: String,
+    pub span: Span,
+}
+
+/// A use of a name.
+/// `id` points to the identifier (the first part if it is a path like `t.col`).
+/// `binding` is `None` if the name is not defined in the statement,
+/// which means it is a table, a column of a table or a variable.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Reference {
+    pub id: NodeId,
+    pub name: String,
+    pub span: Span,
+    pub binding: Option<NodeId>,
+}
+
+#[derive(PartialEq, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Resolution {
+    pub bindings: Vec<Binding>,
+    pub references: Vec<Reference>,
+}
+
+impl Resolution {
+    pub fn binding(&self, id: NodeId) -> Option<&Binding> {
+        self.bindings.iter().find(|b| b.id == id)
+    }
+    /// Returns the references to the binding `id`, which is useful to rename it.
+    pub fn references_to(&self, id: NodeId) -> Vec<&Reference> {
+        self.references
+            .iter()
+            .filter(|r| r.binding == Some(id))
+            .collect()
+    }
+}
+
+/// Links the references in `stmt` to CTEs, aliases in FROM clause (including UNNEST)
+/// and aliases in the select list, following the scoping rules of GoogleSQL.
+/// * CTEs are visible to the following CTEs and the main query
+///   (and to themselves in `WITH RECURSIVE`).
+/// * Aliases in FROM clause are visible to the following FROM items
+///   and to the whole query including subqueries (correlated references).
+/// * Aliases in the select list are visible to GROUP BY, HAVING, QUALIFY and ORDER BY.
+///
+/// Schemas are not known, so a name which is not bound is left unresolved.
+pub fn resolve(stmt: &Node) -> Resolution {
+    let mut resolver = Resolver::default();
+    resolver.visit_statement(stmt);
+    resolver.res.bindings.sort_by_key(|b| b.id);
+    resolver.res.references.sort_by_key(|r| r.id);
+    resolver.res
+}
+
+/// Returns CTEs and aliases in the select list which are never referenced.
+/// * A CTE is unused if it is referenced only by itself (or not at all).
+/// * An alias in the select list is unused if the query is not the output of the statement
+///   (e.g. a CTE or a subquery) and the name appears nowhere else.
+///   Aliases are never reported if `*` is used, because the columns are not known.
+pub fn unused_bindings(stmt: &Node) -> Vec<Binding> {
+    let res = resolve(stmt);
+    let mut bodies = Vec::new();
+    collect_cte_bodies(stmt, &mut bodies);
+    let mut outputs = Vec::new();
+    collect_output_aliases(stmt, &mut outputs);
+    let mut names = Vec::new();
+    collect_names(stmt, &mut names);
+    let star = has_star(stmt);
+    res.bindings
+        .iter()
+        .filter(|b| match b.kind {
+            BindingKind::Cte => {
+                let body = bodies.iter().find(|(id, _)| *id == b.id).map(|(_, r)| *r);
+                !res.references_to(b.id).iter().any(|r| match body {
+                    Some((first, last)) => r.id < first || last < r.id,
+                    None => true,
+                })
+            }
+            BindingKind::ColumnAlias => {
+                !star
+                    && !outputs.contains(&b.id)
+                    && res.references_to(b.id).is_empty()
+                    && !names.iter().any(|n| n.eq_ignore_ascii_case(&b.name))
+            }
+            _ => false,
+        })
+        .cloned()
+        .collect()
+}
+
+/// The columns which `*` (or `t.*`) would produce.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct StarExpansion {
+    pub id: NodeId,
+    pub span: Span,
+    /// `None` if the columns of some source are not known
+    /// (e.g. a table missing in the schemas, UNNEST, a table function).
+    pub columns: Option<Vec<String>>,
+}
+
+/// Expands every `*` in `stmt` using `schemas`, the columns of each table
+/// (the key is the path without backticks, e.g. `dataset.table`).
+/// The columns of CTEs and subqueries are derived from their select lists,
+/// where an expression without an alias is named `f0_`, `f1_`, ... as BigQuery does.
+/// `EXCEPT` and `JOIN ... USING` are taken into account.
+pub fn expand_stars(stmt: &Node, schemas: &BTreeMap<String, Vec<String>>) -> Vec<StarExpansion> {
+    let mut expander = Expander {
+        schemas,
+        ctes: Vec::new(),
+        res: Vec::new(),
+    };
+    expander.visit(stmt);
+    expander.res.sort_by_key(|e| e.id);
+    expander.res
+}
+
+// ----- utilities -----
+#[derive(Default)]
+struct Frame {
+    ctes: Vec<(String, NodeId)>,
+    tables: Vec<(String, NodeId)>,
+    aliases: Vec<(String, NodeId)>,
+}
+
+#[derive(Default)]
+struct Resolver {
+    frames: Vec<Frame>,
+    res: Resolution,
+}
+
+fn find(names: &[(String, NodeId)], name: &str) -> Option<NodeId> {
+    names
+        .iter()
+        .rev()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, id)| *id)
+}
+
+fn position(node: &Node) -> Option<NodeId> {
+    node.token.as_ref().map(|t| (t.line, t.column))
+}
+
+fn is_query(node: &Node) -> bool {
+    matches!(
+        node.node_type,
+        NodeType::SelectStatement | NodeType::SetOperator | NodeType::GroupedStatement
+    )
+}
+
+/// Returns the leftmost identifier of a path (`t` of `t.a.b`).
+fn leftmost_identifier(node: &Node) -> Option<&Node> {
+    match node.node_type {
+        NodeType::Identifier => Some(node),
+        NodeType::DotOperator => match node.children.get("left") {
+            Some(ContentType::Node(n)) => leftmost_identifier(n),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Resolver {
+    fn frame(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("a frame is pushed")
+    }
+    fn lookup_cte(&self, name: &str) -> Option<NodeId> {
+        self.frames.iter().rev().find_map(|f| find(&f.ctes, name))
+    }
+    fn lookup_table(&self, name: &str) -> Option<NodeId> {
+        self.frames.iter().rev().find_map(|f| find(&f.tables, name))
+    }
+    fn bind(&mut self, node: &Node, kind: BindingKind, name: String) {
+        let (id, token) = match (position(node), &node.token) {
+            (Some(id), Some(token)) => (id, token),
+            _ => return,
+        };
+        let entry = (name.clone(), id);
+        match kind {
+            BindingKind::Cte => self.frame().ctes.push(entry),
+            BindingKind::ColumnAlias => self.frame().aliases.push(entry),
+            _ => self.frame().tables.push(entry),
+        }
+        self.res.bindings.push(Binding {
+            id,
+            kind,
+            name,
+            span: Span::from_token(token),
+        });
+    }
+    fn refer(&mut self, node: &Node, name: String, binding: Option<NodeId>) {
+        if let (Some(id), Some(token)) = (position(node), &node.token) {
+            self.res.references.push(Reference {
+                id,
+                name,
+                span: Span::from_token(token),
+                binding,
+            });
+        }
+    }
+    /// Binds the explicit alias of `node` if any.
+    fn bind_alias(&mut self, node: &Node, kind: BindingKind) -> bool {
+        match node.children.get("alias") {
+            Some(ContentType::Node(alias)) => {
+                let name = alias_name(alias);
+                self.bind(alias, kind, name);
+                true
+            }
+            _ => false,
+        }
+    }
+    /// Binds the alias of `WITH OFFSET` of `node` if any.
+    /// Without an alias, the `OFFSET` keyword defines `offset`.
+    fn bind_offset(&mut self, node: &Node) {
+        let with_offset = match node.children.get("with_offset") {
+            Some(ContentType::Node(n)) => n,
+            _ => return,
+        };
+        if !self.bind_alias(with_offset, BindingKind::Offset) {
+            if let Some(ContentType::Node(offset)) = with_offset.children.get("offset") {
+                self.bind(offset, BindingKind::Offset, "offset".to_string());
+            }
+        }
+    }
+
+    fn visit_statement(&mut self, node: &Node) {
+        if is_query(node) {
+            self.visit_query(node);
+            return;
+        }
+        match node.node_type {
+            NodeType::DeleteStatement | NodeType::UpdateStatement | NodeType::MergeStatement => {
+                self.frames.push(Frame::default());
+                for key in ["table_name", "from", "using"] {
+                    if let Some(ContentType::Node(n)) = node.children.get(key) {
+                        self.visit_from_item(n);
+                    }
+                }
+                for (key, child) in child_nodes(node) {
+                    if !["table_name", "from", "using"].contains(&key.as_str()) {
+                        self.visit_expr(child, false);
+                    }
+                }
+                self.frames.pop();
+            }
+            _ => {
+                // e.g. CREATE TABLE ... AS SELECT, INSERT ... SELECT, scripting
+                for (_, child) in child_nodes(node) {
+                    self.visit_statement(child);
+                }
+            }
+        }
+    }
+
+    fn visit_query(&mut self, node: &Node) {
+        let with = match node.children.get("with") {
+            Some(ContentType::Node(n)) => Some(n),
+            _ => None,
+        };
+        if let Some(with) = with {
+            self.frames.push(Frame::default());
+            self.visit_with_clause(with);
+        }
+        match node.node_type {
+            NodeType::SelectStatement => self.visit_select(node),
+            NodeType::GroupedStatement => {
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "stmt" => self.visit_query(child),
+                        "with" | "alias" => (),
+                        _ => self.visit_expr(child, false),
+                    }
+                }
+            }
+            _ => {
+                // SetOperator
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "left" | "right" => self.visit_query(child),
+                        "with" => (),
+                        _ => self.visit_expr(child, false),
+                    }
+                }
+            }
+        }
+        if with.is_some() {
+            self.frames.pop();
+        }
+    }
+
+    fn visit_with_clause(&mut self, with: &Node) {
+        let recursive = with.children.contains_key("recursive");
+        if let Some(ContentType::NodeVec(queries)) = with.children.get("queries") {
+            for query in queries {
+                if recursive {
+                    self.bind(query, BindingKind::Cte, alias_name(query));
+                }
+                if let Some(ContentType::Node(stmt)) = query.children.get("stmt") {
+                    self.visit_query(stmt);
+                }
+                if !recursive {
+                    self.bind(query, BindingKind::Cte, alias_name(query));
+                }
+            }
+        }
+    }
+
+    fn visit_select(&mut self, node: &Node) {
+        self.frames.push(Frame::default());
+        if let Some(ContentType::Node(from)) = node.children.get("from") {
+            self.visit_from_item(from);
+        }
+        if let Some(ContentType::NodeVec(exprs)) = node.children.get("exprs") {
+            for expr in exprs {
+                self.visit_expr(expr, false);
+            }
+            for expr in exprs {
+                self.bind_alias(expr, BindingKind::ColumnAlias);
+            }
+        }
+        for (key, child) in child_nodes(node) {
+            match key.as_str() {
+                "from" | "exprs" | "with" => (),
+                "groupby" | "having" | "qualify" | "orderby" => self.visit_expr(child, true),
+                _ => self.visit_expr(child, false),
+            }
+        }
+        self.frames.pop();
+    }
+
+    fn visit_from_item(&mut self, node: &Node) {
+        match node.node_type {
+            NodeType::Identifier | NodeType::MultiTokenIdentifier | NodeType::DotOperator => {
+                if let Some((_, path)) = identifier_path(node) {
+                    let first = leftmost_identifier(node).unwrap_or(node);
+                    let binding = if node.node_type == NodeType::DotOperator {
+                        // e.g. `FROM t, t.arr`
+                        let head = alias_name(first);
+                        self.lookup_table(&head)
+                    } else {
+                        self.lookup_cte(&path)
+                    };
+                    match binding {
+                        Some(id) => self.refer(first, alias_name(first), Some(id)),
+                        None => self.refer(first, path.clone(), None),
+                    }
+                    if !self.bind_alias(node, BindingKind::TableAlias) {
+                        let name = path.rsplit('.').next().unwrap_or(&path).to_string();
+                        self.bind(first, BindingKind::TableAlias, name);
+                    }
+                    // e.g. `FROM t, t.arr AS x WITH OFFSET AS i`
+                    self.bind_offset(node);
+                }
+                // e.g. FOR SYSTEM_TIME AS OF
+                for (key, child) in child_nodes(node) {
+                    let skip = ["left", "right", "trailing_idents", "alias", "with_offset"];
+                    if !skip.contains(&key.as_str()) {
+                        self.visit_expr(child, false);
+                    }
+                }
+            }
+            NodeType::JoinOperator => {
+                for key in ["left", "right"] {
+                    if let Some(ContentType::Node(n)) = node.children.get(key) {
+                        self.visit_from_item(n);
+                    }
+                }
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "left" | "right" | "using" => (),
+                        _ => self.visit_expr(child, false),
+                    }
+                }
+            }
+            NodeType::GroupedExpr | NodeType::KeywordWithExpr => {
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "expr" => self.visit_from_item(child),
+                        "alias" => (),
+                        _ => self.visit_expr(child, false),
+                    }
+                }
+                self.bind_alias(node, BindingKind::TableAlias);
+            }
+            NodeType::GroupedStatement => {
+                self.visit_query(node);
+                self.bind_alias(node, BindingKind::TableAlias);
+            }
+            NodeType::CallingUnnest => {
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "func" | "alias" | "with_offset" => (),
+                        _ => self.visit_expr(child, false),
+                    }
+                }
+                self.bind_alias(node, BindingKind::Unnest);
+                self.bind_offset(node);
+            }
+            NodeType::CallingTableFunction => {
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "func" | "alias" => (),
+                        _ => self.visit_expr(child, false),
+                    }
+                }
+                self.bind_alias(node, BindingKind::TableAlias);
+            }
+            _ => self.visit_expr(node, false),
+        }
+    }
+
+    /// `aliases` means aliases in the select list are visible.
+    fn visit_expr(&mut self, node: &Node, aliases: bool) {
+        if is_query(node) {
+            self.visit_query(node);
+            return;
+        }
+        match node.node_type {
+            NodeType::Identifier => {
+                let name = alias_name(node);
+                let mut binding = None;
+                if aliases {
+                    binding = self.frames.last().and_then(|f| find(&f.aliases, &name));
+                }
+                if binding.is_none() {
+                    binding = self.lookup_table(&name);
+                }
+                self.refer(node, name, binding);
+                return;
+            }
+            NodeType::DotOperator => {
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "left" => self.visit_expr(child, aliases),
+                        "right" | "alias" => (),
+                        _ => self.visit_expr(child, false),
+                    }
+                }
+                return;
+            }
+            // pattern variables of MATCH_RECOGNIZE
+            NodeType::Type | NodeType::GroupedPattern => return,
+            _ => (),
+        }
+        for (key, child) in child_nodes(node) {
+            let skip = match key.as_str() {
+                "func" | "alias" | "extract_datepart" => true,
+                // named argument
+                "left" => node.token.as_ref().is_some_and(|t| t.is("=>")),
+                // named window
+                "window" => {
+                    node.node_type == NodeType::OverClause
+                        && matches!(child.node_type, NodeType::Identifier)
+                }
+                _ => false,
+            };
+            if !skip {
+                self.visit_expr(child, aliases);
+            }
+        }
+    }
+}
+
+fn alias_name(node: &Node) -> String {
+    match &node.token {
+        Some(t) => literal::decode_identifier_literal(&t.literal)
+            .unwrap_or_else(|| t.literal.trim_matches('`').to_string()),
+        None => String::new(),
+    }
+}
+
+// ----- unused bindings -----
+fn collect_cte_bodies(node: &Node, bodies: &mut Vec<(NodeId, (NodeId, NodeId))>) {
+    if node.node_type == NodeType::WithQuery {
+        if let (Some(id), Some(ContentType::Node(stmt))) =
+            (position(node), node.children.get("stmt"))
+        {
+            if let Some(range) = token_range(stmt) {
+                bodies.push((id, range));
+            }
+        }
+    }
+    for (_, child) in child_nodes(node) {
+        collect_cte_bodies(child, bodies);
+    }
+}
+
+/// Collects the aliases which become the names of the output columns
+/// (or the fields of `SELECT AS STRUCT`).
+fn collect_output_aliases(node: &Node, outputs: &mut Vec<NodeId>) {
+    match node.node_type {
+        NodeType::SelectStatement => {
+            if let Some(ContentType::NodeVec(exprs)) = node.children.get("exprs") {
+                for expr in exprs {
+                    if let Some(ContentType::Node(alias)) = expr.children.get("alias") {
+                        outputs.extend(position(alias));
+                    }
+                }
+            }
+            for (key, child) in child_nodes(node) {
+                if key != "with" {
+                    collect_struct_aliases(child, outputs);
+                }
+            }
+        }
+        NodeType::SetOperator | NodeType::GroupedStatement => {
+            for (key, child) in child_nodes(node) {
+                match key.as_str() {
+                    "left" | "right" | "stmt" => collect_output_aliases(child, outputs),
+                    _ => collect_struct_aliases(child, outputs),
+                }
+            }
+        }
+        _ => {
+            for (_, child) in child_nodes(node) {
+                collect_output_aliases(child, outputs);
+            }
+        }
+    }
+}
+
+fn collect_struct_aliases(node: &Node, outputs: &mut Vec<NodeId>) {
+    if node.node_type == NodeType::SelectStatement
+        && node.children.contains_key("as_struct_or_value")
+    {
+        collect_output_aliases(node, outputs);
+        return;
+    }
+    for (_, child) in child_nodes(node) {
+        collect_struct_aliases(child, outputs);
+    }
+}
+
+/// Collects every identifier except aliases and CTE names.
+fn collect_names(node: &Node, names: &mut Vec<String>) {
+    if node.node_type == NodeType::Identifier {
+        names.push(alias_name(node));
+    }
+    for (key, child) in child_nodes(node) {
+        if key != "alias" {
+            collect_names(child, names);
+        }
+    }
+}
+
+fn has_star(node: &Node) -> bool {
+    if node.node_type == NodeType::SelectStatement {
+        if let Some(ContentType::NodeVec(exprs)) = node.children.get("exprs") {
+            let is_star = |n: &Node| n.node_type == NodeType::Asterisk;
+            let star = exprs.iter().any(|e| {
+                is_star(e)
+                    || matches!(e.children.get("right"), Some(ContentType::Node(r)) if is_star(r))
+            });
+            if star {
+                return true;
+            }
+        }
+    }
+    child_nodes(node)
+        .into_iter()
+        .any(|(_, child)| has_star(child))
+}
+
+// ----- star expansion -----
+type Columns = Option<Vec<String>>;
+
+struct Expander<'a> {
+    schemas: &'a BTreeMap<String, Vec<String>>,
+    ctes: Vec<(String, Columns)>,
+    res: Vec<StarExpansion>,
+}
+
+/// The range variables of FROM clause and the columns of `*`.
+struct Sources {
+    vars: Vec<(String, Columns)>,
+    star: Columns,
+}
+
+impl Sources {
+    fn new(name: String, columns: Columns) -> Sources {
+        Sources {
+            vars: vec![(name, columns.clone())],
+            star: columns,
+        }
+    }
+}
+
+fn except_columns(star: &Node) -> Vec<String> {
+    let mut res = Vec::new();
+    if let Some(ContentType::Node(except)) = star.children.get("except") {
+        if let Some(ContentType::Node(group)) = except.children.get("group") {
+            if let Some(ContentType::NodeVec(exprs)) = group.children.get("exprs") {
+                res.extend(exprs.iter().map(alias_name));
+            }
+        }
+    }
+    res
+}
+
+fn using_columns(join: &Node) -> Option<Vec<String>> {
+    let using = match join.children.get("using") {
+        Some(ContentType::Node(n)) => n,
+        _ => return None,
+    };
+    let mut identifiers = Vec::new();
+    let mut nodes = vec![using];
+    while let Some(n) = nodes.pop() {
+        if let (NodeType::Identifier, Some(t)) = (&n.node_type, &n.token) {
+            identifiers.push(((t.line, t.column), alias_name(n)));
+        }
+        for (_, child) in child_nodes(n) {
+            nodes.push(child);
+        }
+    }
+    // in the written order
+    identifiers.sort();
+    let mut res: Vec<String> = Vec::new();
+    for (_, name) in identifiers {
+        if !contains(&res, &name) {
+            res.push(name);
+        }
+    }
+    Some(res)
+}
+
+fn contains(columns: &[String], name: &str) -> bool {
+    columns.iter().any(|c| c.eq_ignore_ascii_case(name))
+}
+
+impl Expander<'_> {
+    fn visit(&mut self, node: &Node) {
+        if is_query(node) {
+            self.query_columns(node);
+            return;
+        }
+        for (_, child) in child_nodes(node) {
+            self.visit(child);
+        }
+    }
+
+    /// Returns the output columns of the query.
+    fn query_columns(&mut self, node: &Node) -> Columns {
+        let depth = self.ctes.len();
+        if let Some(ContentType::Node(with)) = node.children.get("with") {
+            if let Some(ContentType::NodeVec(queries)) = with.children.get("queries") {
+                for query in queries {
+                    let mut columns = match query.children.get("stmt") {
+                        Some(ContentType::Node(stmt)) => self.query_columns(stmt),
+                        _ => None,
+                    };
+                    // e.g. `WITH t(a, b) AS (SELECT 1, 2)`
+                    if let Some(ContentType::Node(group)) = query.children.get("columns") {
+                        if let Some(ContentType::NodeVec(exprs)) = group.children.get("exprs") {
+                            columns = Some(exprs.iter().map(alias_name).collect());
+                        }
+                    }
+                    self.ctes.push((alias_name(query), columns));
+                }
+            }
+        }
+        let res = match node.node_type {
+            NodeType::SelectStatement => self.select_columns(node),
+            NodeType::GroupedStatement => {
+                let mut res = None;
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "stmt" => res = self.query_columns(child),
+                        "with" => (),
+                        _ => self.visit(child),
+                    }
+                }
+                res
+            }
+            _ => {
+                // SetOperator: the names come from the first query
+                let mut res = None;
+                for (key, child) in child_nodes(node) {
+                    match key.as_str() {
+                        "left" => res = self.query_columns(child),
+                        "with" => (),
+                        _ => self.visit(child),
+                    }
+                }
+                res
+            }
+        };
+        self.ctes.truncate(depth);
+        res
+    }
+
+    fn select_columns(&mut self, node: &Node) -> Columns {
+        let sources = match node.children.get("from") {
+            Some(ContentType::Node(from)) => match from.children.get("expr") {
+                Some(ContentType::Node(expr)) => self.sources(expr),
+                _ => Sources::new(String::new(), Some(Vec::new())),
+            },
+            _ => Sources::new(String::new(), Some(Vec::new())),
+        };
+        let mut res = Some(Vec::new());
+        let mut anonymous = 0;
+        if let Some(ContentType::NodeVec(exprs)) = node.children.get("exprs") {
+            for expr in exprs {
+                let star = match (&expr.node_type, expr.children.get("right")) {
+                    (NodeType::Asterisk, _) => Some((expr, sources.star.clone())),
+                    (NodeType::DotOperator, Some(ContentType::Node(r)))
+                        if r.node_type == NodeType::Asterisk =>
+                    {
+                        let columns = match expr.children.get("left") {
+                            Some(ContentType::Node(l)) if l.node_type == NodeType::Identifier => {
+                                let name = alias_name(l);
+                                sources
+                                    .vars
+                                    .iter()
+                                    .rev()
+                                    .find(|(n, _)| n.eq_ignore_ascii_case(&name))
+                                    .and_then(|(_, c)| c.clone())
+                            }
+                            _ => None,
+                        };
+                        Some((r, columns))
+                    }
+                    _ => None,
+                };
+                let columns = match star {
+                    Some((star, columns)) => {
+                        let except = except_columns(star);
+                        let columns = columns.map(|cs| {
+                            cs.into_iter()
+                                .filter(|c| !contains(&except, c))
+                                .collect::<Vec<_>>()
+                        });
+                        if let (Some(id), Some(token)) = (position(star), &star.token) {
+                            self.res.push(StarExpansion {
+                                id,
+                                span: Span::from_token(token),
+                                columns: columns.clone(),
+                            });
+                        }
+                        self.visit(star);
+                        columns
+                    }
+                    None => {
+                        self.visit(expr);
+                        let name = match (expr.children.get("alias"), &expr.node_type) {
+                            (Some(ContentType::Node(alias)), _) => alias_name(alias),
+                            (_, NodeType::Identifier) => alias_name(expr),
+                            (_, NodeType::DotOperator) => match expr.children.get("right") {
+                                Some(ContentType::Node(r)) => alias_name(r),
+                                _ => String::new(),
+                            },
+                            _ => {
+                                anonymous += 1;
+                                format!("f{}_", anonymous - 1)
+                            }
+                        };
+                        Some(vec![name])
+                    }
+                };
+                res = match (res, columns) {
+                    (Some(mut res), Some(mut columns)) => {
+                        res.append(&mut columns);
+                        Some(res)
+                    }
+                    _ => None,
+                };
+            }
+        }
+        for (key, child) in child_nodes(node) {
+            if !["from", "exprs", "with"].contains(&key.as_str()) {
+                self.visit(child);
+            }
+        }
+        res
+    }
+
+    fn sources(&mut self, node: &Node) -> Sources {
+        match node.node_type {
+            NodeType::Identifier | NodeType::MultiTokenIdentifier | NodeType::DotOperator => {
+                for (key, child) in child_nodes(node) {
+                    if !["left", "right", "trailing_idents", "alias"].contains(&key.as_str()) {
+                        self.visit(child);
+                    }
+                }
+                let path = match identifier_path(node) {
+                    Some((_, path)) => path,
+                    None => return Sources::new(String::new(), None),
+                };
+                let columns = match self
+                    .ctes
+                    .iter()
+                    .rev()
+                    .find(|(n, _)| n.eq_ignore_ascii_case(&path))
+                {
+                    Some((_, columns)) => columns.clone(),
+                    None => self.schemas.get(&path).cloned(),
+                };
+                let name = match node.children.get("alias") {
+                    Some(ContentType::Node(alias)) => alias_name(alias),
+                    _ => path.rsplit('.').next().unwrap_or(&path).to_string(),
+                };
+                Sources::new(name, columns)
+            }
+            NodeType::JoinOperator => {
+                let mut left = match node.children.get("left") {
+                    Some(ContentType::Node(n)) => self.sources(n),
+                    _ => Sources::new(String::new(), None),
+                };
+                let mut right = match node.children.get("right") {
+                    Some(ContentType::Node(n)) => self.sources(n),
+                    _ => Sources::new(String::new(), None),
+                };
+                for (key, child) in child_nodes(node) {
+                    if !["left", "right", "using"].contains(&key.as_str()) {
+                        self.visit(child);
+                    }
+                }
+                let star = match (left.star, right.star, using_columns(node)) {
+                    (Some(l), Some(r), Some(using)) => {
+                        // the columns in USING come first and appear only once
+                        let mut star = using.clone();
+                        star.extend(l.into_iter().filter(|c| !contains(&using, c)));
+                        star.extend(r.into_iter().filter(|c| !contains(&using, c)));
+                        Some(star)
+                    }
+                    (Some(mut l), Some(mut r), None) => {
+                        l.append(&mut r);
+                        Some(l)
+                    }
+                    _ => None,
+                };
+                left.vars.append(&mut right.vars);
+                Sources {
+                    vars: left.vars,
+                    star,
+                }
+            }
+            NodeType::GroupedExpr => match node.children.get("expr") {
+                Some(ContentType::Node(n)) => self.sources(n),
+                _ => Sources::new(String::new(), None),
+            },
+            NodeType::GroupedStatement => {
+                let columns = self.query_columns(node);
+                let name = match node.children.get("alias") {
+                    Some(ContentType::Node(alias)) => alias_name(alias),
+                    _ => String::new(),
+                };
+                Sources::new(name, columns)
+            }
+            _ => {
+                // UNNEST, table functions
+                self.visit(node);
+                let name = match node.children.get("alias") {
+                    Some(ContentType::Node(alias)) => alias_name(alias),
+                    _ => String::new(),
+                };
+                Sources::new(name, None)
+            }
+        }
+    }
+}
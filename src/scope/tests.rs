@@ -0,0 +1,217 @@
+use super::*;
+use crate::utils::parse;
+
+/// Returns the name and the binding of each reference.
+fn links(res: &Resolution) -> Vec<(&str, Option<NodeId>)> {
+    res.references
+        .iter()
+        .map(|r| (r.name.as_str(), r.binding))
+        .collect()
+}
+
+#[test]
+fn test_resolve() {
+    let stmts = parse(
+        "\
+WITH c AS (SELECT 1 AS x)
+SELECT t.a AS y, u, o
+FROM c AS t, t.arr, UNNEST(t.b) AS u WITH OFFSET AS o
+WHERE EXISTS(SELECT 1 FROM src WHERE src.id = t.id)
+ORDER BY y;
+",
+    );
+    let res = resolve(&stmts[0]);
+    assert_eq!(
+        res.bindings
+            .iter()
+            .map(|b| (b.kind, b.name.as_str(), b.id))
+            .collect::<Vec<_>>(),
+        vec![
+            (BindingKind::Cte, "c", (1, 6)),
+            (BindingKind::ColumnAlias, "x", (1, 24)),
+            (BindingKind::ColumnAlias, "y", (2, 15)),
+            (BindingKind::TableAlias, "t", (3, 11)),
+            (BindingKind::TableAlias, "arr", (3, 14)),
+            (BindingKind::Unnest, "u", (3, 36)),
+            (BindingKind::Offset, "o", (3, 53)),
+            (BindingKind::TableAlias, "src", (4, 28)),
+        ]
+    );
+    assert_eq!(
+        links(&res),
+        vec![
+            ("t", Some((3, 11))),
+            ("u", Some((3, 36))),
+            ("o", Some((3, 53))),
+            ("c", Some((1, 6))),
+            ("t", Some((3, 11))),
+            ("t", Some((3, 11))),
+            ("src", None),
+            ("src", Some((4, 28))),
+            ("t", Some((3, 11))),
+            ("y", Some((2, 15))),
+        ]
+    );
+    assert_eq!(res.references_to((1, 6)).len(), 1);
+    assert_eq!(
+        res.binding((3, 36)).map(|b| b.kind),
+        Some(BindingKind::Unnest)
+    );
+}
+
+#[test]
+fn test_resolve_scopes() {
+    let stmts = parse(
+        "\
+WITH RECURSIVE r AS (SELECT 1 AS n UNION ALL SELECT n + 1 FROM r)
+SELECT n AS m FROM r WHERE m > 0;
+WITH a AS (SELECT 1), b AS (SELECT * FROM a) SELECT * FROM (SELECT * FROM b) AS a;
+DELETE target AS x WHERE x.id IN (SELECT id FROM u WHERE u.id = x.id);
+",
+    );
+    // aliases in the select list are not visible to WHERE
+    assert_eq!(
+        links(&resolve(&stmts[0])),
+        vec![
+            ("n", None),
+            ("r", Some((1, 16))),
+            ("n", None),
+            ("r", Some((1, 16))),
+            ("m", None),
+        ]
+    );
+    assert_eq!(
+        links(&resolve(&stmts[1])),
+        vec![("a", Some((3, 6))), ("b", Some((3, 23)))]
+    );
+    assert_eq!(
+        links(&resolve(&stmts[2])),
+        vec![
+            ("target", None),
+            ("x", Some((4, 18))),
+            ("id", None),
+            ("u", None),
+            ("u", Some((4, 50))),
+            ("x", Some((4, 18))),
+        ]
+    );
+}
+
+#[test]
+fn test_unused_bindings() {
+    let stmts = parse(
+        "\
+WITH RECURSIVE
+  a AS (SELECT 1 AS x, 2 AS y),
+  b AS (SELECT 1),
+  r AS (SELECT 1 AS n UNION ALL SELECT n FROM r)
+SELECT x AS output, ARRAY(SELECT AS STRUCT 1 AS field) FROM a;
+WITH c AS (SELECT 1 AS x) SELECT * FROM c;
+",
+    );
+    assert_eq!(
+        unused_bindings(&stmts[0])
+            .iter()
+            .map(|b| (b.kind, b.name.as_str(), b.span))
+            .collect::<Vec<_>>(),
+        vec![
+            (BindingKind::ColumnAlias, "y", Span::new(2, 29, 2, 30)),
+            (BindingKind::Cte, "b", Span::new(3, 3, 3, 4)),
+            (BindingKind::Cte, "r", Span::new(4, 3, 4, 4)),
+        ]
+    );
+    assert!(unused_bindings(&stmts[1]).is_empty());
+}
+
+#[test]
+fn test_expand_stars() {
+    let stmts = parse(
+        "\
+WITH c AS (SELECT id, x.name, 1 AS one, id + 1 FROM dataset.users AS x)
+SELECT *, c.* EXCEPT (id) FROM c;
+SELECT * FROM a JOIN b USING (id) JOIN UNNEST([1]);
+SELECT * EXCEPT (id) FROM a JOIN b USING (id);
+WITH c(p, q) AS (SELECT 1, 2) SELECT * FROM c;
+SELECT * FROM d JOIN e USING (y, x);
+WITH Foo AS (SELECT 1 AS z) SELECT * FROM foo;
+",
+    );
+    let schemas = BTreeMap::from([
+        ("dataset.users".to_string(), vec!["id".to_string()]),
+        ("a".to_string(), vec!["id".to_string(), "a1".to_string()]),
+        ("b".to_string(), vec!["b1".to_string(), "id".to_string()]),
+        (
+            "d".to_string(),
+            vec!["p".to_string(), "x".to_string(), "y".to_string()],
+        ),
+        (
+            "e".to_string(),
+            vec!["y".to_string(), "q".to_string(), "x".to_string()],
+        ),
+    ]);
+    let columns = |stmt| {
+        expand_stars(stmt, &schemas)
+            .into_iter()
+            .map(|e| (e.id, e.columns))
+            .collect::<Vec<_>>()
+    };
+    let strings = |xs: &[&str]| Some(xs.iter().map(|x| x.to_string()).collect::<Vec<_>>());
+    assert_eq!(
+        columns(&stmts[0]),
+        vec![
+            ((2, 8), strings(&["id", "name", "one", "f0_"])),
+            ((2, 13), strings(&["name", "one", "f0_"])),
+        ]
+    );
+    assert_eq!(columns(&stmts[1]), vec![((3, 8), None)]);
+    assert_eq!(columns(&stmts[2]), vec![((4, 8), strings(&["a1", "b1"]))]);
+    assert_eq!(columns(&stmts[3]), vec![((5, 38), strings(&["p", "q"]))]);
+    // the columns of USING come first in the written order
+    assert_eq!(
+        columns(&stmts[4]),
+        vec![((6, 8), strings(&["y", "x", "p", "q"]))]
+    );
+    // CTE names are case-insensitive
+    assert_eq!(columns(&stmts[5]), vec![((7, 36), strings(&["z"]))]);
+}
+
+#[test]
+fn test_resolve_unnest_chain() {
+    let stmts = parse(
+        "\
+SELECT i, j, offset
+FROM t, t.arr AS x WITH OFFSET AS i, UNNEST(x.b) y WITH OFFSET j, UNNEST(y.c) WITH OFFSET
+WHERE i = j;
+",
+    );
+    let res = resolve(&stmts[0]);
+    assert_eq!(
+        res.bindings
+            .iter()
+            .map(|b| (b.kind, b.name.as_str(), b.id))
+            .collect::<Vec<_>>(),
+        vec![
+            (BindingKind::TableAlias, "t", (2, 6)),
+            (BindingKind::TableAlias, "x", (2, 18)),
+            (BindingKind::Offset, "i", (2, 35)),
+            (BindingKind::Unnest, "y", (2, 50)),
+            (BindingKind::Offset, "j", (2, 64)),
+            // the default name of `WITH OFFSET`
+            (BindingKind::Offset, "offset", (2, 84)),
+        ]
+    );
+    assert_eq!(
+        links(&res),
+        vec![
+            ("i", Some((2, 35))),
+            ("j", Some((2, 64))),
+            ("offset", Some((2, 84))),
+            ("t", None),
+            ("t", Some((2, 6))),
+            ("x", Some((2, 18))),
+            ("y", Some((2, 50))),
+            ("i", Some((2, 35))),
+            ("j", Some((2, 64))),
+        ]
+    );
+}
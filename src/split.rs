@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests;
+
+use crate::diagnostic::Span;
+use crate::error::BQ2CSTResult;
+use crate::lexer::Lexer;
+use crate::token::Token;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Splits a script at top-level `;` without parsing it,
+/// so that it also works for statements which this crate cannot parse yet.
+/// Only the lexer is used, which means `;` in strings and comments is ignored,
+/// and procedural blocks (`BEGIN ... END`, `IF ... END IF`, `LOOP ... END LOOP`, ...)
+/// including the body of `CREATE PROCEDURE` are kept intact.
+///
+/// Each statement ranges from its first token to `;` (or the last token of the script).
+/// Comments before the first token are not included.
+pub fn split_statements(code: &str) -> BQ2CSTResult<Vec<(Span, String)>> {
+    let tokens = Lexer::new(code.to_string()).tokenize_code()?;
+    let lines: Vec<&str> = code.trim_start_matches('\u{feff}').split('\n').collect();
+    let mut splitter = Splitter::default();
+    let mut res = Vec::new();
+    let mut start: Option<&Token> = None;
+    let mut last: Option<&Token> = None;
+    for (i, token) in tokens.iter().enumerate() {
+        if token.is_comment() || token.literal.is_empty() {
+            continue;
+        }
+        let next = tokens[i + 1..].iter().find(|t| !t.is_comment());
+        let is_end = splitter.push(token, next);
+        if start.is_none() {
+            start = Some(token);
+        }
+        last = Some(token);
+        if is_end {
+            res.push(statement(&lines, start.take().unwrap(), token));
+        }
+    }
+    if let (Some(start), Some(last)) = (start, last) {
+        res.push(statement(&lines, start, last));
+    }
+    Ok(res)
+}
+
+// ----- utilities -----
+#[derive(PartialEq, Clone, Copy)]
+enum Block {
+    /// BEGIN, IF, LOOP, WHILE, REPEAT, FOR and the CASE statement
+    Statement,
+    /// the CASE expression
+    Expr,
+}
+
+pub(crate) struct Splitter {
+    blocks: Vec<Block>,
+    parens: usize,
+    statement_start: bool,
+    /// the token is a part of `END IF`, `END LOOP`, ...
+    after_end: bool,
+}
+
+impl Default for Splitter {
+    fn default() -> Splitter {
+        Splitter {
+            blocks: Vec::new(),
+            parens: 0,
+            statement_start: true,
+            after_end: false,
+        }
+    }
+}
+
+impl Splitter {
+    /// Returns `true` if `token` terminates a top-level statement.
+    pub(crate) fn push(&mut self, token: &Token, next: Option<&Token>) -> bool {
+        let statement_start = self.statement_start;
+        let after_end = self.after_end;
+        self.statement_start = false;
+        self.after_end = false;
+        let in_block = self.blocks.last() == Some(&Block::Statement) && self.parens == 0;
+        match token.literal.to_uppercase().as_str() {
+            "(" | "[" => self.parens += 1,
+            ")" | "]" => self.parens = self.parens.saturating_sub(1),
+            ";" if self.parens == 0 => {
+                self.statement_start = true;
+                return self.blocks.is_empty();
+            }
+            // a label (e.g. `label: BEGIN`)
+            ":" => self.statement_start = statement_start,
+            _ if after_end => (),
+            "BEGIN" => {
+                // BEGIN TRANSACTION
+                let transaction =
+                    next.is_none_or(|t| t.is(";") || t.is("TRANSACTION") || t.is("TRAN"));
+                if !transaction || !statement_start {
+                    self.blocks.push(Block::Statement);
+                    self.statement_start = true;
+                }
+            }
+            "IF" | "LOOP" | "WHILE" | "REPEAT" | "FOR" if statement_start => {
+                self.blocks.push(Block::Statement);
+                self.statement_start = token.is("LOOP") || token.is("REPEAT");
+            }
+            "CASE" => {
+                let block = if statement_start {
+                    Block::Statement
+                } else {
+                    Block::Expr
+                };
+                self.blocks.push(block);
+            }
+            "END" => {
+                self.blocks.pop();
+                self.after_end = true;
+            }
+            "THEN" | "ELSE" | "DO" if in_block => self.statement_start = true,
+            _ => (),
+        }
+        // labels are identifiers followed by `:`
+        if statement_start && next.is_some_and(|t| t.is(":")) {
+            self.statement_start = true;
+        }
+        false
+    }
+}
+
+fn statement(lines: &[&str], start: &Token, end: &Token) -> (Span, String) {
+    let end = Span::from_token(end);
+    let span = Span::new(start.line, start.column, end.end_line, end.end_column);
+    let mut text = String::new();
+    for line in span.start_line..=span.end_line {
+        let chars = lines.get(line - 1).copied().unwrap_or("").chars();
+        let from = if line == span.start_line {
+            span.start_column - 1
+        } else {
+            0
+        };
+        if line == span.end_line {
+            text.extend(chars.take(span.end_column - 1).skip(from));
+        } else {
+            text.extend(chars.skip(from));
+            text.push('\n');
+        }
+    }
+    (span, text)
+}
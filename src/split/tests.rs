@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn test_split_statements() {
+    let code = "\
+-- comment; not a statement
+SELECT ';' /* ; */; SELECT 2;
+BEGIN
+  DECLARE x INT64 DEFAULT (SELECT CASE WHEN TRUE THEN 1 ELSE 2 END);
+  IF x = 1 THEN
+    SELECT IF(x = 1, 'a', 'b');
+  ELSE
+    label: LOOP
+      LEAVE label;
+    END LOOP;
+  END IF;
+EXCEPTION WHEN ERROR THEN
+  CASE x WHEN 1 THEN SELECT 1; ELSE SELECT 2; END CASE;
+END;
+BEGIN TRANSACTION;
+CREATE PROCEDURE p() BEGIN WHILE TRUE DO BREAK; END WHILE; END;
+COMMIT TRANSACTION
+";
+    let res = split_statements(code).unwrap();
+    let texts: Vec<&str> = res.iter().map(|(_, s)| s.as_str()).collect();
+    assert_eq!(texts.len(), 6);
+    assert_eq!(texts[0], "SELECT ';' /* ; */;");
+    assert_eq!(texts[1], "SELECT 2;");
+    assert!(texts[2].starts_with("BEGIN\n  DECLARE"));
+    assert!(texts[2].ends_with("END CASE;\nEND;"));
+    assert_eq!(texts[3], "BEGIN TRANSACTION;");
+    assert_eq!(
+        texts[4],
+        "CREATE PROCEDURE p() BEGIN WHILE TRUE DO BREAK; END WHILE; END;"
+    );
+    assert_eq!(texts[5], "COMMIT TRANSACTION");
+    assert_eq!(res[1].0, Span::new(2, 21, 2, 30));
+    assert_eq!(res[2].0, Span::new(3, 1, 14, 5));
+}
+
+#[test]
+fn test_split_statements_error() {
+    assert!(split_statements("SELECT 'abc").is_err());
+    assert!(split_statements("").unwrap().is_empty());
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests;
+
+use crate::cst::{ContentType, Node};
+use serde::{Deserialize, Serialize};
+
+/// Approximate sizes of a parse result.
+/// They are useful to set limits on inputs and to debug pathological ones.
+#[derive(PartialEq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ParseStats {
+    /// The number of nodes (including comments and EOF).
+    pub node_count: usize,
+    /// The number of tokens which belong to the nodes (EOF is not included).
+    pub token_count: usize,
+    /// The maximum depth of the trees (a statement without children is 1).
+    pub max_depth: usize,
+    /// Heap and inline memory used by the trees in bytes.
+    /// The overhead of the allocator and `BTreeMap` internals is not included.
+    pub cst_bytes: usize,
+    /// The peak of heap allocations while lexing and parsing in bytes.
+    /// Only available if the `alloc-stats` feature is enabled
+    /// and `CountingAllocator` is the global allocator.
+    pub peak_allocation: Option<usize>,
+}
+
+/// Counts the nodes and tokens of `stmts` (`peak_allocation` is not set).
+pub fn collect_stats(stmts: &[Node]) -> ParseStats {
+    let mut stats = ParseStats::default();
+    for stmt in stmts {
+        collect(stmt, 1, &mut stats);
+    }
+    stats
+}
+
+fn collect(node: &Node, depth: usize, stats: &mut ParseStats) {
+    stats.node_count += 1;
+    stats.max_depth = stats.max_depth.max(depth);
+    stats.cst_bytes += core::mem::size_of::<Node>();
+    if let Some(token) = &node.token {
+        if !token.literal.is_empty() {
+            stats.token_count += 1;
+        }
+        stats.cst_bytes += token.literal.len();
+    }
+    for (key, content) in &node.children {
+        stats.cst_bytes += key.len() + core::mem::size_of::<ContentType>();
+        match content {
+            ContentType::Node(n) => collect(n, depth + 1, stats),
+            ContentType::NodeVec(ns) => {
+                for n in ns {
+                    collect(n, depth + 1, stats);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+pub use counting::{peak_allocation, reset_peak_allocation, CountingAllocator};
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    static INSTALLED: AtomicBool = AtomicBool::new(false);
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+    /// A global allocator which wraps `System` and records the peak of allocations.
+    /// The counters are process-wide, so concurrent parses affect each other's stats.
+    ///
+    /// ```ignore
+    /// #[global_allocator]
+    /// static ALLOCATOR: bq2cst::stats::CountingAllocator = bq2cst::stats::CountingAllocator;
+    /// ```
+    pub struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                INSTALLED.store(true, Ordering::Relaxed);
+                let current = CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+                PEAK.fetch_max(current, Ordering::Relaxed);
+            }
+            ptr
+        }
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+    }
+
+    /// Starts a new measurement and returns the current allocation,
+    /// or `None` if `CountingAllocator` is not the global allocator.
+    pub fn reset_peak_allocation() -> Option<usize> {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        INSTALLED.load(Ordering::Relaxed).then_some(current)
+    }
+
+    /// The peak of allocations since `reset_peak_allocation` was called.
+    pub fn peak_allocation() -> usize {
+        PEAK.load(Ordering::Relaxed)
+    }
+}
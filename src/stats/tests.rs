@@ -0,0 +1,20 @@
+use super::*;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+
+#[test]
+fn test_collect_stats() {
+    let code = "SELECT a + 1 FROM t; -- comment\n";
+    let tokens = Lexer::new(code.to_string()).tokenize_code().unwrap();
+    let stmts = Parser::new(tokens).parse_code().unwrap();
+    let stats = collect_stats(&stmts);
+    // SELECT, +, a, 1, FROM, t, ;, comment and EOF
+    assert_eq!(stats.node_count, 9);
+    assert_eq!(stats.token_count, 8);
+    // SELECT -> FROM -> t
+    assert_eq!(stats.max_depth, 3);
+    assert!(stats.node_count * core::mem::size_of::<Node>() < stats.cst_bytes);
+    assert_eq!(stats.peak_allocation, None);
+
+    assert_eq!(collect_stats(&[]), ParseStats::default());
+}
@@ -2,14 +2,86 @@
 mod tests;
 
 use crate::constants;
-use regex::Regex;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+/// The style of a comment token.
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum CommentKind {
+    /// `# ...`
+    Hash,
+    /// `-- ...`
+    DoubleDash,
+    /// `/* ... */`
+    Block,
+    /// `/** ... */`
+    DocBlock,
+    /// `{% ... %}` or `{# ... #}` (see `ParseOptions.jinja_templates`)
+    TemplateTag,
+}
+
+/// A comment which switches formatting, e.g. `-- bq2cst: off`.
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum FormatDirective {
+    /// `-- bq2cst: off`
+    Off,
+    /// `-- bq2cst: on`
+    On,
+}
+
+/// The letter case of a token as it is written in the code.
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum LetterCase {
+    /// `SELECT`
+    Upper,
+    /// `select`
+    Lower,
+    /// `Select`, `sElEcT`
+    Mixed,
+}
+
+/// The form of a numeric literal.
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum NumericKind {
+    /// `123`, `0x1F`
+    Integer,
+    /// `1.5`, `.5`, `1.`
+    Decimal,
+    /// `1e10`, `1.5E-3`
+    Exponential,
+}
+
+/// Trivia (see `leading_trivia` and `trailing_trivia`) and `offset` are not compared by `==`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Token {
     pub line: usize,
     pub column: usize,
     pub literal: String,
+    /// `true` if the token was created or rewritten by `crate::edit`
+    /// rather than read from the source code.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    pub synthetic: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) leading_trivia: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub(crate) trailing_trivia: String,
+    /// The byte offset from the beginning of the input (BOM included).
+    /// Only the EOF token returned by the lexer has it,
+    /// whose `line` and `column` are those right after the last character.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+impl PartialEq for Token {
+    fn eq(&self, other: &Token) -> bool {
+        self.line == other.line
+            && self.column == other.column
+            && self.literal == other.literal
+            && self.synthetic == other.synthetic
+    }
 }
 
 impl Token {
@@ -18,8 +90,26 @@ impl Token {
             line,
             column,
             literal,
+            synthetic: false,
+            leading_trivia: String::new(),
+            trailing_trivia: String::new(),
+            offset: None,
         }
     }
+    /// Creates a token which does not come from the source code.
+    pub fn synthetic(line: usize, column: usize, literal: &str) -> Token {
+        Token {
+            line,
+            column,
+            literal: literal.to_string(),
+            synthetic: true,
+            leading_trivia: String::new(),
+            trailing_trivia: String::new(),
+            offset: None,
+        }
+    }
+    /// EOF without a position (e.g. appended to a part of the tokens).
+    /// The lexer returns EOF at the end of the input instead (see `offset`).
     pub fn eof() -> Token {
         // `const EOF` is not allowed
         // because of `"".to_string()`
@@ -27,16 +117,29 @@ impl Token {
             line: usize::MAX,
             column: usize::MAX,
             literal: "".to_string(),
+            synthetic: false,
+            leading_trivia: String::new(),
+            trailing_trivia: String::new(),
+            offset: None,
         }
     }
+    /// Whitespace before the token, from the first line break after the previous token
+    /// (or from the beginning of the code if this is the first token).
+    ///
+    /// Concatenating `leading_trivia`, `literal` and `trailing_trivia` of all the tokens
+    /// returned by the lexer (including comments and EOF) reproduces the code
+    /// except for BOM and `ParseOptions.header_lines`.
+    /// Tokens which are not read from the code (e.g. `synthetic`) have no trivia,
+    /// nor do any tokens unless `ParseOptions.keep_trivia` is `true`.
+    pub fn leading_trivia(&self) -> &str {
+        &self.leading_trivia
+    }
+    /// Whitespace after the token up to (but not including) the next line break.
+    pub fn trailing_trivia(&self) -> &str {
+        &self.trailing_trivia
+    }
     pub fn is_string(&self) -> bool {
-        if self.quoted_by('"') {
-            true
-        } else if self.quoted_by('\'') {
-            true
-        } else {
-            false
-        }
+        self.quoted_by('"') || self.quoted_by('\'')
     }
     pub fn in_(&self, vec: &Vec<&str>) -> bool {
         for v in vec {
@@ -56,12 +159,6 @@ impl Token {
         if self.is_reserved_keyword() {
             return false;
         }
-        for kw in constants::KEYWORDS.iter() {
-            // `PartialEq<&str>` is implemented for String
-            if self.literal.to_uppercase() == *kw {
-                return false;
-            }
-        }
         let mut iterator = self.literal.chars();
         match iterator.next() {
             Some('a'..='z') | Some('A'..='Z') | Some('_') => (),
@@ -76,36 +173,68 @@ impl Token {
         true
     }
     pub fn is_parameter(&self) -> bool {
-        let mut iterator = self.literal.chars();
-        match iterator.next() {
-            Some('?') | Some('@') => return true,
-            _ => return false,
-        }
+        matches!(self.literal.chars().next(), Some('?') | Some('@')) && !self.is_hint()
+    }
+    /// `@{...}` (see `ParseOptions.hints`)
+    pub fn is_hint(&self) -> bool {
+        self.literal.starts_with("@{")
     }
     pub fn is_template(&self) -> bool {
-        let mut iterator = self.literal.chars();
-        match iterator.next() {
-            Some('{') => return true,
-            _ => return false,
-        }
+        matches!(self.literal.chars().next(), Some('{'))
+    }
+    /// `{% ... %}` and `{# ... #}` are treated like comments
+    /// because they do not produce any SQL by themselves (or they wrap SQL).
+    pub fn is_template_tag(&self) -> bool {
+        self.literal.starts_with("{%") && self.literal.ends_with("%}")
+            || self.literal.starts_with("{#") && self.literal.ends_with("#}")
     }
     pub fn is_numeric(&self) -> bool {
-        let re = Regex::new(r"^([0-9]+|([0-9]*\.[0-9]+))([eE][\+\-]?[0-9]+)?$").unwrap();
-        re.is_match(self.literal.as_str())
+        self.numeric_kind().is_some()
+    }
+    pub fn numeric_kind(&self) -> Option<NumericKind> {
+        let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        let literal = self.literal.as_str();
+        if let Some(hex) = literal
+            .strip_prefix("0x")
+            .or_else(|| literal.strip_prefix("0X"))
+        {
+            let valid = !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit());
+            return valid.then_some(NumericKind::Integer);
+        }
+        let (mantissa, exponent) = match literal.split_once(['e', 'E']) {
+            Some((m, e)) => (m, Some(e)),
+            None => (literal, None),
+        };
+        // `1`, `1.`, `1.5` or `.5`
+        let (integer, fraction) = match mantissa.split_once('.') {
+            Some((i, f)) => (i, Some(f)),
+            None => (mantissa, None),
+        };
+        if !digits(integer)
+            || !fraction.is_none_or(digits)
+            || integer.is_empty() && fraction.is_none_or(str::is_empty)
+        {
+            return None;
+        }
+        match exponent {
+            Some(e) => {
+                let e = e.strip_prefix(['+', '-']).unwrap_or(e);
+                (!e.is_empty() && digits(e)).then_some(NumericKind::Exponential)
+            }
+            None if fraction.is_some() => Some(NumericKind::Decimal),
+            None => Some(NumericKind::Integer),
+        }
     }
     pub fn is_boolean(&self) -> bool {
         self.literal.to_uppercase() == "TRUE" || self.literal.to_uppercase() == "FALSE"
     }
     pub fn is_reserved_keyword(&self) -> bool {
-        for kw in constants::KEYWORDS.iter() {
-            // `PartialEq<&str>` is implemented for String
-            if self.literal.to_uppercase() == *kw {
-                return true;
-            }
-        }
-        false
+        constants::is_reserved_keyword(&self.literal)
     }
     pub fn is_comment(&self) -> bool {
+        if self.is_template_tag() {
+            return true;
+        }
         let mut iter = self.literal.chars();
         let first_char = match iter.next() {
             Some(c) => match c {
@@ -119,10 +248,63 @@ impl Token {
             Some(c) => c,
             None => return false,
         };
-        if first_char == '-' && second_char == '-' || first_char == '/' && second_char == '*' {
-            true
+        first_char == '-' && second_char == '-' || first_char == '/' && second_char == '*'
+    }
+    pub fn comment_kind(&self) -> Option<CommentKind> {
+        if !self.is_comment() {
+            return None;
+        }
+        if self.is_template_tag() {
+            Some(CommentKind::TemplateTag)
+        } else if self.literal.starts_with('#') {
+            Some(CommentKind::Hash)
+        } else if self.literal.starts_with("--") {
+            Some(CommentKind::DoubleDash)
+        } else if self.literal.starts_with("/**") && self.literal != "/**/" {
+            Some(CommentKind::DocBlock)
         } else {
-            false
+            Some(CommentKind::Block)
+        }
+    }
+    /// Returns the content of a comment without `--`, `#`, `/*` or `*/`.
+    /// `None` if the token is not a comment or is a template tag.
+    pub fn comment_text(&self) -> Option<&str> {
+        match self.comment_kind()? {
+            CommentKind::Hash => Some(&self.literal[1..]),
+            CommentKind::DoubleDash => Some(&self.literal[2..]),
+            CommentKind::Block | CommentKind::DocBlock => {
+                Some(self.literal.trim_start_matches("/*").trim_end_matches("*/"))
+            }
+            CommentKind::TemplateTag => None,
+        }
+    }
+    /// Recognizes `bq2cst: off` and `bq2cst: on` in any style of comment
+    /// (e.g. `# bq2cst:off`, `/* bq2cst: ON */`).
+    pub fn format_directive(&self) -> Option<FormatDirective> {
+        let value = self
+            .comment_text()?
+            .trim()
+            .strip_prefix("bq2cst")?
+            .trim_start()
+            .strip_prefix(':')?
+            .trim();
+        if value.eq_ignore_ascii_case("off") {
+            Some(FormatDirective::Off)
+        } else if value.eq_ignore_ascii_case("on") {
+            Some(FormatDirective::On)
+        } else {
+            None
+        }
+    }
+    /// Returns `None` if the literal has no cased letters (e.g. `;`, `123`).
+    pub fn letter_case(&self) -> Option<LetterCase> {
+        let upper = self.literal.chars().any(|c| c.is_uppercase());
+        let lower = self.literal.chars().any(|c| c.is_lowercase());
+        match (upper, lower) {
+            (true, true) => Some(LetterCase::Mixed),
+            (true, false) => Some(LetterCase::Upper),
+            (false, true) => Some(LetterCase::Lower),
+            (false, false) => None,
         }
     }
     fn quoted_by(&self, ch: char) -> bool {
@@ -141,6 +323,10 @@ impl Token {
             line,
             column,
             literal: literal.to_string(),
+            synthetic: false,
+            leading_trivia: String::new(),
+            trailing_trivia: String::new(),
+            offset: None,
         }
     }
     pub fn from_str0(literal: &str) -> Token {
@@ -148,6 +334,10 @@ impl Token {
             line: 0,
             column: 0,
             literal: literal.to_string(),
+            synthetic: false,
+            leading_trivia: String::new(),
+            trailing_trivia: String::new(),
+            offset: None,
         }
     }
 }
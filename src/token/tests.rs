@@ -24,6 +24,17 @@ fn test_is_identifier() {
     assert!(!Token::from_str0("").is_identifier());
 }
 
+#[test]
+fn test_is_reserved_keyword() {
+    assert!(constants::KEYWORDS.windows(2).all(|w| w[0] < w[1]));
+    assert!(Token::from_str0("SELECT").is_reserved_keyword());
+    assert!(Token::from_str0("select").is_reserved_keyword());
+    assert!(Token::from_str0("Within").is_reserved_keyword());
+    assert!(Token::from_str0("ſelect").is_reserved_keyword());
+    assert!(!Token::from_str0("selects").is_reserved_keyword());
+    assert!(!Token::from_str0("INT64").is_reserved_keyword());
+}
+
 #[test]
 fn test_is_numeric() {
     // vald
@@ -40,10 +51,39 @@ fn test_is_numeric() {
     assert!(Token::from_str0("10.11E+10").is_numeric());
     assert!(Token::from_str0("10.11E-10").is_numeric());
 
+    assert!(Token::from_str0("0x1F").is_numeric());
+    assert!(Token::from_str0("10.").is_numeric());
+
     // invalid
     assert!(!Token::from_str0("e10").is_numeric());
     assert!(!Token::from_str0("xxx").is_numeric());
     assert!(!Token::from_str0("x01").is_numeric());
+    assert!(!Token::from_str0("0x").is_numeric());
+    assert!(!Token::from_str0("1.2.3").is_numeric());
+}
+
+#[test]
+fn test_numeric_kind() {
+    let test_cases = vec![
+        ("10", Some(NumericKind::Integer)),
+        ("0x1f", Some(NumericKind::Integer)),
+        ("10.11", Some(NumericKind::Decimal)),
+        ("10.", Some(NumericKind::Decimal)),
+        (".11", Some(NumericKind::Decimal)),
+        ("10e10", Some(NumericKind::Exponential)),
+        ("10.E-1", Some(NumericKind::Exponential)),
+        (".11e+10", Some(NumericKind::Exponential)),
+        ("0x1e10.5", None),
+        ("'10'", None),
+    ];
+    for (literal, expected) in test_cases {
+        assert_eq!(
+            Token::from_str0(literal).numeric_kind(),
+            expected,
+            "{}",
+            literal
+        );
+    }
 }
 
 #[test]
@@ -53,3 +93,79 @@ fn test_is_comment() {
     assert!(Token::from_str0("/*\nxxx\n*/").is_comment());
     assert!(Token::from_str0("# xxx").is_comment());
 }
+
+#[test]
+fn test_comment_kind() {
+    assert_eq!(
+        Token::from_str0("#standardSQL").comment_kind(),
+        Some(CommentKind::Hash)
+    );
+    assert_eq!(
+        Token::from_str0("-- comment").comment_kind(),
+        Some(CommentKind::DoubleDash)
+    );
+    assert_eq!(
+        Token::from_str0("/* comment */").comment_kind(),
+        Some(CommentKind::Block)
+    );
+    assert_eq!(
+        Token::from_str0("/** comment */").comment_kind(),
+        Some(CommentKind::DocBlock)
+    );
+    assert_eq!(
+        Token::from_str0("/**/").comment_kind(),
+        Some(CommentKind::Block)
+    );
+    assert_eq!(
+        Token::from_str0("{% if x %}").comment_kind(),
+        Some(CommentKind::TemplateTag)
+    );
+    assert_eq!(Token::from_str0("{{ x }}").comment_kind(), None);
+    assert_eq!(Token::from_str0("SELECT").comment_kind(), None);
+}
+
+#[test]
+fn test_format_directive() {
+    assert_eq!(
+        Token::from_str0("-- bq2cst: off").format_directive(),
+        Some(FormatDirective::Off)
+    );
+    assert_eq!(
+        Token::from_str0("#bq2cst:on").format_directive(),
+        Some(FormatDirective::On)
+    );
+    assert_eq!(
+        Token::from_str0("/* bq2cst : OFF */").format_directive(),
+        Some(FormatDirective::Off)
+    );
+    assert_eq!(
+        Token::from_str0("-- bq2cst: offline").format_directive(),
+        None
+    );
+    assert_eq!(
+        Token::from_str0("-- note bq2cst: off").format_directive(),
+        None
+    );
+    assert_eq!(Token::from_str0("'bq2cst: off'").format_directive(), None);
+}
+
+#[test]
+fn test_letter_case() {
+    assert_eq!(
+        Token::from_str0("SELECT").letter_case(),
+        Some(LetterCase::Upper)
+    );
+    assert_eq!(
+        Token::from_str0("select").letter_case(),
+        Some(LetterCase::Lower)
+    );
+    assert_eq!(
+        Token::from_str0("Select").letter_case(),
+        Some(LetterCase::Mixed)
+    );
+    assert_eq!(
+        Token::from_str0("INT64").letter_case(),
+        Some(LetterCase::Upper)
+    );
+    assert_eq!(Token::from_str0(";").letter_case(), None);
+}
@@ -1,10 +1,50 @@
-pub fn set_panic_hook() {
-    // When the `console_error_panic_hook` feature is enabled, we can call the
-    // `set_panic_hook` function at least once during initialization, and then
-    // we will get better error messages if our code ever panics.
-    //
-    // For more details see
-    // https://github.com/rustwasm/console_error_panic_hook#readme
-    #[cfg(feature = "console_error_panic_hook")]
-    console_error_panic_hook::set_once();
+#[cfg(test)]
+mod tests;
+
+#[cfg(test)]
+use crate::{cst::Node, lexer::Lexer, parser::Parser};
+use alloc::{vec, vec::Vec};
+
+/// Parses `code`, which is expected to be valid, in unit tests.
+#[cfg(test)]
+pub(crate) fn parse(code: &str) -> Vec<Node> {
+    let tokens = Lexer::new(code.into())
+        .tokenize_code()
+        .expect("Failed to tokenize code.");
+    Parser::new(tokens)
+        .parse_code()
+        .expect("Failed to parse code.")
+}
+
+pub fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut curr = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// Returns the keyword which `word` is likely to be a misspelling of.
+/// `None` is returned if `word` is one of the keywords or nothing is close enough.
+pub fn closest_keyword<'a>(word: &str, keywords: &[&'a str]) -> Option<&'a str> {
+    let word = word.to_uppercase();
+    if keywords.contains(&word.as_str()) {
+        return None;
+    }
+    let threshold = word.chars().count().div_ceil(3);
+    let mut res: Option<(usize, &str)> = None;
+    for kw in keywords {
+        let d = levenshtein_distance(&word, kw);
+        if d <= threshold && res.is_none_or(|(min, _)| d < min) {
+            res = Some((d, kw));
+        }
+    }
+    res.map(|(_, kw)| kw)
 }
@@ -0,0 +1,19 @@
+use super::*;
+
+#[test]
+fn test_levenshtein_distance() {
+    assert_eq!(levenshtein_distance("", ""), 0);
+    assert_eq!(levenshtein_distance("SELECT", "SELECT"), 0);
+    assert_eq!(levenshtein_distance("SELEC", "SELECT"), 1);
+    assert_eq!(levenshtein_distance("GROPU", "GROUP"), 2);
+    assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+}
+
+#[test]
+fn test_closest_keyword() {
+    let keywords = vec!["SELECT", "GROUP", "ORDER", "BY"];
+    assert_eq!(closest_keyword("selec", &keywords), Some("SELECT"));
+    assert_eq!(closest_keyword("GROPU", &keywords), Some("GROUP"));
+    assert_eq!(closest_keyword("group", &keywords), None);
+    assert_eq!(closest_keyword("col", &keywords), None);
+}
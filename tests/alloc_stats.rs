@@ -0,0 +1,22 @@
+// Run `cargo test --features alloc-stats --test alloc_stats` to check `ParseStats.peak_allocation`.
+
+#![cfg(feature = "alloc-stats")]
+
+use bq2cst::document::parse_document;
+use bq2cst::options::ParseOptions;
+use bq2cst::stats::CountingAllocator;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_peak_allocation() {
+    let small = parse_document("SELECT 1;".to_string(), ParseOptions::default()).unwrap();
+    let code = "SELECT a, b, c FROM t WHERE x = 1;\n".repeat(100);
+    let large = parse_document(code, ParseOptions::default()).unwrap();
+    let small_peak = small.stats.peak_allocation.unwrap();
+    let large_peak = large.stats.peak_allocation.unwrap();
+    assert!(0 < small_peak);
+    assert!(small_peak < large_peak);
+    assert!(large.stats.cst_bytes < large_peak);
+}
@@ -0,0 +1,64 @@
+// Runs the parser across `tests/corpus/*.sql`,
+// which consist of examples adapted from the GoogleSQL reference,
+// and reports how many of them can be parsed.
+// Examples are separated by `---` lines.
+// Run `cargo test --test corpus -- --verbose` to list the failed examples.
+
+use bq2cst::error::BQ2CSTResult;
+use bq2cst::lexer::Lexer;
+use bq2cst::parser::Parser;
+use std::fs;
+use std::path::Path;
+
+// raise this when grammar gaps are fixed
+const MIN_COVERAGE: f64 = 97.5;
+
+fn parse(code: &str) -> BQ2CSTResult<()> {
+    let tokens = Lexer::new(code.to_string()).tokenize_code()?;
+    Parser::new(tokens).parse_code()?;
+    Ok(())
+}
+
+fn main() {
+    let verbose = std::env::args().any(|arg| arg == "--verbose");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("corpus");
+    let mut files: Vec<_> = fs::read_dir(root)
+        .expect("Failed to read corpus.")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    files.sort();
+
+    let (mut total, mut passed) = (0, 0);
+    for file in files {
+        let name = file.file_name().unwrap().to_string_lossy().to_string();
+        let code = fs::read_to_string(&file).unwrap();
+        let (mut file_total, mut file_passed) = (0, 0);
+        for example in code.split("\n---\n") {
+            file_total += 1;
+            match parse(example) {
+                Ok(_) => file_passed += 1,
+                Err(e) if verbose => println!(
+                    "----- {} -----\n{}\n{}:{} {}\n",
+                    name,
+                    example.trim(),
+                    e.line,
+                    e.column,
+                    e.message()
+                ),
+                Err(_) => (),
+            }
+        }
+        println!("{}: {}/{}", name, file_passed, file_total);
+        total += file_total;
+        passed += file_passed;
+    }
+    let coverage = 100.0 * passed as f64 / total as f64;
+    println!("coverage: {:.1}% ({}/{})", coverage, passed, total);
+    if coverage < MIN_COVERAGE {
+        eprintln!("coverage is lower than {:.1}%", MIN_COVERAGE);
+        std::process::exit(1);
+    }
+}
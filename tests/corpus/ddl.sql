@@ -0,0 +1,93 @@
+-- Examples adapted from the "Data definition language (DDL) statements" page of the GoogleSQL reference.
+-- Examples are separated by `---` lines.
+CREATE SCHEMA mydataset
+OPTIONS(location = "us", default_table_expiration_days = 3.75, labels = [("label1", "value1"), ("label2", "value2")]);
+---
+CREATE TABLE mydataset.newtable (
+  x INT64 OPTIONS(description = "An optional INTEGER field"),
+  y STRUCT<a ARRAY<STRING> OPTIONS(description = "A repeated STRING field"), b BOOL>
+)
+PARTITION BY _PARTITIONDATE
+OPTIONS(
+  expiration_timestamp = TIMESTAMP "2025-01-01 00:00:00 UTC",
+  partition_expiration_days = 1,
+  description = "a table that expires in 2025, with each partition living for 24 hours",
+  labels = [("org_unit", "development")]
+);
+---
+CREATE TABLE mydataset.newtable (transaction_id INT64, transaction_date DATE)
+PARTITION BY transaction_date
+CLUSTER BY transaction_id
+OPTIONS(partition_expiration_days = 3, description = "a table clustered by transaction_id");
+---
+CREATE TABLE mydataset.newtable (customer_id INT64, date1 DATE)
+PARTITION BY RANGE_BUCKET(customer_id, GENERATE_ARRAY(0, 100, 10));
+---
+CREATE TABLE mydataset.top_words OPTIONS(description = "Top ten words per Shakespeare corpus") AS
+SELECT corpus, ARRAY_AGG(STRUCT(word, word_count) ORDER BY word_count DESC LIMIT 10) AS top_words
+FROM `bigquery-public-data`.samples.shakespeare
+GROUP BY corpus;
+---
+CREATE TABLE mydataset.newtable LIKE mydataset.sourcetable;
+---
+CREATE TABLE mydataset.newtable COPY mydataset.sourcetable;
+---
+CREATE SNAPSHOT TABLE mydataset.mytablesnapshot CLONE mydataset.mytable FOR SYSTEM_TIME AS OF TIMESTAMP_SUB(CURRENT_TIMESTAMP(), INTERVAL 1 HOUR);
+---
+CREATE VIEW `myproject.mydataset.newview`
+OPTIONS(expiration_timestamp = TIMESTAMP_ADD(CURRENT_TIMESTAMP(), INTERVAL 48 HOUR), friendly_name = "newview")
+AS SELECT column_1, column_2, column_3 FROM `myproject.mydataset.mytable`;
+---
+CREATE MATERIALIZED VIEW project.dataset.mv_table
+PARTITION BY DATE(transaction_time)
+CLUSTER BY account_id
+AS SELECT transaction_time, account_id, SUM(amount) AS total FROM project.dataset.base_table GROUP BY 1, 2;
+---
+CREATE EXTERNAL TABLE dataset.CsvTable OPTIONS (format = 'CSV', uris = ['gs://bucket/path1.csv', 'gs://bucket/path2.csv']);
+---
+CREATE EXTERNAL TABLE dataset.AutoHivePartitionedTable
+WITH PARTITION COLUMNS
+OPTIONS (uris = ['gs://bucket/path/*'], format = 'PARQUET', hive_partition_uri_prefix = 'gs://bucket/path', require_hive_partition_filter = false);
+---
+CREATE FUNCTION mydataset.multiplyInputs(x FLOAT64, y FLOAT64)
+RETURNS FLOAT64
+LANGUAGE js
+AS r"""
+  return x*y;
+""";
+---
+CREATE TABLE FUNCTION mydataset.names_by_year(y INT64)
+RETURNS TABLE<name STRING, year INT64, total INT64>
+AS (SELECT year, name, SUM(number) AS total FROM `bigquery-public-data.usa_names.usa_1910_current` WHERE year = y GROUP BY year, name);
+---
+CREATE PROCEDURE mydataset.create_customer()
+BEGIN
+  DECLARE id STRING;
+  SET id = GENERATE_UUID();
+  INSERT INTO mydataset.customers (customer_id) VALUES(id);
+  SELECT FORMAT("Created customer %s", id);
+END;
+---
+CREATE ROW ACCESS POLICY us_filter ON mydataset.table1 GRANT TO ("group:abc@example.com", "user:hello@example.com") FILTER USING (Region = "US");
+---
+CREATE SEARCH INDEX my_index ON dataset.my_table(ALL COLUMNS);
+---
+CREATE VECTOR INDEX my_index ON my_dataset.my_table(embeddings) OPTIONS (index_type = 'IVF', distance_type = 'COSINE');
+---
+ALTER TABLE mydataset.mytable ADD COLUMN A STRING, ADD COLUMN IF NOT EXISTS B GEOGRAPHY;
+---
+ALTER TABLE mydataset.mytable RENAME COLUMN A TO columnA, RENAME COLUMN IF EXISTS B TO columnB;
+---
+ALTER TABLE mydataset.mytable ALTER COLUMN price SET DATA TYPE NUMERIC;
+---
+ALTER TABLE mydataset.mytable SET OPTIONS (expiration_timestamp = TIMESTAMP "2025-02-03 12:34:56", description = "Table that expires seven days from now");
+---
+ALTER SCHEMA mydataset SET DEFAULT COLLATE 'und:ci';
+---
+DROP SCHEMA IF EXISTS mydataset CASCADE;
+---
+DROP TABLE FUNCTION mydataset.my_table_function;
+---
+DROP ALL ROW ACCESS POLICIES ON mydataset.table1;
+---
+UNDROP SCHEMA mydataset;
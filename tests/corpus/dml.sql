@@ -0,0 +1,58 @@
+-- Examples adapted from the "Data manipulation language (DML) statements" page of the GoogleSQL reference.
+-- Examples are separated by `---` lines.
+INSERT dataset.Inventory (product, quantity) VALUES ('top load washer', 10), ('front load washer', 20);
+---
+INSERT dataset.Warehouse (warehouse, state) SELECT * FROM UNNEST([('warehouse #1', 'WA'), ('warehouse #2', 'CA')]);
+---
+INSERT INTO dataset.DetailedInventory VALUES ('top load washer', 10, FALSE, [(CURRENT_DATE, 'comment1')], NULL);
+---
+DELETE dataset.Inventory WHERE quantity = 0;
+---
+DELETE FROM dataset.DetailedInventory WHERE true;
+---
+TRUNCATE TABLE dataset.Inventory;
+---
+UPDATE dataset.Inventory SET quantity = quantity - 10, supply_constrained = DEFAULT WHERE product LIKE '%washer%';
+---
+UPDATE dataset.DetailedInventory
+SET comments = ARRAY(SELECT comment FROM UNNEST(comments) AS comment UNION ALL SELECT (CAST('2016-01-01' AS DATE), 'comment1'))
+WHERE product LIKE '%washer%';
+---
+UPDATE dataset.Inventory i
+SET quantity = i.quantity + n.quantity
+FROM dataset.NewArrivals n
+WHERE i.product = n.product;
+---
+MERGE dataset.DetailedInventory T
+USING dataset.Inventory S
+ON T.product = S.product
+WHEN NOT MATCHED AND quantity < 20 THEN
+  INSERT(product, quantity, supply_constrained, comments)
+  VALUES(product, quantity, true, ARRAY<STRUCT<created DATE, comment STRING>>[(DATE('2016-01-01'), 'comment1')])
+WHEN NOT MATCHED THEN
+  INSERT(product, quantity, supply_constrained)
+  VALUES(product, quantity, false);
+---
+MERGE dataset.NewArrivals T
+USING (SELECT * FROM dataset.NewArrivals WHERE warehouse <> 'warehouse #2') S
+ON T.product = S.product
+WHEN MATCHED AND T.warehouse = 'warehouse #1' THEN
+  UPDATE SET quantity = T.quantity + 20
+WHEN MATCHED THEN
+  DELETE
+WHEN NOT MATCHED BY SOURCE THEN
+  DELETE;
+---
+MERGE dataset.Inventory T
+USING dataset.NewArrivals S
+ON FALSE
+WHEN NOT MATCHED AND product LIKE '%washer%' THEN
+  INSERT (product, quantity) VALUES(product, quantity)
+WHEN NOT MATCHED BY SOURCE AND product LIKE '%washer%' THEN
+  DELETE;
+---
+MERGE dataset.Inventory T
+USING dataset.NewArrivals S
+ON T.product = S.product
+WHEN NOT MATCHED THEN
+  INSERT ROW;
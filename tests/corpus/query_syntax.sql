@@ -0,0 +1,69 @@
+-- Examples adapted from the "Query syntax" page of the GoogleSQL reference.
+-- Examples are separated by `---` lines.
+SELECT * FROM Roster;
+---
+SELECT s.SchoolID FROM Roster AS s;
+---
+SELECT * EXCEPT (order_id) FROM orders;
+---
+SELECT * REPLACE ("widget" AS item_name) FROM orders;
+---
+SELECT AS STRUCT 1 a, 2 b;
+---
+SELECT AS VALUE STRUCT(1 AS a, 2 AS b) xyz;
+---
+SELECT DISTINCT SchoolID FROM Roster;
+---
+SELECT * FROM UNNEST([1, 2, 3]) AS number WITH OFFSET AS offset;
+---
+SELECT * FROM t FOR SYSTEM_TIME AS OF TIMESTAMP_SUB(CURRENT_TIMESTAMP(), INTERVAL 1 HOUR);
+---
+SELECT * FROM Roster JOIN TeamMascot ON Roster.SchoolID = TeamMascot.SchoolID;
+---
+SELECT * FROM Roster FULL OUTER JOIN TeamMascot USING (SchoolID);
+---
+SELECT * FROM Roster, UNNEST(Roster.items) AS item;
+---
+SELECT * FROM Produce TABLESAMPLE SYSTEM (10 PERCENT);
+---
+SELECT * FROM Produce PIVOT(SUM(sales) FOR quarter IN ('Q1', 'Q2', 'Q3', 'Q4'));
+---
+SELECT * FROM Produce UNPIVOT(sales FOR quarter IN (Q1, Q2, Q3, Q4));
+---
+SELECT LastName, SUM(PointsScored) FROM PlayerStats GROUP BY LastName;
+---
+SELECT product_type, product_name, SUM(product_count) AS product_sum
+FROM Products
+GROUP BY ROLLUP(product_type, product_name);
+---
+SELECT product_type, SUM(product_count) FROM Products GROUP BY ALL;
+---
+SELECT item, RANK() OVER (PARTITION BY category ORDER BY purchases DESC) AS rank
+FROM Produce
+WHERE Produce.category = 'vegetable'
+QUALIFY rank <= 3;
+---
+SELECT item, purchases, category, LAST_VALUE(item) OVER (d) AS most_popular
+FROM Produce
+WINDOW a AS (PARTITION BY category), b AS (a ORDER BY purchases), c AS (b ROWS BETWEEN 2 PRECEDING AND 2 FOLLOWING), d AS (c);
+---
+SELECT * FROM UNNEST(ARRAY<INT64>[1, 2, 3]) AS number
+UNION ALL
+SELECT 1;
+---
+SELECT * FROM t1 INTERSECT DISTINCT SELECT * FROM t2;
+---
+WITH RECURSIVE T1 AS ((SELECT 1 AS n) UNION ALL (SELECT n + 1 AS n FROM T1 WHERE n < 3))
+SELECT n FROM T1;
+---
+SELECT * FROM Roster ORDER BY LastName DESC NULLS LAST LIMIT 10 OFFSET 2;
+---
+SELECT ARRAY(SELECT AS STRUCT 1 a, 2 b);
+---
+SELECT * FROM ML.PREDICT(MODEL `mydataset.mymodel`, (SELECT * FROM t));
+---
+FROM Produce
+|> WHERE sales > 0
+|> AGGREGATE SUM(sales) AS total_sales GROUP BY item;
+---
+SELECT * FROM GRAPH_TABLE(FinGraph MATCH (p:Person) COLUMNS (p.name));
@@ -0,0 +1,76 @@
+-- Examples adapted from the "Procedural language" page of the GoogleSQL reference.
+-- Examples are separated by `---` lines.
+DECLARE x INT64 DEFAULT 0;
+---
+DECLARE d DATE DEFAULT (SELECT MAX(d) FROM mydataset.tbl);
+---
+SET (a, b, c) = (1 + 3, 'foo', false);
+---
+SET @@dataset_project_id = 'MyProject';
+---
+EXECUTE IMMEDIATE 'SELECT ? * (? + 2)' INTO y USING 1, 3;
+---
+BEGIN
+  SELECT 1;
+EXCEPTION WHEN ERROR THEN
+  SELECT @@error.message, @@error.stack_trace, @@error.statement_text, @@error.formatted_stack_trace;
+END;
+---
+CASE
+  WHEN EXISTS(SELECT 1 FROM schema.products_a WHERE product_id = target_product_id) THEN
+    SELECT 'found product in products_a table';
+  ELSE
+    SELECT 'did not find product';
+END CASE;
+---
+IF EXISTS(SELECT 1 FROM schema.products WHERE product_id = target_product_id) THEN
+  SELECT CONCAT('found product ', CAST(target_product_id AS STRING));
+ELSEIF EXISTS(SELECT 1 FROM schema.more_products WHERE product_id = target_product_id) THEN
+  SELECT CONCAT('found product from more_products table', CAST(target_product_id AS STRING));
+ELSE
+  SELECT CONCAT('did not find product ', CAST(target_product_id AS STRING));
+END IF;
+---
+LOOP
+  SET x = x + 1;
+  IF x >= 10 THEN
+    LEAVE;
+  END IF;
+END LOOP;
+---
+REPEAT
+  SET x = x + 1;
+  UNTIL x >= 3
+END REPEAT;
+---
+WHILE x < 10 DO
+  SET x = x + 1;
+  CONTINUE;
+END WHILE;
+---
+label_1: BEGIN
+  SELECT 1;
+  BREAK label_1;
+END label_1;
+---
+FOR record IN (SELECT word, word_count FROM bigquery-public-data.samples.shakespeare LIMIT 5)
+DO
+  SELECT record.word, record.word_count;
+END FOR;
+---
+BEGIN TRANSACTION;
+---
+COMMIT TRANSACTION;
+---
+RAISE USING MESSAGE = 'Found product';
+---
+CALL mydataset.create_customer();
+---
+EXPORT DATA OPTIONS(uri = 'gs://bucket/folder/*.csv', format = 'CSV', overwrite = true, header = true, field_delimiter = ';') AS
+SELECT field1, field2 FROM mydataset.table1 ORDER BY field1 LIMIT 10;
+---
+LOAD DATA INTO mydataset.table1 FROM FILES(format = 'AVRO', uris = ['gs://bucket/path/file.avro']);
+---
+ASSERT (SELECT COUNT(*) > 0 FROM UNNEST([1, 2, 3, -1]) AS x WHERE x > 0) AS 'No positive numbers';
+---
+GRANT `roles/bigquery.dataViewer` ON SCHEMA myProject.myDataset TO "user:raha@example-pet-store.com", "user:sasha@example-pet-store.com";
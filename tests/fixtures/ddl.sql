@@ -0,0 +1,10 @@
+CREATE OR REPLACE TABLE dataset.t (
+  id INT64 NOT NULL,
+  tags ARRAY<STRUCT<key STRING, value ARRAY<INT64>>>,
+)
+PARTITION BY DATE(created_at)
+OPTIONS (description = 'example');
+
+CREATE TEMP FUNCTION add_one(x INT64) RETURNS INT64 AS (x + 1);
+
+DROP TABLE IF EXISTS dataset.t;
@@ -0,0 +1,11 @@
+DECLARE x INT64 DEFAULT 0;
+BEGIN
+  SET x = x + 1; -- increment
+  IF x > 0 THEN
+    SELECT x;
+  ELSE
+    RAISE USING MESSAGE = 'unexpected';
+  END IF;
+EXCEPTION WHEN ERROR THEN
+  SELECT @@error.message;
+END;
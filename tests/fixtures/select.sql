@@ -0,0 +1,16 @@
+-- set operators and star modifiers
+SELECT * EXCEPT(a) REPLACE (b + 1 AS b) FROM `project.dataset.table` AS t
+EXCEPT DISTINCT
+SELECT * FROM u;
+
+WITH cte AS (SELECT 1 AS x)
+SELECT
+  x,
+  SUM(x) OVER (PARTITION BY x ORDER BY x ROWS UNBOUNDED PRECEDING) AS total,
+FROM cte
+WHERE x BETWEEN 0 AND 10
+GROUP BY x
+HAVING COUNT(*) > 1
+QUALIFY ROW_NUMBER() OVER () = 1
+ORDER BY x DESC
+LIMIT 10;
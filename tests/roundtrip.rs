@@ -0,0 +1,208 @@
+// Property-based tests which write random token sequences out as code
+// and check that the lexer reads them back as they were
+// (and that the tokens with trivia reproduce the code).
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use bq2cst::lexer::Lexer;
+use bq2cst::options::ParseOptions;
+use proptest::prelude::*;
+
+// a list of tokens written without space (e.g. `r` and `'...'` of `r'...'`)
+type Chunk = Vec<String>;
+
+fn identifier() -> impl Strategy<Value = Chunk> {
+    "[a-zA-Z_][a-zA-Z0-9_]{0,8}".prop_map(|s| vec![s])
+}
+
+fn escape() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("\\\\".to_string()),
+        Just("\\'".to_string()),
+        Just("\\\"".to_string()),
+        Just("\\`".to_string()),
+        Just("\\n".to_string()),
+        Just("\\t".to_string()),
+        "\\\\x[0-9a-fA-F]{2}",
+        "\\\\u[0-9a-fA-F]{4}",
+        "\\\\[0-3][0-7]{2}",
+    ]
+}
+
+/// Contents of quoted literals without unescaped quotes.
+fn contents(newline: bool) -> impl Strategy<Value = String> {
+    let plain = if newline {
+        "[a-z0-9 .,;()\\-#\n]"
+    } else {
+        "[a-z0-9 .,;()\\-#]"
+    };
+    prop::collection::vec(prop_oneof![plain.prop_map(String::from), escape()], 0..8)
+        .prop_map(|parts| parts.concat())
+}
+
+fn quoted_identifier() -> impl Strategy<Value = Chunk> {
+    // escape sequences (e.g. `` \` ``, `\u00e9`) are allowed as in string literals
+    let plain = "[a-z0-9 .,;()\\-#]";
+    prop::collection::vec(prop_oneof![plain.prop_map(String::from), escape()], 1..8)
+        .prop_map(|parts| vec![format!("`{}`", parts.concat())])
+}
+
+fn string_literal() -> impl Strategy<Value = Chunk> {
+    let prefix = prop_oneof![
+        Just(None),
+        Just(Some("r")),
+        Just(Some("b")),
+        Just(Some("rb")),
+        Just(Some("BR")),
+    ];
+    let body = prop_oneof![
+        contents(false).prop_map(|s| format!("'{}'", s)),
+        contents(false).prop_map(|s| format!("\"{}\"", s)),
+        contents(true).prop_map(|s| format!("'''{}'''", s)),
+        contents(true).prop_map(|s| format!("\"\"\"{}\"\"\"", s)),
+    ];
+    (prefix, body).prop_map(|(prefix, body)| match prefix {
+        Some(p) => vec![p.to_string(), body],
+        None => vec![body],
+    })
+}
+
+fn numeric_literal() -> impl Strategy<Value = Chunk> {
+    prop_oneof![
+        "[0-9]{1,5}",
+        "[0-9]{1,3}\\.[0-9]{0,3}",
+        "\\.[0-9]{1,3}",
+        "[0-9]{1,3}[eE][+-]?[0-9]{1,2}",
+        "0[xX][0-9a-fA-F]{1,4}",
+    ]
+    .prop_map(|s| vec![s])
+}
+
+fn symbol() -> impl Strategy<Value = Chunk> {
+    prop::sample::select(vec![
+        "(",
+        ")",
+        "[",
+        "]",
+        ",",
+        ".",
+        ";",
+        "*",
+        "/",
+        "+",
+        "-",
+        "~",
+        "&",
+        "|",
+        "^",
+        "||",
+        "<<",
+        ">>",
+        "=",
+        "!=",
+        "<>",
+        "<",
+        "<=",
+        ">",
+        ">=",
+        "=>",
+        "?",
+        "@param",
+        "@@time_zone",
+    ])
+    .prop_map(|s| vec![s.to_string()])
+}
+
+fn comment() -> impl Strategy<Value = Chunk> {
+    prop_oneof![
+        "-- [a-z0-9 ']{0,8}\n".prop_map(|s| s.trim_end().to_string()),
+        "# [a-z0-9 ']{0,8}\n".prop_map(|s| s.trim_end().to_string()),
+        "/\\*[a-z0-9 '\n]{0,8}\\*/",
+    ]
+    .prop_map(|s| vec![s])
+}
+
+fn chunks() -> impl Strategy<Value = Vec<(Chunk, &'static str)>> {
+    let chunk = prop_oneof![
+        identifier(),
+        quoted_identifier(),
+        string_literal(),
+        numeric_literal(),
+        symbol(),
+        comment(),
+    ];
+    let separator = prop::sample::select(vec![" ", "  ", "\n", " \n\t"]);
+    prop::collection::vec((chunk, separator), 0..20)
+}
+
+/// Writes out the chunks and returns the code and the expected tokens.
+fn unlex(chunks: &[(Chunk, &str)]) -> (String, Vec<(usize, usize, String)>) {
+    let mut code = String::new();
+    let mut tokens = Vec::new();
+    let (mut line, mut column) = (1, 1);
+    for (chunk, separator) in chunks {
+        for literal in chunk {
+            tokens.push((line, column, literal.clone()));
+            for ch in literal.chars() {
+                if ch == '\n' {
+                    line += 1;
+                    column = 1;
+                } else {
+                    column += 1;
+                }
+            }
+            code.push_str(literal);
+        }
+        // line comments need a line break
+        let separator = if chunk[0].starts_with("--") || chunk[0].starts_with('#') {
+            "\n"
+        } else {
+            separator
+        };
+        for ch in separator.chars() {
+            if ch == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        code.push_str(separator);
+    }
+    (code, tokens)
+}
+
+proptest! {
+    #[test]
+    fn test_roundtrip(chunks in chunks()) {
+        let (code, expected) = unlex(&chunks);
+        let tokens = Lexer::new(code.clone())
+            .tokenize_code()
+            .map_err(|e| TestCaseError::fail(format!("{:?}", e)))?;
+        let actual: Vec<_> = tokens
+            .into_iter()
+            .filter(|t| !t.literal.is_empty()) // EOF
+            .map(|t| (t.line, t.column, t.literal))
+            .collect();
+        prop_assert_eq!(actual, expected, "code: {:?}", code);
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_trivia(chunks in chunks()) {
+        let (code, _) = unlex(&chunks);
+        let options = ParseOptions {
+            keep_trivia: true,
+            ..Default::default()
+        };
+        let tokens = Lexer::with_options(code.clone(), options)
+            .tokenize_code()
+            .map_err(|e| TestCaseError::fail(format!("{:?}", e)))?;
+        let actual: String = tokens
+            .iter()
+            .map(|t| format!("{}{}{}", t.leading_trivia(), t.literal, t.trailing_trivia()))
+            .collect();
+        prop_assert_eq!(actual, code);
+    }
+}
@@ -0,0 +1,67 @@
+// CST snapshots of `tests/fixtures/*.sql`, stored in `tests/snapshots/*.snap`.
+// To create or update the snapshots, run `make snapshot`
+// and review the diff before committing it.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use bq2cst::lexer::Lexer;
+use bq2cst::parser::Parser;
+use difference::Changeset;
+use std::fs;
+use std::path::Path;
+
+fn render(code: &str) -> String {
+    let tokens = Lexer::new(code.to_string())
+        .tokenize_code()
+        .expect("Failed to tokenize code.");
+    let stmts = Parser::new(tokens)
+        .parse_code()
+        .expect("Failed to parse code.");
+    stmts
+        .iter()
+        .map(|stmt| stmt.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_snapshots() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests");
+    let update = std::env::var("UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1");
+    let mut fixtures: Vec<_> = fs::read_dir(root.join("fixtures"))
+        .expect("Failed to read fixtures.")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty());
+
+    let mut failures = Vec::new();
+    for fixture in fixtures {
+        let name = fixture.file_stem().unwrap().to_string_lossy().to_string();
+        println!("========== {} ==========", name);
+        let code = fs::read_to_string(&fixture).unwrap();
+        let result = render(&code);
+        let snapshot = root.join("snapshots").join(format!("{}.snap", name));
+        if update {
+            fs::write(&snapshot, &result).unwrap();
+            continue;
+        }
+        match fs::read_to_string(&snapshot) {
+            Ok(expected) if expected == result => (),
+            Ok(expected) => {
+                println!("{}\n", Changeset::new(&expected, &result, "\n"));
+                failures.push(name);
+            }
+            Err(_) => {
+                println!("snapshot not found: {}\n", snapshot.display());
+                failures.push(name);
+            }
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "snapshots do not match: {:?} (run with UPDATE_SNAPSHOTS=1 to update them)",
+        failures
+    );
+}
@@ -0,0 +1,175 @@
+// https://github.com/rustwasm/wasm-bindgen/issues/2882
+#![allow(non_upper_case_globals)]
+
+mod types;
+
+use bq2cst::{cst, lexer, options, parser, split};
+use serde::Serialize;
+use serde_wasm_bindgen::Serializer;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(skip_typescript)]
+pub fn parse(code: String) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let l = lexer::Lexer::new(code);
+    let s = Serializer::json_compatible();
+    let mut p = parser::Parser::new(match l.tokenize_code() {
+        Ok(tokens) => tokens,
+        Err(bq2cst_error) => {
+            return Err(bq2cst_error
+                .serialize(&s)
+                .expect("Problem converting error struct to json."))
+        }
+    });
+    let stmts = match p.parse_code() {
+        Ok(stmts) => stmts,
+        Err(bq2cst_error) => {
+            return Err(bq2cst_error
+                .serialize(&s)
+                .expect("Problem converting error struct to json."))
+        }
+    };
+    Ok(stmts
+        .serialize(&s)
+        .expect("Problem converting stmts to json."))
+}
+
+/// Same as `parse` but the result has the shape of `cst_version`
+/// (the latest `cst::CST_VERSION` by default).
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_with_version(code: String, cst_version: Option<u32>) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let s = Serializer::json_compatible();
+    let cst_version = cst_version.unwrap_or(cst::CST_VERSION);
+    let stmts = lexer::Lexer::new(code)
+        .tokenize_code()
+        .and_then(|tokens| parser::Parser::new(tokens).parse_code());
+    let stmts = match stmts {
+        Ok(stmts) => stmts,
+        Err(bq2cst_error) => {
+            return Err(bq2cst_error
+                .serialize(&s)
+                .expect("Problem converting error struct to json."))
+        }
+    };
+    match cst::CstOutput::new(stmts, cst_version) {
+        Some(output) => Ok(output
+            .serialize(&s)
+            .expect("Problem converting stmts to json.")),
+        None => Err(JsValue::from_str(&format!(
+            "Unknown cst_version: {}",
+            cst_version
+        ))),
+    }
+}
+
+/// Same as `parse` but the statements are wrapped in `cst::Script`,
+/// which also holds the file-leading comments and the blank lines between statements.
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_script(code: String) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let s = Serializer::json_compatible();
+    let script = lexer::Lexer::new(code)
+        .tokenize_code()
+        .and_then(|tokens| parser::Parser::new(tokens).parse_script());
+    match script {
+        Ok(script) => Ok(script
+            .serialize(&s)
+            .expect("Problem converting script to json.")),
+        Err(bq2cst_error) => Err(bq2cst_error
+            .serialize(&s)
+            .expect("Problem converting error struct to json.")),
+    }
+}
+
+/// Same as `parse` but `is_cancelled` is called before parsing each statement.
+/// If it returns a truthy value, the parse is aborted with the error `P006`.
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_with_cancellation(
+    code: String,
+    is_cancelled: &js_sys::Function,
+) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let s = Serializer::json_compatible();
+    let is_cancelled = is_cancelled.clone();
+    let stmts = lexer::Lexer::new(code).tokenize_code().and_then(|tokens| {
+        parser::Parser::new(tokens)
+            .with_cancellation(move || {
+                is_cancelled
+                    .call0(&JsValue::NULL)
+                    .is_ok_and(|v| v.is_truthy())
+            })
+            .parse_code()
+    });
+    match stmts {
+        Ok(stmts) => Ok(stmts
+            .serialize(&s)
+            .expect("Problem converting stmts to json.")),
+        Err(bq2cst_error) => Err(bq2cst_error
+            .serialize(&s)
+            .expect("Problem converting error struct to json.")),
+    }
+}
+
+/// Same as `parse` but `options` (a partial `options::ParseOptions`) are applied,
+/// e.g. `{ max_input_bytes: 1000000 }` to refuse huge code.
+#[wasm_bindgen(skip_typescript)]
+pub fn parse_with_options(code: String, options: JsValue) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let s = Serializer::json_compatible();
+    let options: options::ParseOptions = serde_wasm_bindgen::from_value(options)?;
+    let stmts = lexer::Lexer::with_options(code, options.clone())
+        .tokenize_code()
+        .and_then(|tokens| parser::Parser::with_options(tokens, options).parse_code());
+    match stmts {
+        Ok(stmts) => Ok(stmts
+            .serialize(&s)
+            .expect("Problem converting stmts to json.")),
+        Err(bq2cst_error) => Err(bq2cst_error
+            .serialize(&s)
+            .expect("Problem converting error struct to json.")),
+    }
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn tokenize(code: String) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let l = lexer::Lexer::new(code);
+    let s = Serializer::json_compatible();
+    let tokens = match l.tokenize_code() {
+        Ok(tokens) => tokens,
+        Err(bq2cst_error) => {
+            return Err(bq2cst_error
+                .serialize(&s)
+                .expect("Problem converting error struct to json."))
+        }
+    };
+    Ok(tokens
+        .serialize(&s)
+        .expect("Problem converting tokens to json."))
+}
+
+#[wasm_bindgen(skip_typescript)]
+pub fn split_statements(code: String) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let s = Serializer::json_compatible();
+    match split::split_statements(&code) {
+        Ok(stmts) => Ok(stmts
+            .serialize(&s)
+            .expect("Problem converting statements to json.")),
+        Err(bq2cst_error) => Err(bq2cst_error
+            .serialize(&s)
+            .expect("Problem converting error struct to json.")),
+    }
+}
+
+fn set_panic_hook() {
+    // When the `console_error_panic_hook` feature is enabled, we can call the
+    // `set_panic_hook` function at least once during initialization, and then
+    // we will get better error messages if our code ever panics.
+    //
+    // For more details see
+    // https://github.com/rustwasm/console_error_panic_hook#readme
+    #[cfg(feature = "console_error_panic_hook")]
+    console_error_panic_hook::set_once();
+}
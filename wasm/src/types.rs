@@ -4,6 +4,47 @@ use wasm_bindgen::prelude::*;
 const NODES: &'static str = r#"
 export function parse(code: string): UnknownNode[];
 export function tokenize(code: string): Token[];
+export function split_statements(code: string): [Span, string][];
+export function parse_with_version(code: string, cst_version?: 2): VersionedCst;
+export function parse_with_version(code: string, cst_version: 1): UnknownNode[];
+export function parse_script(code: string): Script;
+export function parse_with_cancellation(code: string, is_cancelled: () => boolean): UnknownNode[];
+export function parse_with_options(code: string, options: ParseOptions): UnknownNode[];
+
+export type ParseOptions = {
+  header_lines?: number;
+  allow_invalid_escapes?: boolean;
+  jinja_templates?: boolean;
+  placeholders?: boolean;
+  nested_comments?: boolean;
+  hints?: boolean;
+  disabled_features?: string[];
+  max_input_bytes?: number;
+  max_tokens?: number;
+  max_nodes?: number;
+  max_depth?: number;
+  normalize_identifiers?: boolean;
+  strict_declare?: boolean;
+  keep_trivia?: boolean;
+};
+
+export type VersionedCst = {
+  cst_version: 2;
+  stmts: UnknownNode[];
+};
+
+export type Script = {
+  leading_comments: Comment[];
+  stmts: UnknownNode[];
+  blank_lines: number[];
+  layouts: OriginalLayout[];
+  eof?: Token;
+};
+
+export type OriginalLayout = {
+  single_line: boolean;
+  own_line_clauses: string[];
+};
 
 export type UnknownNode =
   | AccessOperator
@@ -67,9 +108,11 @@ export type UnknownNode =
   | GroupedExpr
   | GroupedExprs
   | GroupedIdentWithOptions
+  | GroupedPattern
   | GroupedStatement
   | GroupedTypeDeclarationOrConstraints
   | GroupedType
+  | Hint
   | Identifier
   | IfStatement
   | IdentWithOptions
@@ -89,6 +132,8 @@ export type UnknownNode =
   | LimitClause
   | LoadStatement
   | LoopStatement
+  | MatchRecognizeClause
+  | MatchRecognizeConfig
   | MergeStatement
   | MultiTokenIdentifier
   | NullLiteral
@@ -96,6 +141,7 @@ export type UnknownNode =
   | OverClause
   | OverwritePartitionsClause
   | Parameter
+  | PatternDefinition
   | PivotOperator
   | PivotConfig
   | RaiseStatement
@@ -106,6 +152,7 @@ export type UnknownNode =
   | SelectStatement
   | SetOperator
   | SetStatement
+  | SetSystemVariableStatement
   | SingleTokenStatement
   | StringLiteral
   | StructLiteral
@@ -120,6 +167,8 @@ export type UnknownNode =
   | TypeDeclaration
   | UnaryOperator
   | UndropStatement
+  | Unknown
+  | UnparsedStatement
   | UnpivotConfig
   | UnpivotOperator
   | UpdateStatement
@@ -139,14 +188,35 @@ export type Token = {
   line: number;
   column: number;
   literal: string;
+  synthetic?: true;
+  // whitespace before the token (from the first line break after the previous token)
+  // only with `keep_trivia`
+  leading_trivia?: string;
+  // whitespace after the token up to the next line break
+  trailing_trivia?: string;
+  // byte offset (only EOF returned by the lexer)
+  offset?: number;
+};
+
+// `end_*` points to the next of the last character
+export type Span = {
+  start_line: number;
+  start_column: number;
+  end_line: number;
+  end_column: number;
 };
 
 interface BaseNode {
   token: Token | null;
   node_type: string;
+  // placed between `-- bq2cst: off` and `-- bq2cst: on`
+  format_exempt?: true;
+  // `-- key: value` comments placed immediately before a statement
+  annotations?: { [key: string]: string[] };
   children: {
-    leading_comments?: { NodeVec: Comment[] };
-    trailing_comments?: { NodeVec: Comment[] };
+    // Template is included only if `{% ... %}` or `{# ... #}` is used
+    leading_comments?: { NodeVec: (Comment | Template)[] };
+    trailing_comments?: { NodeVec: (Comment | Template)[] };
   };
 }
 
@@ -160,6 +230,8 @@ export type CallingFunctionGeneral = Expr & {
     distinct?: NodeChild;
     args?: { NodeVec: (Expr & UnknownNode | SelectStatement)[] };
     ignore_nulls?: NodeVecChild;
+    // e.g. JSON_VALUE(json, '$.a' RETURNING INT64)
+    returning?: NodeChild;
     orderby?: NodeChild;
     limit?: NodeChild;
     having?: NodeChild;
@@ -184,6 +256,8 @@ export type FromItemExpr = Expr & {
     with_offset: NodeChild;
     pivot?: NodeChild;
     unpivot?: NodeChild;
+    match_recognize?: NodeChild;
+    hint?: { Node: Hint };
   };
 };
 
@@ -218,6 +292,8 @@ export type XXXStatement = BaseNode & {
   token: Token;
   children: {
     semicolon?: { Node: Symbol_ };
+    hint?: { Node: Hint };
+    statement_options?: NodeChild;
   };
 };
 
@@ -580,6 +656,7 @@ export type CreateFunctionStatement = XXXStatement & {
     remote?: NodeChild;
     determinism?: NodeVecChild;
     language?: NodeChild;
+    with_connection?: NodeChild;
     options?: NodeChild;
     as?: NodeChild;
   };
@@ -622,7 +699,9 @@ export type CreateProcedureStatement = XXXStatement & {
 export type CreateReservationStatement = XXXStatement & {
   node_type: "CreateReservationStatement";
   children: {
+    or_replace?: NodeVecChild;
     what: NodeChild;
+    if_not_exists?: NodeVecChild;
     ident: NodeChild;
     as?: NodeChild;
     json?: NodeChild;
@@ -649,6 +728,7 @@ export type CreateRowAccessPolicyStatement = XXXStatement & {
 export type CreateSchemaStatement = XXXStatement & {
   node_type: "CreateSchemaStatement";
   children: {
+    or_replace?: NodeVecChild;
     external?: NodeChild;
     what: NodeChild;
     if_not_exists?: NodeVecChild;
@@ -726,9 +806,9 @@ export type DeclareStatement = XXXStatement & {
 export type DeleteStatement = XXXStatement & {
   node_type: "DeleteStatement";
   children: {
-    from: NodeChild;
+    from?: NodeChild;
     table_name: NodeChild;
-    where: NodeChild;
+    where?: NodeChild;
   };
 };
 
@@ -764,6 +844,7 @@ export type DropStatement = XXXStatement & {
   children: {
     external?: NodeChild;
     materialized?: NodeChild;
+    snapshot?: NodeChild;
     table?: NodeChild;
     what: NodeChild;
     if_exists?: NodeVecChild;
@@ -908,6 +989,15 @@ export type GroupedIdentWithOptions = BaseNode & {
   };
 };
 
+export type GroupedPattern = BaseNode & {
+  token: Token;
+  node_type: "GroupedPattern";
+  children: {
+    patterns: NodeVecChild;
+    rparen: NodeChild;
+  };
+};
+
 export type GroupedStatement = FromItemExpr &
   XXXStatement & {
     node_type: "GroupedStatement";
@@ -936,6 +1026,11 @@ export type GroupedType = BaseNode & {
   };
 };
 
+export type Hint = BaseNode & {
+  token: Token;
+  node_type: "Hint";
+};
+
 export type Identifier = IdentifierGeneral & {
   node_type: "Identifier";
 };
@@ -1099,6 +1194,31 @@ export type LoadStatement = XXXStatement & {
   };
 };
 
+export type MatchRecognizeClause = BaseNode & {
+  token: Token;
+  node_type: "MatchRecognizeClause";
+  children: {
+    config: NodeChild;
+    as?: NodeChild;
+    alias?: NodeChild;
+  };
+};
+
+export type MatchRecognizeConfig = BaseNode & {
+  token: Token;
+  node_type: "MatchRecognizeConfig";
+  children: {
+    partitionby?: NodeChild;
+    orderby?: NodeChild;
+    measures?: NodeChild;
+    after_match_skip?: NodeVecChild;
+    pattern: NodeChild;
+    define: NodeChild;
+    options?: NodeChild;
+    rparen: NodeChild;
+  };
+};
+
 export type MergeStatement = XXXStatement & {
   node_type: "MergeStatement";
   children: {
@@ -1146,6 +1266,16 @@ export type Parameter = IdentifierGeneral & {
   node_type: "Parameter";
 };
 
+export type PatternDefinition = BaseNode & {
+  token: Token;
+  node_type: "PatternDefinition";
+  children: {
+    as: NodeChild;
+    expr: NodeChild;
+    comma?: NodeChild;
+  };
+};
+
 export type PivotOperator = BaseNode & {
   token: Token;
   node_type: "PivotOperator";
@@ -1240,6 +1370,8 @@ export type SetOperator = XXXStatement & {
     distinct_or_all: NodeChild;
     left: { Node: SetOperator | SelectStatement | GroupedStatement };
     right: { Node: SetOperator | SelectStatement | GroupedStatement };
+    orderby?: NodeChild;
+    limit?: NodeChild;
   };
 };
 
@@ -1250,6 +1382,13 @@ export type SetStatement = XXXStatement & {
   };
 };
 
+export type SetSystemVariableStatement = XXXStatement & {
+  node_type: "SetSystemVariableStatement";
+  children: {
+    expr: NodeChild;
+  };
+};
+
 export type SingleTokenStatement = XXXStatement & {
   node_type: "SingleTokenStatement";
 };
@@ -1365,6 +1504,21 @@ export type UndropStatement = XXXStatement & {
   };
 };
 
+// a token of UnparsedStatement
+export type Unknown = BaseNode & {
+  token: Token;
+  node_type: "Unknown";
+};
+
+// returned only when recovering from errors
+export type UnparsedStatement = XXXStatement & {
+  node_type: "UnparsedStatement";
+  children: {
+    // the rest of the tokens in order of appearance
+    unknown?: { NodeVec: (Unknown | Comment | Template)[] };
+  };
+};
+
 export type UnpivotConfig = BaseNode & {
   token: Token;
   node_type: "UnpivotConfig";
@@ -1490,6 +1644,7 @@ export type WithQuery = BaseNode & {
   token: Token;
   node_type: "WithQuery";
   children: {
+    columns?: { Node: GroupedExprs };
     as: { Node: Keyword };
     stmt: { Node: GroupedStatement };
     comma: NodeChild;
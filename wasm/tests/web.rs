@@ -0,0 +1,20 @@
+// to run the following tests
+// you have to run `wasm-pack test --node`
+
+#![cfg(target_arch = "wasm32")]
+
+use bq2cst_wasm as bq2cst;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn pass() {
+    bq2cst::parse("select 1;".to_string()).expect("Failed to parse code.");
+    bq2cst::tokenize("select 1;".to_string()).expect("Failed to tokenize code.");
+    bq2cst::parse_with_version("select 1;".to_string(), Some(1)).expect("Failed to parse code.");
+    bq2cst::parse_script("-- header\n\nselect 1;".to_string()).expect("Failed to parse code.");
+    let is_cancelled = js_sys::Function::new_no_args("return false;");
+    bq2cst::parse_with_cancellation("select 1;".to_string(), &is_cancelled)
+        .expect("Failed to parse code.");
+    let options = js_sys::JSON::parse("{\"max_nodes\": 1}").unwrap();
+    assert!(bq2cst::parse_with_options("select 1;".to_string(), options).is_err());
+}